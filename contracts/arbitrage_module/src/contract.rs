@@ -1,99 +1,500 @@
-use cosmwasm_std::{DepsMut, Deps, Env, MessageInfo, Response, Uint128, Decimal, StdResult};
+use cosmwasm_std::{
+    DepsMut, Deps, Env, MessageInfo, Response, Uint128, Decimal, StdResult, StdError, Addr, Api, BankMsg, WasmMsg,
+    to_json_binary, coins,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use equilibria_smart_contracts::config::{ConfigValidator, ValidateConfigResponse};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::migration;
+use equilibria_smart_contracts::ownership;
+use equilibria_smart_contracts::roles::{self, Role};
+use equilibria_smart_contracts::dex::{Asset, AssetInfo, DexPair};
+use equilibria_smart_contracts::oracle::{calculate_optimal_trade_size, PoolModel};
 use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-use crate::ArbitrageOpportunityResponse;
+use crate::{ArbitrageOpportunityResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, LegacyConfigMsg, RewardPoolResponse};
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub eqa_token: Addr,
+    pub oracle: Addr,
+    pub eqa_denom: String,
+    pub collateral_denom: String,
+    pub max_reward_per_block: Uint128,
+    pub dex_pair: Addr,
+    pub pool_model: PoolModel,
+    pub pool_fee: Decimal,
+}
+
+// Oracle query types
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum OracleQueryMsg {
+    GetPrice { denom: String },
+}
+
+#[derive(Deserialize)]
+struct PriceResponse {
+    price: Decimal,
+}
+
+const CONFIG: Item<Config> = Item::new("config");
 const REWARD_PERCENTAGE: Item<Decimal> = Item::new("reward_percentage");
+const REWARD_POOL: Item<Uint128> = Item::new("reward_pool");
+const COLLATERAL_RESERVE: Item<Uint128> = Item::new("collateral_reserve");
+const BLOCK_REWARDS: Item<(u64, Uint128)> = Item::new("block_rewards"); // (height, rewards paid at that height)
+const MAX_REWARD_PERCENTAGE: Decimal = Decimal::percent(50);
 const PEG_TARGET: Decimal = Decimal::one(); // Target price of 1.0
 
 pub fn initialize(
     deps: DepsMut,
     info: MessageInfo,
-    reward_percentage: Decimal,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    if reward_percentage > Decimal::percent(50) {
-        return Err(ContractError::CustomError { 
-            msg: "Reward percentage cannot exceed 50%".to_string() 
-        });
-    }
+    let config = Config {
+        eqa_token: msg.eqa_token,
+        oracle: msg.oracle,
+        eqa_denom: msg.eqa_denom,
+        collateral_denom: msg.collateral_denom,
+        max_reward_per_block: msg.max_reward_per_block,
+        dex_pair: msg.dex_pair,
+        pool_model: msg.pool_model,
+        pool_fee: msg.pool_fee,
+    };
+    validate_config(deps.api, &config, msg.reward_percentage).into_result()?;
     
-    REWARD_PERCENTAGE.save(deps.storage, &reward_percentage)?;
+    ownership::initialize_owner(deps.storage, deps.api, info.sender.as_str())?;
+    CONFIG.save(deps.storage, &config)?;
+    REWARD_PERCENTAGE.save(deps.storage, &msg.reward_percentage)?;
+    REWARD_POOL.save(deps.storage, &Uint128::zero())?;
+    COLLATERAL_RESERVE.save(deps.storage, &Uint128::zero())?;
     
     Ok(Response::new()
         .add_attribute("action", "initialize")
         .add_attribute("admin", info.sender)
+        .add_attribute("eqa_token", config.eqa_token)
+        .add_attribute("reward_percentage", msg.reward_percentage.to_string()))
+}
+
+/// Upgrade a module that predates ownership and its config.
+/// The stored reward percentage is kept; everything else comes from the migrate message.
+pub fn migrate_legacy_layout(deps: DepsMut, legacy: Option<LegacyConfigMsg>) -> Result<(), ContractError> {
+    let legacy = legacy.ok_or_else(|| ContractError::InvalidConfig {
+        field: "legacy_config".to_string(),
+        reason: "required to migrate from the unversioned layout".to_string(),
+    })?;
+    let reward_percentage = REWARD_PERCENTAGE.load(deps.storage)?;
+    let config = Config {
+        eqa_token: legacy.eqa_token,
+        oracle: legacy.oracle,
+        eqa_denom: legacy.eqa_denom,
+        collateral_denom: legacy.collateral_denom,
+        max_reward_per_block: legacy.max_reward_per_block,
+        dex_pair: legacy.dex_pair,
+        pool_model: legacy.pool_model,
+        pool_fee: legacy.pool_fee,
+    };
+    validate_config(deps.api, &config, reward_percentage).into_result()?;
+    
+    let owner = deps.api.addr_validate(&legacy.owner)?;
+    migration::migrate_legacy_owner(deps.storage, owner)?;
+    CONFIG.save(deps.storage, &config)?;
+    REWARD_POOL.save(deps.storage, &Uint128::zero())?;
+    COLLATERAL_RESERVE.save(deps.storage, &Uint128::zero())?;
+    
+    Ok(())
+}
+
+fn validate_config(api: &dyn Api, config: &Config, reward_percentage: Decimal) -> ConfigValidator {
+    let mut validator = ConfigValidator::new();
+    validator
+        .address(api, "eqa_token", config.eqa_token.as_str())
+        .address(api, "oracle", config.oracle.as_str())
+        .address(api, "dex_pair", config.dex_pair.as_str())
+        .not_empty("eqa_denom", &config.eqa_denom)
+        .not_empty("collateral_denom", &config.collateral_denom)
+        .check("collateral_denom", config.collateral_denom != config.eqa_denom, "must differ from eqa_denom")
+        .check("pool_fee", config.pool_fee < Decimal::one(), "must be below 100%")
+        .range("reward_percentage", reward_percentage, Decimal::zero(), MAX_REWARD_PERCENTAGE);
+    validator
+}
+
+pub fn execute_update_reward_percentage(
+    deps: DepsMut,
+    info: MessageInfo,
+    reward_percentage: Decimal,
+) -> Result<Response, ContractError> {
+    roles::assert_role(deps.storage, Role::FeeManager, &info.sender)?;
+    
+    validate_config(deps.api, &CONFIG.load(deps.storage)?, reward_percentage).into_result()?;
+    REWARD_PERCENTAGE.save(deps.storage, &reward_percentage)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "update_reward_percentage")
         .add_attribute("reward_percentage", reward_percentage.to_string()))
 }
 
-pub fn execute_arbitrage(
+pub fn execute_fund_reward_pool(
     deps: DepsMut,
-    _env: Env,
     info: MessageInfo,
-    amount: Uint128,
-    target_price: Decimal,
 ) -> Result<Response, ContractError> {
-    let reward_percentage = REWARD_PERCENTAGE.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let amount = must_pay(&info, &config.collateral_denom)?;
     
-    // Check if the target price moves price toward peg
-    if (target_price > PEG_TARGET && PEG_TARGET > target_price) || 
-       (target_price < PEG_TARGET && PEG_TARGET < target_price) {
-        return Err(ContractError::InvalidPrice {});
+    let reward_pool = REWARD_POOL.update(deps.storage, |pool| -> StdResult<_> {
+        Ok(pool.checked_add(amount)?)
+    })?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "fund_reward_pool")
+        .add_attribute("funder", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("reward_pool", reward_pool))
+}
+
+pub fn execute_update_max_reward_per_block(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_reward_per_block: Uint128,
+) -> Result<Response, ContractError> {
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+    
+    let mut config = CONFIG.load(deps.storage)?;
+    config.max_reward_per_block = max_reward_per_block;
+    validate_config(deps.api, &config, REWARD_PERCENTAGE.load(deps.storage)?).into_result()?;
+    CONFIG.save(deps.storage, &config)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "update_max_reward_per_block")
+        .add_attribute("max_reward_per_block", max_reward_per_block))
+}
+
+pub fn execute_mint_at_peg(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = must_pay(&info, &config.collateral_denom)?;
+    
+    // Minting adds supply, so it only moves the price toward peg while EQA trades above it
+    let price = query_eqa_price(deps.as_ref(), &config)?;
+    if price <= PEG_TARGET {
+        return Err(ContractError::UnsupportedMarketCondition {});
     }
     
-    // Calculate deviation from peg
-    let deviation = if target_price > PEG_TARGET {
-        target_price - PEG_TARGET
-    } else {
-        PEG_TARGET - target_price
+    COLLATERAL_RESERVE.update(deps.storage, |reserve| -> StdResult<_> {
+        Ok(reserve.checked_add(amount)?)
+    })?;
+    
+    let mint_msg = WasmMsg::Execute {
+        contract_addr: config.eqa_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+            recipient: info.sender.to_string(),
+            amount,
+        })?,
+        funds: vec![],
     };
     
-    // Simple reward calculation: reward = amount * deviation * reward_percentage 
-    let reward_amount = amount.u128() as u64 * (deviation * reward_percentage).atomics().u128() as u64 / 1_000_000u64;
+    let reward = pay_reward(deps, &env, &config, amount, price - PEG_TARGET)?;
+    
+    let mut response = Response::new()
+        .add_message(mint_msg)
+        .add_attribute("action", "mint_at_peg")
+        .add_attribute("trader", info.sender.as_str())
+        .add_attribute("amount", amount)
+        .add_attribute("price", price.to_string())
+        .add_attribute("reward", reward);
     
-    // In a real implementation, this would transfer tokens to the arbitrageur
+    if !reward.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(reward.u128(), config.collateral_denom),
+        });
+    }
+    
+    Ok(response)
+}
+
+pub fn execute_redeem_at_peg(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    trader: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    
+    // Only EQA sent through its own token contract can be redeemed
+    if info.sender != config.eqa_token {
+        return Err(ContractError::InvalidToken {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::InvalidAmount {});
+    }
+    
+    // Redeeming removes supply, so it only moves the price toward peg while EQA trades below it
+    let price = query_eqa_price(deps.as_ref(), &config)?;
+    if price >= PEG_TARGET {
+        return Err(ContractError::UnsupportedMarketCondition {});
+    }
+    
+    let reserve = COLLATERAL_RESERVE.load(deps.storage)?;
+    if reserve < amount {
+        return Err(ContractError::InsufficientCollateral { required: amount, available: reserve });
+    }
+    COLLATERAL_RESERVE.save(deps.storage, &(reserve - amount))?;
+    
+    let trader = deps.api.addr_validate(&trader)?;
+    let reward = pay_reward(deps, &env, &config, amount, PEG_TARGET - price)?;
+    
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: config.eqa_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![],
+    };
+    
+    // Collateral at peg plus any reward go out in a single transfer
+    let payout = BankMsg::Send {
+        to_address: trader.to_string(),
+        amount: coins((amount + reward).u128(), config.collateral_denom),
+    };
     
     Ok(Response::new()
-        .add_attribute("action", "arbitrage")
-        .add_attribute("trader", info.sender)
+        .add_message(burn_msg)
+        .add_message(payout)
+        .add_attribute("action", "redeem_at_peg")
+        .add_attribute("trader", trader)
         .add_attribute("amount", amount)
-        .add_attribute("reward", reward_amount.to_string())
-        .add_attribute("target_price", target_price.to_string()))
+        .add_attribute("price", price.to_string())
+        .add_attribute("reward", reward))
 }
 
-pub fn query_arbitrage_opportunity(
-    deps: Deps,
-    current_price: Decimal,
-) -> StdResult<ArbitrageOpportunityResponse> {
-    let reward_percentage = REWARD_PERCENTAGE.load(deps.storage)?;
+/// Arbitrage the configured pair back toward peg in one transaction: above peg, mint EQA for the
+/// attached collateral and sell it on the pair; below peg, buy EQA on the pair and redeem it.
+/// Swaps are priced at 1.0 with no allowed spread, so they revert rather than return less than peg value.
+pub fn execute_arbitrage_via_pair(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = must_pay(&info, &config.collateral_denom)?;
+    let pair = dex_pair(&config);
+    let price = pair.query_price(&deps.querier)?;
     
-    // Calculate deviation from peg
-    let deviation = if current_price > PEG_TARGET {
-        current_price - PEG_TARGET
+    let response = Response::new()
+        .add_attribute("action", "arbitrage_via_pair")
+        .add_attribute("trader", info.sender.as_str())
+        .add_attribute("amount", amount)
+        .add_attribute("pair_price", price.to_string());
+    
+    if price > PEG_TARGET {
+        COLLATERAL_RESERVE.update(deps.storage, |reserve| -> StdResult<_> {
+            Ok(reserve.checked_add(amount)?)
+        })?;
+        
+        let mint_msg = WasmMsg::Execute {
+            contract_addr: config.eqa_token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                recipient: env.contract.address.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        };
+        let swap_msg = pair.swap_msg(
+            Asset { info: pair.eqa.clone(), amount },
+            Some(PEG_TARGET),
+            Some(Decimal::zero()),
+            Some(info.sender.to_string()),
+        )?;
+        
+        Ok(response
+            .add_message(mint_msg)
+            .add_message(swap_msg)
+            .add_attribute("direction", "mint"))
+    } else if price < PEG_TARGET {
+        // The pair pays out with a plain transfer, so the purchase is measured by the balance change
+        let balance: BalanceResponse = deps.querier.query_wasm_smart(
+            config.eqa_token.to_string(),
+            &Cw20QueryMsg::Balance { address: env.contract.address.to_string() },
+        )?;
+        
+        let swap_msg = pair.swap_msg(
+            Asset { info: pair.collateral.clone(), amount },
+            Some(PEG_TARGET),
+            Some(Decimal::zero()),
+            None,
+        )?;
+        let complete_msg = WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::CompleteRedeem {
+                trader: info.sender.to_string(),
+                balance_before: balance.balance,
+            })?,
+            funds: vec![],
+        };
+        
+        Ok(response
+            .add_message(swap_msg)
+            .add_message(complete_msg)
+            .add_attribute("direction", "redeem"))
     } else {
-        PEG_TARGET - current_price
+        Err(ContractError::UnsupportedMarketCondition {})
+    }
+}
+
+/// Redeem the EQA bought on the pair by `execute_arbitrage_via_pair`. Only callable by the contract itself.
+pub fn execute_complete_redeem(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    trader: String,
+    balance_before: Uint128,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    
+    let config = CONFIG.load(deps.storage)?;
+    let balance: BalanceResponse = deps.querier.query_wasm_smart(
+        config.eqa_token.to_string(),
+        &Cw20QueryMsg::Balance { address: env.contract.address.to_string() },
+    )?;
+    let amount = balance.balance.checked_sub(balance_before).map_err(StdError::from)?;
+    
+    let reserve = COLLATERAL_RESERVE.load(deps.storage)?;
+    if reserve < amount {
+        return Err(ContractError::InsufficientCollateral { required: amount, available: reserve });
+    }
+    COLLATERAL_RESERVE.save(deps.storage, &(reserve - amount))?;
+    
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: config.eqa_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![],
+    };
+    let payout = BankMsg::Send {
+        to_address: trader.clone(),
+        amount: coins(amount.u128(), config.collateral_denom),
     };
     
-    let opportunity_exists = deviation > Decimal::percent(1); // 1% deviation
+    Ok(Response::new()
+        .add_message(burn_msg)
+        .add_message(payout)
+        .add_attribute("action", "complete_redeem")
+        .add_attribute("trader", trader)
+        .add_attribute("amount", amount))
+}
+
+// Take the reward for an arbitrage of `amount` at `deviation` from peg out of the pool,
+// limited by what the pool holds and what is left of this block's budget
+fn pay_reward(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    amount: Uint128,
+    deviation: Decimal,
+) -> Result<Uint128, ContractError> {
+    let reward_percentage = REWARD_PERCENTAGE.load(deps.storage)?;
+    let reward_pool = REWARD_POOL.load(deps.storage)?;
+    let paid_this_block = rewards_paid_at(deps.as_ref(), env.block.height)?;
     
-    // For simplicity, we're setting a fixed optimal trade size
-    // In a real implementation, this would be calculated based on market depth
-    let optimal_trade_size = Uint128::from(100_000u128);
+    let reward = (amount * (deviation * reward_percentage))
+        .min(reward_pool)
+        .min(config.max_reward_per_block.saturating_sub(paid_this_block));
     
-    // Expected profit calculation
-    let expected_profit = deviation * reward_percentage;
+    REWARD_POOL.save(deps.storage, &(reward_pool - reward))?;
+    BLOCK_REWARDS.save(deps.storage, &(env.block.height, paid_this_block + reward))?;
     
-    // Determine direction
-    let direction = if current_price > PEG_TARGET {
-        "sell"
-    } else {
-        "buy"
-    }.to_string();
-    
-    Ok(ArbitrageOpportunityResponse {
-        opportunity_exists,
-        optimal_trade_size,
-        expected_profit,
-        direction,
+    Ok(reward)
+}
+
+fn rewards_paid_at(deps: Deps, height: u64) -> StdResult<Uint128> {
+    let paid = match BLOCK_REWARDS.may_load(deps.storage)? {
+        Some((block, paid)) if block == height => paid,
+        _ => Uint128::zero(),
+    };
+    Ok(paid)
+}
+
+// Require exactly one coin of `denom` and return its amount
+fn must_pay(info: &MessageInfo, denom: &str) -> Result<Uint128, ContractError> {
+    match info.funds.as_slice() {
+        [coin] if coin.denom == denom && !coin.amount.is_zero() => Ok(coin.amount),
+        _ => Err(ContractError::InvalidFunds {}),
+    }
+}
+
+fn query_eqa_price(deps: Deps, config: &Config) -> StdResult<Decimal> {
+    let response: PriceResponse = deps.querier.query_wasm_smart(
+        config.oracle.to_string(),
+        &OracleQueryMsg::GetPrice { denom: config.eqa_denom.clone() },
+    )?;
+    Ok(response.price)
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    
+    Ok(ConfigResponse {
+        reward_percentage: REWARD_PERCENTAGE.load(deps.storage)?,
+        eqa_token: config.eqa_token,
+        oracle: config.oracle,
+        eqa_denom: config.eqa_denom,
+        collateral_denom: config.collateral_denom,
+        max_reward_per_block: config.max_reward_per_block,
+        dex_pair: config.dex_pair,
+        pool_model: config.pool_model,
+        pool_fee: config.pool_fee,
+    })
+}
+
+pub fn query_validate_config(deps: Deps) -> StdResult<ValidateConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let reward_percentage = REWARD_PERCENTAGE.load(deps.storage)?;
+    
+    Ok(validate_config(deps.api, &config, reward_percentage).into_response())
+}
+
+pub fn query_reward_pool(deps: Deps, env: Env) -> StdResult<RewardPoolResponse> {
+    Ok(RewardPoolResponse {
+        reward_pool: REWARD_POOL.load(deps.storage)?,
+        collateral_reserve: COLLATERAL_RESERVE.load(deps.storage)?,
+        rewards_paid_this_block: rewards_paid_at(deps, env.block.height)?,
     })
 }
+
+pub fn query_arbitrage_opportunity(deps: Deps) -> StdResult<ArbitrageOpportunityResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let reserves = dex_pair(&config).query_reserves(&deps.querier)?;
+    let trade = calculate_optimal_trade_size(&reserves, &config.pool_model, config.pool_fee)?;
+    
+    Ok(match trade {
+        Some(trade) => ArbitrageOpportunityResponse {
+            opportunity_exists: true,
+            direction: Some(trade.direction),
+            optimal_trade_size: trade.offer_amount,
+            expected_return: trade.return_amount,
+            expected_profit: trade.expected_profit,
+        },
+        None => ArbitrageOpportunityResponse {
+            opportunity_exists: false,
+            direction: None,
+            optimal_trade_size: Uint128::zero(),
+            expected_return: Uint128::zero(),
+            expected_profit: Uint128::zero(),
+        },
+    })
+}
+
+// The configured pair, trading the EQA token against the native collateral
+fn dex_pair(config: &Config) -> DexPair {
+    DexPair {
+        contract_addr: config.dex_pair.clone(),
+        eqa: AssetInfo::Token { contract_addr: config.eqa_token.to_string() },
+        collateral: AssetInfo::NativeToken { denom: config.collateral_denom.clone() },
+    }
+}
@@ -1,20 +1,35 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, Decimal,
+    entry_point, to_json_binary, from_json, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+    Decimal, Addr,
 };
+use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use equilibria_smart_contracts::config::{ChainIdMapping, ChainIdPattern, NetworkEnvironment};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::migration;
+use equilibria_smart_contracts::network::{self, NetworkManager};
+use equilibria_smart_contracts::ownership::{self, OwnershipAction};
+use equilibria_smart_contracts::roles::{self, Role};
+use equilibria_smart_contracts::oracle::{PoolModel, TradeDirection};
 
 mod contract;
 
+// Stored at instantiation and checked on migration
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    contract::initialize(deps, info, msg.reward_percentage)
+    migration::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    network::instantiate_chain_id_mappings(deps.storage, msg.chain_id_mappings.as_deref())?;
+    NetworkManager::verify_network(deps.as_ref(), &env, None)?;
+    contract::initialize(deps, info, msg)
 }
 
 #[entry_point]
@@ -25,8 +40,42 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Arbitrage { amount, target_price } => {
-            contract::execute_arbitrage(deps, env, info, amount, target_price)
+        ExecuteMsg::MintAtPeg {} => {
+            contract::execute_mint_at_peg(deps, env, info)
+        }
+        ExecuteMsg::Receive(cw20_msg) => {
+            match from_json(&cw20_msg.msg)? {
+                ReceiveMsg::RedeemAtPeg {} => {
+                    contract::execute_redeem_at_peg(deps, env, info, cw20_msg.sender, cw20_msg.amount)
+                }
+            }
+        }
+        ExecuteMsg::ArbitrageViaPair {} => {
+            contract::execute_arbitrage_via_pair(deps, env, info)
+        }
+        ExecuteMsg::CompleteRedeem { trader, balance_before } => {
+            contract::execute_complete_redeem(deps, env, info, trader, balance_before)
+        }
+        ExecuteMsg::FundRewardPool {} => {
+            contract::execute_fund_reward_pool(deps, info)
+        }
+        ExecuteMsg::UpdateMaxRewardPerBlock { max_reward_per_block } => {
+            contract::execute_update_max_reward_per_block(deps, info, max_reward_per_block)
+        }
+        ExecuteMsg::UpdateRewardPercentage { reward_percentage } => {
+            contract::execute_update_reward_percentage(deps, info, reward_percentage)
+        }
+        ExecuteMsg::UpdateOwnership(action) => {
+            ownership::execute_update_ownership(deps, env, info, action)
+        }
+        ExecuteMsg::GrantRole { role, address } => {
+            roles::execute_grant_role(deps, info, role, address)
+        }
+        ExecuteMsg::RevokeRole { role, address } => {
+            roles::execute_revoke_role(deps, info, role, address)
+        }
+        ExecuteMsg::SetChainIdMapping { pattern, environment } => {
+            network::execute_set_chain_id_mapping(deps, info, pattern, environment)
         }
     }
 }
@@ -34,38 +83,148 @@ pub fn execute(
 #[entry_point]
 pub fn query(
     deps: Deps,
-    _env: Env,
+    env: Env,
     msg: QueryMsg,
 ) -> StdResult<Binary> {
     match msg {
-        QueryMsg::ArbitrageOpportunity { current_price } => {
-            let result = contract::query_arbitrage_opportunity(deps, current_price)?;
+        QueryMsg::ArbitrageOpportunity {} => {
+            let result = contract::query_arbitrage_opportunity(deps)?;
             to_json_binary(&result)
         }
+        QueryMsg::Config {} => {
+            to_json_binary(&contract::query_config(deps)?)
+        }
+        QueryMsg::ValidateConfig {} => {
+            to_json_binary(&contract::query_validate_config(deps)?)
+        }
+        QueryMsg::RewardPool {} => {
+            to_json_binary(&contract::query_reward_pool(deps, env)?)
+        }
+        QueryMsg::GetOwnership {} => {
+            to_json_binary(&ownership::get_ownership(deps.storage)?)
+        }
+        QueryMsg::HasRole { role, address } => {
+            to_json_binary(&roles::query_has_role(deps, role, address)?)
+        }
+        QueryMsg::RoleMembers { role, start_after, limit } => {
+            to_json_binary(&roles::query_role_members(deps, role, start_after, limit)?)
+        }
+        QueryMsg::GetChainIdMappings {} => {
+            to_json_binary(&network::query_chain_id_mappings(deps, &env)?)
+        }
     }
 }
 
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = migration::migrate_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    
+    // Contracts without a stored version predate ownership and only stored their reward percentage
+    if previous.is_none() {
+        contract::migrate_legacy_layout(deps, msg.legacy_config)?;
+    }
+    
+    Ok(migration::migrate_response(previous, CONTRACT_VERSION))
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub reward_percentage: Decimal,
+    pub eqa_token: Addr,             // CW20 token this module is minter of
+    pub oracle: Addr,                // Price source for EQA
+    pub eqa_denom: String,           // Denom the oracle quotes EQA under
+    pub collateral_denom: String,    // Native stablecoin EQA is minted and redeemed against at 1:1
+    pub max_reward_per_block: Uint128,
+    pub dex_pair: Addr,              // Terraswap/Astroport-style EQA/collateral pair
+    pub pool_model: PoolModel,
+    pub pool_fee: Decimal,           // Pair commission, charged on the return amount
+    pub chain_id_mappings: Option<Vec<ChainIdMapping>>, // added to the default mappings before the network is verified
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    pub legacy_config: Option<LegacyConfigMsg>, // required when migrating from the unversioned layout
+}
+
+/// Settings the unversioned module had no storage for; its reward percentage is kept
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfigMsg {
+    pub owner: String,
+    pub eqa_token: Addr,
+    pub oracle: Addr,
+    pub eqa_denom: String,
+    pub collateral_denom: String,
+    pub max_reward_per_block: Uint128,
+    pub dex_pair: Addr,
+    pub pool_model: PoolModel,
+    pub pool_fee: Decimal,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    Arbitrage { amount: Uint128, target_price: Decimal },
+    /// Mint EQA 1:1 for the attached collateral while EQA trades above peg
+    MintAtPeg {},
+    /// Redeem EQA sent through the CW20 `Send` hook while EQA trades below peg
+    Receive(Cw20ReceiveMsg),
+    /// Arbitrage the DEX pair back toward peg with the attached collateral in a single transaction
+    ArbitrageViaPair {},
+    /// Internal step of `ArbitrageViaPair` that redeems the EQA bought on the pair
+    CompleteRedeem { trader: String, balance_before: Uint128 },
+    /// Add the attached collateral to the arbitrage reward pool
+    FundRewardPool {},
+    UpdateMaxRewardPerBlock { max_reward_per_block: Uint128 },
+    UpdateRewardPercentage { reward_percentage: Decimal },
+    UpdateOwnership(OwnershipAction),
+    GrantRole { role: Role, address: String },
+    RevokeRole { role: Role, address: String },
+    SetChainIdMapping { pattern: ChainIdPattern, environment: Option<NetworkEnvironment> }, // None removes the mapping
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    ArbitrageOpportunity { current_price: Decimal },
+    ArbitrageOpportunity {}, // Priced from the configured DEX pair's reserves
+    Config {},
+    ValidateConfig {}, // Every invalid config field, not just the first
+    RewardPool {},
+    GetOwnership {},
+    HasRole { role: Role, address: String }, // The owner holds every role
+    RoleMembers { role: Role, start_after: Option<String>, limit: Option<u32> },
+    GetChainIdMappings {}, // Also reports the network the current chain id maps to
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    RedeemAtPeg {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ArbitrageOpportunityResponse {
     pub opportunity_exists: bool,
-    pub optimal_trade_size: Uint128,
-    pub expected_profit: Decimal,
-    pub direction: String,
+    pub direction: Option<TradeDirection>,
+    pub optimal_trade_size: Uint128, // Amount offered to the pair to bring it back to peg
+    pub expected_return: Uint128,
+    pub expected_profit: Uint128,    // After the pair fee, before arbitrage rewards
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub reward_percentage: Decimal,
+    pub eqa_token: Addr,
+    pub oracle: Addr,
+    pub eqa_denom: String,
+    pub collateral_denom: String,
+    pub max_reward_per_block: Uint128,
+    pub dex_pair: Addr,
+    pub pool_model: PoolModel,
+    pub pool_fee: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardPoolResponse {
+    pub reward_pool: Uint128,
+    pub collateral_reserve: Uint128,     // Collateral backing EQA minted here, paid out on redemption
+    pub rewards_paid_this_block: Uint128,
 }
@@ -0,0 +1,153 @@
+use cosmwasm_std::{
+    coins, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, Env, IbcMsg, IbcTimeout, MessageInfo, StdResult,
+    Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use equilibria_smart_contracts::error::ContractError;
+
+use crate::cw20_handler::{get_contract_address, AXELAR_USDC_KEY, NOBLE_USDC_KEY};
+use crate::state::NATIVE_DENOMS;
+use crate::CollateralAsset;
+
+// Timeout for IBC withdrawals when the caller does not pick one
+pub const DEFAULT_IBC_TIMEOUT_SECONDS: u64 = 10 * 60;
+
+/// Resolve a registry token key to the asset that backs it.
+/// Keys with a configured native denom are bank coins, everything else is a CW20 from the registry.
+pub fn load_asset(deps: Deps, registry_addr: &str, token_key: &str) -> StdResult<CollateralAsset> {
+    match NATIVE_DENOMS.may_load(deps.storage, token_key)? {
+        Some(denom) => Ok(CollateralAsset::Native { denom }),
+        None => Ok(CollateralAsset::Cw20 {
+            contract_addr: get_contract_address(deps, registry_addr, token_key)?,
+        }),
+    }
+}
+
+/// Find the token key for a CW20 address or native denom
+pub fn token_key_for(deps: Deps, registry_addr: &str, token: &str) -> Result<&'static str, ContractError> {
+    for key in [AXELAR_USDC_KEY, NOBLE_USDC_KEY] {
+        if load_asset(deps, registry_addr, key)?.identifier() == token {
+            return Ok(key);
+        }
+    }
+
+    Err(ContractError::UnsupportedAsset { asset: token.to_string() })
+}
+
+impl CollateralAsset {
+    /// The CW20 contract address or the native denom
+    pub fn identifier(&self) -> &str {
+        match self {
+            CollateralAsset::Cw20 { contract_addr } => contract_addr,
+            CollateralAsset::Native { denom } => denom,
+        }
+    }
+
+    /// Take `amount` from the caller into this contract.
+    /// CW20s are pulled with `TransferFrom`; native coins must already be attached to the call.
+    pub fn receive_msg(&self, env: &Env, info: &MessageInfo, amount: Uint128) -> Result<Option<CosmosMsg>, ContractError> {
+        match self {
+            CollateralAsset::Cw20 { contract_addr } => Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: info.sender.to_string(),
+                    recipient: env.contract.address.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            }))),
+            CollateralAsset::Native { denom } => {
+                let sent = info.funds.iter()
+                    .filter(|coin| &coin.denom == denom)
+                    .map(|coin| coin.amount)
+                    .sum::<Uint128>();
+                if sent != amount {
+                    return Err(ContractError::InvalidFunds {});
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Pay `amount` out of this contract to a local address
+    pub fn transfer_msg(&self, recipient: &str, amount: Uint128) -> StdResult<CosmosMsg> {
+        match self {
+            CollateralAsset::Cw20 { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            })),
+            CollateralAsset::Native { denom } => Ok(CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: coins(amount.u128(), denom),
+            })),
+        }
+    }
+
+    /// Hand `amount` to a contract along with `msg` and any extra `funds`.
+    /// CW20s go through the token's `Send` hook, with the extra funds sent to the contract just before;
+    /// native coins are attached to the call together with the extra funds.
+    pub fn send_to_contract_msgs(
+        &self,
+        contract: &str,
+        amount: Uint128,
+        msg: Binary,
+        funds: Vec<Coin>,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        match self {
+            CollateralAsset::Cw20 { contract_addr } => {
+                let mut msgs = vec![];
+                if !funds.is_empty() {
+                    msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                        to_address: contract.to_string(),
+                        amount: funds,
+                    }));
+                }
+                msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.clone(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                        contract: contract.to_string(),
+                        amount,
+                        msg,
+                    })?,
+                    funds: vec![],
+                }));
+                Ok(msgs)
+            }
+            CollateralAsset::Native { denom } => {
+                let mut all_funds = coins(amount.u128(), denom);
+                all_funds.extend(funds);
+                Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract.to_string(),
+                    msg,
+                    funds: all_funds,
+                })])
+            }
+        }
+    }
+
+    /// Send `amount` over IBC; only native coins can travel this way
+    pub fn ibc_transfer_msg(
+        &self,
+        env: &Env,
+        channel_id: String,
+        recipient: String,
+        amount: Uint128,
+        timeout_seconds: u64,
+    ) -> Result<CosmosMsg, ContractError> {
+        match self {
+            CollateralAsset::Native { denom } => Ok(CosmosMsg::Ibc(IbcMsg::Transfer {
+                channel_id,
+                to_address: recipient,
+                amount: Coin { denom: denom.clone(), amount },
+                timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(timeout_seconds)),
+            })),
+            CollateralAsset::Cw20 { contract_addr } => Err(ContractError::NativeAssetRequired {
+                asset: contract_addr.clone(),
+            }),
+        }
+    }
+}
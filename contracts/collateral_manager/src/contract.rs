@@ -1,8 +1,14 @@
-use cosmwasm_std::{DepsMut, Deps, Env, MessageInfo, Response, Uint128, StdResult, Addr};
+use cosmwasm_std::{DepsMut, Deps, Env, MessageInfo, Response, Uint128, StdResult, Addr, Api, Order};
+use equilibria_smart_contracts::config::{storage::GLOBAL_CONFIG, ConfigValidator, ValidateConfigResponse};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::ownership;
+use equilibria_smart_contracts::roles::{self, Role};
 use equilibria_smart_contracts::state::{COLLATERAL, CollateralState};
 
-use crate::state::ADMIN;
+use crate::cw20_handler::{AXELAR_USDC_KEY, NOBLE_USDC_KEY};
+use crate::asset::load_asset;
+use crate::{cross_chain, psm, rate_limit};
+use crate::state::{BRIDGE_FEES, DISABLED_COLLATERAL, NATIVE_DENOMS, PSM_CONFIG, RATE_LIMITS, REGISTRY_ADDRESS};
 
 pub fn initialize(
     deps: DepsMut,
@@ -11,7 +17,7 @@ pub fn initialize(
 ) -> Result<Response, ContractError> {
     // Set admin (defaults to sender if not provided)
     let admin_addr = admin.unwrap_or(info.sender.clone());
-    ADMIN.save(deps.storage, &admin_addr)?;
+    ownership::initialize_owner(deps.storage, deps.api, admin_addr.as_str())?;
     
     // Initialize empty collateral state
     let collateral_state = CollateralState {
@@ -34,10 +40,7 @@ pub fn execute_update_collateral(
     usdc_noble: Uint128,
 ) -> Result<Response, ContractError> {
     // Check if caller is admin
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(ContractError::Unauthorized {});
-    }
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
     
     COLLATERAL.update(deps.storage, |mut state| -> Result<_, ContractError> {
         state.usdc_axelar = usdc_axelar;
@@ -53,6 +56,73 @@ pub fn execute_update_collateral(
         .add_attribute("total_locked", usdc_axelar + usdc_noble))
 }
 
+// Disabled assets stop accepting deposits; withdrawals are unaffected
+pub fn execute_set_collateral_enabled(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    // Guardians may disable an asset; enabling it again is up to the admin
+    let role = if enabled { Role::Admin } else { Role::Guardian };
+    roles::assert_role(deps.storage, role, &info.sender)?;
+    
+    if asset != AXELAR_USDC_KEY && asset != NOBLE_USDC_KEY {
+        return Err(ContractError::UnsupportedAsset { asset });
+    }
+    
+    if enabled {
+        DISABLED_COLLATERAL.remove(deps.storage, &asset);
+    } else {
+        DISABLED_COLLATERAL.save(deps.storage, &asset, &true)?;
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", "set_collateral_enabled")
+        .add_attribute("asset", asset)
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+// Mark a collateral asset as an IBC-native bank denom, or back to a CW20 from the registry
+pub fn execute_set_native_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    denom: Option<String>,
+) -> Result<Response, ContractError> {
+    // Check if caller is admin
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+    
+    if asset != AXELAR_USDC_KEY && asset != NOBLE_USDC_KEY {
+        return Err(ContractError::UnsupportedAsset { asset });
+    }
+    
+    match &denom {
+        Some(denom) => NATIVE_DENOMS.save(deps.storage, &asset, denom)?,
+        None => NATIVE_DENOMS.remove(deps.storage, &asset),
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", "set_native_denom")
+        .add_attribute("asset", asset)
+        .add_attribute("denom", denom.unwrap_or_default()))
+}
+
+pub fn query_collateral_assets(deps: Deps) -> StdResult<crate::CollateralAssetsResponse> {
+    let registry_addr = crate::state::REGISTRY_ADDRESS.load(deps.storage)?;
+    
+    let assets = [AXELAR_USDC_KEY, NOBLE_USDC_KEY]
+        .iter()
+        .map(|key| Ok(crate::CollateralAssetInfo {
+            asset: key.to_string(),
+            info: load_asset(deps, &registry_addr, key)?,
+            enabled: !DISABLED_COLLATERAL.has(deps.storage, key),
+        }))
+        .collect::<StdResult<Vec<_>>>()?;
+    
+    Ok(crate::CollateralAssetsResponse { assets })
+}
+
 pub fn query_collateral_info(deps: Deps) -> StdResult<crate::CollateralResponse> {
     let collateral = COLLATERAL.load(deps.storage)?;
     
@@ -62,3 +132,31 @@ pub fn query_collateral_info(deps: Deps) -> StdResult<crate::CollateralResponse>
         total_locked: collateral.total_locked,
     })
 }
+
+pub fn validate_registry_address(api: &dyn Api, registry_address: &str) -> ConfigValidator {
+    let mut validator = ConfigValidator::new();
+    validator.address(api, "registry_address", registry_address);
+    validator
+}
+
+/// Every invalid setting: registry, PSM, bridge fees, rate limits and the network config if one is stored
+pub fn query_validate_config(deps: Deps) -> StdResult<ValidateConfigResponse> {
+    let mut validator = validate_registry_address(deps.api, &REGISTRY_ADDRESS.load(deps.storage)?);
+    
+    if let Some(config) = PSM_CONFIG.may_load(deps.storage)? {
+        validator.nested("psm", psm::validate_config(deps.api, &config));
+    }
+    for item in BRIDGE_FEES.range(deps.storage, None, None, Order::Ascending) {
+        let ((gateway, destination_chain), fee) = item?;
+        validator.nested(&format!("bridge_fees.{}.{}", gateway, destination_chain), cross_chain::validate_bridge_fee(&fee));
+    }
+    for item in RATE_LIMITS.range(deps.storage, None, None, Order::Ascending) {
+        let ((asset, destination_chain), limit) = item?;
+        validator.nested(&format!("rate_limits.{}.{}", asset, destination_chain), rate_limit::validate_limit(&limit));
+    }
+    if let Some(config) = GLOBAL_CONFIG.may_load(deps.storage)? {
+        validator.nested("network", config.validate(deps.api));
+    }
+    
+    Ok(validator.into_response())
+}
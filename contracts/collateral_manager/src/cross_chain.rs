@@ -1,16 +1,46 @@
 use cosmwasm_std::{
-    to_json_binary, WasmMsg, Response, Uint128, Binary,
-    DepsMut, MessageInfo, CosmosMsg, Env,
+    coins, to_json_binary, from_json, WasmMsg, Response, Uint128, Binary, Coin, Deps,
+    DepsMut, MessageInfo, CosmosMsg, Env, Order, Reply, StdResult, SubMsg, SubMsgResult,
 };
+use cw_storage_plus::Bound;
+use equilibria_smart_contracts::config::ConfigValidator;
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::roles::{self, Role};
+
+use crate::asset::load_asset;
+use crate::cw20_handler::{AXELAR_USDC_KEY, NOBLE_USDC_KEY, update_collateral_with_token_type};
+use crate::rate_limit;
+use crate::state::{
+    BRIDGE_FEES, PENDING_REFUND, PENDING_TRANSFERS, PROCESSED_MESSAGES, REGISTRY_ADDRESS, TRANSFERS, TRANSFER_COUNT,
+    TRUSTED_REMOTES,
+};
+use crate::{
+    BridgeFee, BridgeFeePayment, BridgeFeeQuoteResponse, CollateralAsset, CrossChainMessage, CrossChainPayload, CrossChainTransfer, TransferStatus, TransfersResponse, TrustedRemote,
+    TrustedRemotesResponse,
+};
 
 // Axelar Gateway contract address key in registry
 pub const AXELAR_GATEWAY_KEY: &str = "axelar_gateway";
 pub const NOBLE_GATEWAY_KEY: &str = "noble_gateway";
 
-// Fees for cross-chain operations
+// Default fees for cross-chain operations, used until a fee is set for a gateway and destination
 pub const AXELAR_FEE: Uint128 = Uint128::new(1_000_000); // 1 USDC
 pub const NOBLE_FEE: Uint128 = Uint128::new(500_000);   // 0.5 USDC
+pub const DEFAULT_FEE_DENOM: &str = "uluna";
+
+// Outbound transfers not acknowledged within this window are overdue, and the admin may expire them.
+// The gateway holds their tokens, so until then they are only refunded once it acknowledges a failure.
+pub const TRANSFER_TIMEOUT_SECONDS: u64 = 24 * 60 * 60;
+
+// Version of the inbound message schema this contract understands
+pub const PAYLOAD_VERSION: u16 = 1;
+
+// Reply id for refund transfers
+pub const REFUND_REPLY_ID: u64 = 1;
+
+// Pagination defaults for list queries
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
 
 // Interface to Axelar Gateway
 #[derive(serde::Serialize)]
@@ -20,7 +50,8 @@ enum AxelarGatewayMsg {
         destination_chain: String,
         destination_address: String,
         symbol: String, 
-        amount: Uint128 
+        amount: Uint128,
+        transfer_id: u64,
     },
 }
 
@@ -32,20 +63,221 @@ enum NobleGatewayMsg {
         recipient_chain: String,
         recipient: String,
         denom: String, 
-        amount: Uint128 
+        amount: Uint128,
+        transfer_id: u64,
     },
 }
 
+fn validate_gateway(gateway_key: &str) -> Result<(), ContractError> {
+    if gateway_key != AXELAR_GATEWAY_KEY && gateway_key != NOBLE_GATEWAY_KEY {
+        return Err(ContractError::UnknownGateway { gateway: gateway_key.to_string() });
+    }
+    
+    Ok(())
+}
+
+pub fn validate_bridge_fee(fee: &BridgeFee) -> ConfigValidator {
+    let mut validator = ConfigValidator::new();
+    if let BridgeFeePayment::Denom { denom } = &fee.payment {
+        validator.not_empty("payment.denom", denom);
+    }
+    validator
+}
+
+// The fee for a gateway and destination chain, falling back to the gateway's default
+pub fn load_bridge_fee(deps: Deps, gateway_key: &str, destination_chain: &str) -> StdResult<BridgeFee> {
+    if let Some(fee) = BRIDGE_FEES.may_load(deps.storage, (gateway_key, destination_chain))? {
+        return Ok(fee);
+    }
+    
+    let amount = if gateway_key == AXELAR_GATEWAY_KEY { AXELAR_FEE } else { NOBLE_FEE };
+    Ok(BridgeFee {
+        amount,
+        payment: BridgeFeePayment::Denom { denom: DEFAULT_FEE_DENOM.to_string() },
+    })
+}
+
+// Split a transfer into what is delivered and the fee coins that travel with it to the gateway
+fn apply_fee(
+    info: &MessageInfo,
+    asset: &CollateralAsset,
+    fee: &BridgeFee,
+    amount: Uint128,
+) -> Result<(Uint128, Vec<Coin>), ContractError> {
+    match &fee.payment {
+        BridgeFeePayment::Deducted {} => {
+            if amount <= fee.amount {
+                return Err(ContractError::BridgeFeeNotCovered { amount, fee: fee.amount });
+            }
+            Ok((amount - fee.amount, vec![]))
+        },
+        BridgeFeePayment::Denom { denom } => {
+            // Fees in the transferred asset itself are configured as deducted instead
+            if denom == asset.identifier() {
+                return Err(ContractError::BridgeFeeNotDeducted { denom: denom.clone() });
+            }
+            
+            let sent = info.funds.iter()
+                .filter(|c| &c.denom == denom)
+                .map(|c| c.amount)
+                .sum::<Uint128>();
+            if sent != fee.amount {
+                return Err(ContractError::IncorrectBridgeFee { 
+                    denom: denom.clone(), 
+                    expected: fee.amount, 
+                    sent, 
+                });
+            }
+            
+            if fee.amount.is_zero() {
+                return Ok((amount, vec![]));
+            }
+            Ok((amount, coins(fee.amount.u128(), denom)))
+        },
+    }
+}
+
+pub fn execute_set_bridge_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    gateway: String,
+    destination_chain: String,
+    fee: Option<BridgeFee>,
+) -> Result<Response, ContractError> {
+    // Fee managers set bridge fees; governance does so as the owner
+    roles::assert_role(deps.storage, Role::FeeManager, &info.sender)?;
+    
+    validate_gateway(&gateway)?;
+    
+    match &fee {
+        Some(fee) => {
+            validate_bridge_fee(fee).into_result()?;
+            BRIDGE_FEES.save(deps.storage, (&gateway, &destination_chain), fee)?
+        }
+        None => BRIDGE_FEES.remove(deps.storage, (&gateway, &destination_chain)),
+    }
+    
+    let fee = load_bridge_fee(deps.as_ref(), &gateway, &destination_chain)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "set_bridge_fee")
+        .add_attribute("gateway", gateway)
+        .add_attribute("destination_chain", destination_chain)
+        .add_attribute("fee", fee.amount))
+}
+
+pub fn query_bridge_fee_quote(
+    deps: Deps,
+    gateway: String,
+    destination_chain: String,
+    amount: Uint128,
+) -> Result<BridgeFeeQuoteResponse, ContractError> {
+    validate_gateway(&gateway)?;
+    let fee = load_bridge_fee(deps, &gateway, &destination_chain)?;
+    
+    let amount_delivered = match fee.payment {
+        BridgeFeePayment::Deducted {} => amount.saturating_sub(fee.amount),
+        BridgeFeePayment::Denom { .. } => amount,
+    };
+    
+    Ok(BridgeFeeQuoteResponse {
+        fee: fee.amount,
+        payment: fee.payment,
+        amount_delivered,
+    })
+}
+
+// An outbound transfer that has been recorded but not yet handed to its gateway
+struct OutboundTransfer {
+    transfer: CrossChainTransfer,
+    asset: CollateralAsset,
+    pull_msg: Option<CosmosMsg>,
+    gross_amount: Uint128,
+    fee: Uint128,
+    fee_funds: Vec<Coin>,
+}
+
+impl OutboundTransfer {
+    // Hand the tokens and the fee to the gateway together with the transfer instructions
+    fn forward_msgs(&self, gateway: &str, msg: Binary) -> StdResult<Vec<CosmosMsg>> {
+        self.asset.send_to_contract_msgs(gateway, self.gross_amount, msg, self.fee_funds.clone())
+    }
+}
+
+// Record an outbound transfer and pull the user's tokens into this contract.
+// The delivered amount counts towards the asset's collateral bucket until the gateway acknowledges the transfer;
+// a deducted fee leaves with the transfer.
+fn start_transfer(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    gateway_key: &str,
+    destination_chain: String,
+    recipient: String,
+    amount: Uint128,
+) -> Result<OutboundTransfer, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidAmount {});
+    }
+    
+    // Each gateway carries its own USDC variant
+    let token_key = if gateway_key == AXELAR_GATEWAY_KEY { AXELAR_USDC_KEY } else { NOBLE_USDC_KEY };
+    
+    // Get the CW20 address or native denom
+    let registry_addr = REGISTRY_ADDRESS.load(deps.storage)?;
+    let asset = load_asset(deps.as_ref(), &registry_addr, token_key)?;
+    
+    // Verify the fee and work out what reaches the destination
+    let fee = load_bridge_fee(deps.as_ref(), gateway_key, &destination_chain)?;
+    let (net_amount, fee_funds) = apply_fee(info, &asset, &fee, amount)?;
+    
+    // Everything handed to the gateway counts towards the route's rate limit
+    rate_limit::record_outflow(deps.branch(), env, token_key, &destination_chain, amount)?;
+    
+    // Pull the user's tokens into this contract, or check they were attached
+    let pull_msg = asset.receive_msg(env, info, amount)?;
+    
+    update_collateral_with_token_type(deps.branch(), token_key, net_amount, true)?;
+    
+    let id = TRANSFER_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    TRANSFER_COUNT.save(deps.storage, &id)?;
+    
+    let transfer = CrossChainTransfer {
+        id,
+        gateway: gateway_key.to_string(),
+        asset: token_key.to_string(),
+        sender: info.sender.clone(),
+        destination_chain,
+        recipient,
+        amount: net_amount,
+        status: TransferStatus::Pending,
+        created_at: env.block.time.seconds(),
+        timeout_at: env.block.time.seconds() + TRANSFER_TIMEOUT_SECONDS,
+    };
+    TRANSFERS.save(deps.storage, id, &transfer)?;
+    PENDING_TRANSFERS.save(deps.storage, id, &true)?;
+    
+    Ok(OutboundTransfer {
+        transfer,
+        asset,
+        pull_msg,
+        gross_amount: amount,
+        fee: fee.amount,
+        fee_funds,
+    })
+}
+
 // Send tokens via Axelar to another chain
 pub fn send_via_axelar(
-    mut deps: DepsMut,
+    deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     destination_chain: String,
     destination_address: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
     // Load registry address
-    let registry_addr = crate::state::REGISTRY_ADDRESS.load(deps.storage)?;
+    let registry_addr = REGISTRY_ADDRESS.load(deps.storage)?;
     
     // Get Axelar gateway address from registry
     let axelar_gateway = crate::cw20_handler::get_contract_address(
@@ -54,115 +286,356 @@ pub fn send_via_axelar(
         AXELAR_GATEWAY_KEY
     )?;
     
-    // Verify funds sent cover the fee
-    let sent_funds = info.funds.iter().find(|c| c.denom == "uluna").ok_or_else(|| {
-        ContractError::CustomError { 
-            msg: "No Luna sent to cover cross-chain fee".to_string() 
-        }
-    })?;
-    
-    if sent_funds.amount < AXELAR_FEE {
-        return Err(ContractError::CustomError { 
-            msg: format!("Insufficient fee: sent {}, required {}", sent_funds.amount, AXELAR_FEE) 
-        });
-    }
-    
-    // Get token address from registry
-    use crate::cw20_handler::{AXELAR_USDC_KEY, update_collateral_with_token_type};
-    let token_addr = crate::cw20_handler::get_contract_address(
-        deps.as_ref(), 
-        &registry_addr, 
-        AXELAR_USDC_KEY
+    let outbound = start_transfer(
+        deps, &env, &info, AXELAR_GATEWAY_KEY, destination_chain, destination_address, amount,
     )?;
+    let transfer = &outbound.transfer;
     
-    // Update collateral state directly instead of using receive_tokens
-    update_collateral_with_token_type(deps.branch(), AXELAR_USDC_KEY, amount, true)?;
-    
-    // Create transfer message - simplified version
-    let transfer_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: token_addr,
-        msg: to_json_binary(&cw20::Cw20ExecuteMsg::TransferFrom {
-            owner: info.sender.to_string(),
-            recipient: "contract_address".to_string(), // This is simplified
-            amount,
-        })?,
-        funds: vec![],
-    });
-    
-    // Create axelar cross-chain transfer message
-    let axelar_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: axelar_gateway,
-        msg: to_json_binary(&AxelarGatewayMsg::SendToken {
-            destination_chain,
-            destination_address,
+    let axelar_msgs = outbound.forward_msgs(
+        &axelar_gateway,
+        to_json_binary(&AxelarGatewayMsg::SendToken {
+            destination_chain: transfer.destination_chain.clone(),
+            destination_address: transfer.recipient.clone(),
             symbol: "USDC".to_string(),
-            amount,
+            amount: transfer.amount,
+            transfer_id: transfer.id,
         })?,
-        funds: vec![],
-    });
+    )?;
     
     Ok(Response::new()
-        .add_message(transfer_msg)
-        .add_message(axelar_msg)
+        .add_messages(outbound.pull_msg.clone())
+        .add_messages(axelar_msgs)
         .add_attribute("action", "cross_chain_send_axelar")
-        .add_attribute("amount", amount.to_string())
-        .add_attribute("fee", AXELAR_FEE.to_string()))
+        .add_attribute("transfer_id", transfer.id.to_string())
+        .add_attribute("amount", transfer.amount.to_string())
+        .add_attribute("fee", outbound.fee.to_string()))
 }
 
-// Send tokens via Noble to another chain - simplified for compilation
+// Send tokens via Noble to another chain
 pub fn send_via_noble(
     deps: DepsMut,
-    _info: MessageInfo, // Prefix with underscore to address warning
+    env: Env,
+    info: MessageInfo,
     recipient_chain: String,
     recipient: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    // Simplified implementation
-    let registry_addr = crate::state::REGISTRY_ADDRESS.load(deps.storage)?;
+    // Load registry address
+    let registry_addr = REGISTRY_ADDRESS.load(deps.storage)?;
+    
+    // Get Noble gateway address from registry
     let noble_gateway = crate::cw20_handler::get_contract_address(
         deps.as_ref(), 
         &registry_addr, 
         NOBLE_GATEWAY_KEY
     )?;
     
+    let outbound = start_transfer(
+        deps, &env, &info, NOBLE_GATEWAY_KEY, recipient_chain, recipient, amount,
+    )?;
+    let transfer = &outbound.transfer;
+    
+    let noble_msgs = outbound.forward_msgs(
+        &noble_gateway,
+        to_json_binary(&NobleGatewayMsg::CrossChainTransfer {
+            recipient_chain: transfer.destination_chain.clone(),
+            recipient: transfer.recipient.clone(),
+            denom: outbound.asset.identifier().to_string(),
+            amount: transfer.amount,
+            transfer_id: transfer.id,
+        })?,
+    )?;
+    
     Ok(Response::new()
+        .add_messages(outbound.pull_msg.clone())
+        .add_messages(noble_msgs)
         .add_attribute("action", "cross_chain_send_noble")
+        .add_attribute("transfer_id", transfer.id.to_string())
         .add_attribute("gateway", noble_gateway)
-        .add_attribute("amount", amount.to_string())
-        .add_attribute("recipient_chain", recipient_chain)
-        .add_attribute("recipient", recipient))
+        .add_attribute("amount", transfer.amount.to_string())
+        .add_attribute("fee", outbound.fee.to_string())
+        .add_attribute("recipient_chain", transfer.destination_chain.clone())
+        .add_attribute("recipient", transfer.recipient.clone()))
+}
+
+// Finalize an outbound transfer from a gateway acknowledgement.
+// Only the gateway that carried the transfer may acknowledge it.
+fn handle_transfer_ack(
+    mut deps: DepsMut,
+    gateway_key: &str,
+    transfer_id: u64,
+    success: bool,
+) -> Result<Response, ContractError> {
+    let mut transfer = TRANSFERS.load(deps.storage, transfer_id)?;
+    if transfer.gateway != gateway_key {
+        return Err(ContractError::Unauthorized {});
+    }
+    if transfer.status != TransferStatus::Pending {
+        return Err(ContractError::TransferNotPending { transfer_id });
+    }
+    PENDING_TRANSFERS.remove(deps.storage, transfer_id);
+    
+    if !success {
+        // The gateway returns the tokens on failure, so the sender can be refunded right away
+        return refund(deps, transfer);
+    }
+    
+    // The tokens have left the chain
+    update_collateral_with_token_type(deps.branch(), &transfer.asset, transfer.amount, false)?;
+    transfer.status = TransferStatus::Completed;
+    TRANSFERS.save(deps.storage, transfer_id, &transfer)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "complete_transfer")
+        .add_attribute("transfer_id", transfer_id.to_string()))
+}
+
+// Pay a failed transfer back to its sender.
+// If the refund itself fails the transfer is marked failed and can be refunded again later.
+fn refund(
+    mut deps: DepsMut,
+    mut transfer: CrossChainTransfer,
+) -> Result<Response, ContractError> {
+    let registry_addr = REGISTRY_ADDRESS.load(deps.storage)?;
+    let asset = load_asset(deps.as_ref(), &registry_addr, &transfer.asset)?;
+    
+    update_collateral_with_token_type(deps.branch(), &transfer.asset, transfer.amount, false)?;
+    transfer.status = TransferStatus::Refunded;
+    TRANSFERS.save(deps.storage, transfer.id, &transfer)?;
+    PENDING_REFUND.save(deps.storage, &transfer.id)?;
+    
+    let refund_msg = asset.transfer_msg(transfer.sender.as_str(), transfer.amount)?;
+    
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_always(refund_msg, REFUND_REPLY_ID))
+        .add_attribute("action", "refund_transfer")
+        .add_attribute("transfer_id", transfer.id.to_string())
+        .add_attribute("recipient", transfer.sender)
+        .add_attribute("amount", transfer.amount.to_string()))
+}
+
+// Retry the refund of a transfer whose refund failed earlier; callable by anyone.
+// Pending transfers are never refunded here, even past their timeout: the gateway may still deliver them.
+pub fn execute_refund_transfer(
+    deps: DepsMut,
+    transfer_id: u64,
+) -> Result<Response, ContractError> {
+    let transfer = TRANSFERS.load(deps.storage, transfer_id)?;
+    if transfer.status != TransferStatus::Failed {
+        return Err(ContractError::TransferNotRefundable { transfer_id });
+    }
+    
+    refund(deps, transfer)
+}
+
+// Refund a pending transfer its gateway never acknowledged, once its timeout has passed.
+// The gateway may still hold the tokens and deliver them later, in which case the transfer is paid out twice:
+// the admin should only expire a transfer after confirming with the gateway that it was dropped.
+// A late acknowledgement of an expired transfer is rejected as no longer pending.
+pub fn execute_expire_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    transfer_id: u64,
+) -> Result<Response, ContractError> {
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+    
+    let transfer = TRANSFERS.load(deps.storage, transfer_id)?;
+    if transfer.status != TransferStatus::Pending {
+        return Err(ContractError::TransferNotPending { transfer_id });
+    }
+    if env.block.time.seconds() < transfer.timeout_at {
+        return Err(ContractError::TransferNotExpired { transfer_id, timeout_at: transfer.timeout_at });
+    }
+    PENDING_TRANSFERS.remove(deps.storage, transfer_id);
+    
+    let response = refund(deps, transfer)?;
+    Ok(response.add_attribute("expired", "true"))
+}
+
+// Roll back the refund bookkeeping when the refund transfer fails
+pub fn handle_refund_reply(
+    mut deps: DepsMut,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    let transfer_id = PENDING_REFUND.load(deps.storage)?;
+    PENDING_REFUND.remove(deps.storage);
+    
+    if let SubMsgResult::Err(err) = reply.result {
+        let mut transfer = TRANSFERS.load(deps.storage, transfer_id)?;
+        update_collateral_with_token_type(deps.branch(), &transfer.asset, transfer.amount, true)?;
+        transfer.status = TransferStatus::Failed;
+        TRANSFERS.save(deps.storage, transfer_id, &transfer)?;
+        
+        return Ok(Response::new()
+            .add_attribute("action", "refund_failed")
+            .add_attribute("transfer_id", transfer_id.to_string())
+            .add_attribute("error", err));
+    }
+    
+    Ok(Response::new())
+}
+
+// Authenticate and dispatch a message delivered by a gateway.
+// The caller must be the registered gateway, the remote sender must be whitelisted,
+// and each message id is processed at most once per gateway.
+fn handle_inbound_message(
+    mut deps: DepsMut,
+    info: &MessageInfo,
+    gateway_key: &str,
+    source_chain: &str,
+    source_address: &str,
+    payload: Binary,
+) -> Result<Response, ContractError> {
+    let registry_addr = REGISTRY_ADDRESS.load(deps.storage)?;
+    let gateway = crate::cw20_handler::get_contract_address(deps.as_ref(), &registry_addr, gateway_key)?;
+    if info.sender != gateway {
+        return Err(ContractError::Unauthorized {});
+    }
+    
+    if !TRUSTED_REMOTES.has(deps.storage, (source_chain, source_address)) {
+        return Err(ContractError::UntrustedRemote { 
+            chain: source_chain.to_string(), 
+            address: source_address.to_string(), 
+        });
+    }
+    
+    let message: CrossChainMessage = from_json(&payload)?;
+    if message.version != PAYLOAD_VERSION {
+        return Err(ContractError::UnsupportedPayloadVersion { version: message.version });
+    }
+    
+    if PROCESSED_MESSAGES.has(deps.storage, (gateway_key, &message.message_id)) {
+        return Err(ContractError::MessageAlreadyProcessed { message_id: message.message_id });
+    }
+    PROCESSED_MESSAGES.save(deps.storage, (gateway_key, &message.message_id), &true)?;
+    
+    // Each gateway carries its own USDC variant
+    let token_key = if gateway_key == AXELAR_GATEWAY_KEY { AXELAR_USDC_KEY } else { NOBLE_USDC_KEY };
+    
+    let response = match message.payload {
+        CrossChainPayload::TransferAck { transfer_id, success } => 
+            handle_transfer_ack(deps, gateway_key, transfer_id, success)?,
+        
+        // Bridged tokens delivered by the gateway are added to the asset's bucket
+        CrossChainPayload::DepositCollateral { amount } => {
+            update_collateral_with_token_type(deps, token_key, amount, true)?;
+            Response::new()
+                .add_attribute("action", "cross_chain_deposit")
+                .add_attribute("amount", amount)
+        },
+        
+        // Bridged tokens back EQA minted to a local recipient, under the PSM's fee and ceiling
+        CrossChainPayload::MintTo { recipient, amount } => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            let config = crate::psm::load_config(deps.as_ref())?;
+            let (fee, mint_msg) = crate::psm::mint_against(deps.branch(), &config, token_key, recipient.as_str(), amount)?;
+            update_collateral_with_token_type(deps, token_key, amount, true)?;
+            Response::new()
+                .add_message(mint_msg)
+                .add_attribute("action", "cross_chain_mint")
+                .add_attribute("recipient", recipient)
+                .add_attribute("amount", amount)
+                .add_attribute("fee", fee)
+        },
+        
+        // EQA burned on the remote chain releases the debt it was minted under
+        CrossChainPayload::RepayDebt { amount } => {
+            crate::psm::repay_debt(deps, token_key, amount)?;
+            Response::new()
+                .add_attribute("action", "cross_chain_repay")
+                .add_attribute("amount", amount)
+        },
+    };
+    
+    Ok(response
+        .add_attribute("gateway", gateway_key)
+        .add_attribute("message_id", message.message_id))
 }
 
 // Handle incoming cross-chain messages from Axelar
 pub fn handle_axelar_message(
-    _deps: DepsMut, // Prefix with underscore to address warning
-    _info: MessageInfo, // Prefix with underscore to address warning
+    deps: DepsMut,
+    info: MessageInfo,
     source_chain: String,
     source_address: String,
-    _payload: Binary, // Prefix with underscore to address warning
+    payload: Binary,
 ) -> Result<Response, ContractError> {
-    // Simplified implementation
-    Ok(Response::new()
-        .add_attribute("action", "handle_axelar_message")
+    let response = handle_inbound_message(deps, &info, AXELAR_GATEWAY_KEY, &source_chain, &source_address, payload)?;
+    
+    Ok(response
         .add_attribute("source_chain", source_chain)
         .add_attribute("source_address", source_address))
 }
 
 // Handle incoming cross-chain messages from Noble
 pub fn handle_noble_message(
-    _deps: DepsMut, // Prefix with underscore to address warning
-    _info: MessageInfo, // Prefix with underscore to address warning
+    deps: DepsMut,
+    info: MessageInfo,
     source_chain: String,
     sender: String,
-    _payload: Binary, // Prefix with underscore to address warning
+    payload: Binary,
 ) -> Result<Response, ContractError> {
-    // Simplified implementation
-    Ok(Response::new()
-        .add_attribute("action", "handle_noble_message")
+    let response = handle_inbound_message(deps, &info, NOBLE_GATEWAY_KEY, &source_chain, &sender, payload)?;
+    
+    Ok(response
         .add_attribute("source_chain", source_chain)
         .add_attribute("sender", sender))
 }
 
+pub fn execute_set_trusted_remote(
+    deps: DepsMut,
+    info: MessageInfo,
+    source_chain: String,
+    source_address: String,
+    trusted: bool,
+) -> Result<Response, ContractError> {
+    // Check if caller is admin
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+    
+    if trusted {
+        TRUSTED_REMOTES.save(deps.storage, (&source_chain, &source_address), &true)?;
+    } else {
+        TRUSTED_REMOTES.remove(deps.storage, (&source_chain, &source_address));
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", "set_trusted_remote")
+        .add_attribute("source_chain", source_chain)
+        .add_attribute("source_address", source_address)
+        .add_attribute("trusted", trusted.to_string()))
+}
+
+pub fn query_trusted_remotes(deps: Deps) -> StdResult<TrustedRemotesResponse> {
+    let remotes = TRUSTED_REMOTES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|key| key.map(|(source_chain, source_address)| TrustedRemote { source_chain, source_address }))
+        .collect::<StdResult<Vec<_>>>()?;
+    
+    Ok(TrustedRemotesResponse { remotes })
+}
+
+pub fn query_transfer(deps: Deps, transfer_id: u64) -> StdResult<CrossChainTransfer> {
+    TRANSFERS.load(deps.storage, transfer_id)
+}
+
+pub fn query_pending_transfers(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<TransfersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    
+    let transfers = PENDING_TRANSFERS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| TRANSFERS.load(deps.storage, id?))
+        .collect::<StdResult<Vec<_>>>()?;
+    
+    Ok(TransfersResponse { transfers })
+}
+
 // Register contract callbacks for cross-chain messages
 pub fn register_callbacks(
     deps: DepsMut,
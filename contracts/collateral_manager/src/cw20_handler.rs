@@ -1,9 +1,13 @@
 use cosmwasm_std::{
-    to_json_binary, WasmMsg, Response, Uint128, StdResult,
-    Deps, DepsMut, CosmosMsg, WasmQuery, QueryRequest, Env,
+    to_json_binary, Response, Uint128, StdResult,
+    Deps, DepsMut, WasmQuery, QueryRequest, Env, MessageInfo,
 };
-use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, BalanceResponse};
+use cw20::{Cw20QueryMsg, BalanceResponse};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::roles::{self, Role};
+
+use crate::asset::{load_asset, token_key_for, DEFAULT_IBC_TIMEOUT_SECONDS};
+use crate::state::DISABLED_COLLATERAL;
 
 // Supported stablecoin registry keys
 pub const AXELAR_USDC_KEY: &str = "axelar_usdc";
@@ -17,6 +21,7 @@ pub fn get_contract_address(
 ) -> StdResult<String> {
     let query_msg = registry::QueryMsg::GetContractAddress {
         name: contract_key.to_string(),
+        network: None, // the registry's own network
     };
     
     let query = QueryRequest::Wasm(WasmQuery::Smart {
@@ -28,77 +33,79 @@ pub fn get_contract_address(
     Ok(response.address)
 }
 
-// Verify if a token address is supported by checking registry
+// Verify if a token address or native denom is supported by checking registry
 pub fn is_supported_token(
     deps: Deps, 
     registry_addr: &str,
     token_addr: &str,
 ) -> StdResult<bool> {
-    let axelar_addr = get_contract_address(deps, registry_addr, AXELAR_USDC_KEY)?;
-    let noble_addr = get_contract_address(deps, registry_addr, NOBLE_USDC_KEY)?;
+    for key in [AXELAR_USDC_KEY, NOBLE_USDC_KEY] {
+        if load_asset(deps, registry_addr, key)?.identifier() == token_addr {
+            return Ok(true);
+        }
+    }
     
-    Ok(token_addr == axelar_addr || token_addr == noble_addr)
+    Ok(false)
 }
 
-// Get token type from address using registry
+// Get token type from a CW20 address or native denom using registry
 pub fn get_token_type(
     deps: Deps,
     registry_addr: &str,
     token_addr: &str,
 ) -> Result<&'static str, ContractError> {
-    let axelar_addr = get_contract_address(deps, registry_addr, AXELAR_USDC_KEY)?;
-    let noble_addr = get_contract_address(deps, registry_addr, NOBLE_USDC_KEY)?;
-    
-    if token_addr == axelar_addr {
-        return Ok(AXELAR_USDC_KEY);
-    } else if token_addr == noble_addr {
-        return Ok(NOBLE_USDC_KEY);
-    }
-    
-    Err(ContractError::CustomError { 
-        msg: format!("Unsupported token address: {}", token_addr) 
-    })
+    token_key_for(deps, registry_addr, token_addr)
 }
 
-// Transfer tokens from user to contract
+// Take tokens from the caller into the contract.
+// CW20s are pulled with TransferFrom, native coins must be attached to the call.
 pub fn receive_tokens(
     deps: DepsMut,
-    _env: Env, // Prefixed with underscore to address the warning
+    env: Env,
+    info: &MessageInfo,
     registry_addr: &str,
     token_addr: String,
-    from: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
     if !is_supported_token(deps.as_ref(), registry_addr, &token_addr)? {
-        return Err(ContractError::CustomError { 
-            msg: format!("Unsupported token address: {}", token_addr) 
-        });
+        return Err(ContractError::UnsupportedAsset { asset: token_addr });
     }
     
-    // Create TransferFrom message to pull tokens from user to this contract
-    let transfer_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: token_addr.clone(),
-        msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
-            owner: from.clone(),
-            recipient: "recipient_address".to_string(), // Simplified for testing
-            amount,
-        })?,
-        funds: vec![],
-    });
+    let token_type = get_token_type(deps.as_ref(), registry_addr, &token_addr)?;
+    let asset = load_asset(deps.as_ref(), registry_addr, token_type)?;
+    let receive_msg = asset.receive_msg(&env, info, amount)?;
     
     // Update collateral state based on token type
-    let token_type = get_token_type(deps.as_ref(), registry_addr, &token_addr)?;
+    if DISABLED_COLLATERAL.has(deps.storage, token_type) {
+        return Err(ContractError::AssetDisabled { asset: token_type.to_string() });
+    }
     update_collateral_with_token_type(deps, token_type, amount, true)?;
     
     Ok(Response::new()
-        .add_message(transfer_msg)
+        .add_messages(receive_msg)
         .add_attribute("action", "receive_tokens")
         .add_attribute("token", token_type)
-        .add_attribute("from", from)
+        .add_attribute("from", info.sender.as_str())
         .add_attribute("amount", amount.to_string()))
 }
 
-// Send tokens from contract to user
+// Pay collateral out of the contract on the admin's instruction
+pub fn execute_send_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    registry_addr: &str,
+    token_addr: String,
+    to: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // Only the admin moves protocol collateral out
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+    
+    send_tokens(deps, env, registry_addr, token_addr, to, amount)
+}
+
+// Send tokens from contract to user; callers are responsible for authorizing the payout
 pub fn send_tokens(
     deps: DepsMut,
     _env: Env, // Prefixed with underscore to address the warning
@@ -108,23 +115,14 @@ pub fn send_tokens(
     amount: Uint128,
 ) -> Result<Response, ContractError> {
     if !is_supported_token(deps.as_ref(), registry_addr, &token_addr)? {
-        return Err(ContractError::CustomError { 
-            msg: format!("Unsupported token address: {}", token_addr) 
-        });
+        return Err(ContractError::UnsupportedAsset { asset: token_addr });
     }
     
-    // Create Transfer message to send tokens from this contract to the user
-    let transfer_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: token_addr.clone(),
-        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: to.clone(),
-            amount,
-        })?,
-        funds: vec![],
-    });
+    // Transfer for CW20s, bank send for native coins
+    let token_type = get_token_type(deps.as_ref(), registry_addr, &token_addr)?;
+    let transfer_msg = load_asset(deps.as_ref(), registry_addr, token_type)?.transfer_msg(&to, amount)?;
     
     // Update collateral state based on token type
-    let token_type = get_token_type(deps.as_ref(), registry_addr, &token_addr)?;
     update_collateral_with_token_type(deps, token_type, amount, false)?;
     
     Ok(Response::new()
@@ -135,6 +133,40 @@ pub fn send_tokens(
         .add_attribute("amount", amount.to_string()))
 }
 
+// Send native collateral to another chain over IBC
+#[allow(clippy::too_many_arguments)]
+pub fn send_tokens_via_ibc(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    registry_addr: &str,
+    token_addr: String,
+    channel_id: String,
+    recipient: String,
+    amount: Uint128,
+    timeout_seconds: Option<u64>,
+) -> Result<Response, ContractError> {
+    // Only the admin moves protocol collateral off-chain
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+    
+    let token_type = get_token_type(deps.as_ref(), registry_addr, &token_addr)?;
+    let asset = load_asset(deps.as_ref(), registry_addr, token_type)?;
+    let timeout_seconds = timeout_seconds.unwrap_or(DEFAULT_IBC_TIMEOUT_SECONDS);
+    let ibc_msg = asset.ibc_transfer_msg(&env, channel_id.clone(), recipient.clone(), amount, timeout_seconds)?;
+    
+    // Timed-out transfers are returned to this contract's bank balance and can be re-sent
+    update_collateral_with_token_type(deps, token_type, amount, false)?;
+    
+    Ok(Response::new()
+        .add_message(ibc_msg)
+        .add_attribute("action", "send_tokens_via_ibc")
+        .add_attribute("token", token_type)
+        .add_attribute("channel_id", channel_id)
+        .add_attribute("to", recipient)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("timeout_seconds", timeout_seconds.to_string()))
+}
+
 // Query the balance of a specific token
 pub fn query_token_balance(
     deps: Deps,
@@ -169,8 +201,9 @@ pub fn update_collateral_with_token_type(
                     state.usdc_axelar += amount;
                 } else {
                     if state.usdc_axelar < amount {
-                        return Err(ContractError::CustomError { 
-                            msg: "Insufficient Axelar USDC balance".to_string() 
+                        return Err(ContractError::InsufficientCollateral { 
+                            required: amount, 
+                            available: state.usdc_axelar, 
                         });
                     }
                     state.usdc_axelar -= amount;
@@ -181,16 +214,15 @@ pub fn update_collateral_with_token_type(
                     state.usdc_noble += amount;
                 } else {
                     if state.usdc_noble < amount {
-                        return Err(ContractError::CustomError { 
-                            msg: "Insufficient Noble USDC balance".to_string() 
+                        return Err(ContractError::InsufficientCollateral { 
+                            required: amount, 
+                            available: state.usdc_noble, 
                         });
                     }
                     state.usdc_noble -= amount;
                 }
             },
-            _ => return Err(ContractError::CustomError { 
-                msg: format!("Unsupported token type: {}", token_type) 
-            }),
+            _ => return Err(ContractError::UnsupportedAsset { asset: token_type.to_string() }),
         }
         
         // Update total locked amount
@@ -1,17 +1,30 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
-    Addr,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, Uint128,
+    Addr, Decimal, from_json,
 };
+use cw20::Cw20ReceiveMsg;
+use equilibria_smart_contracts::config::{ChainIdMapping, ChainIdPattern, NetworkEnvironment};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::migration;
+use equilibria_smart_contracts::network::{self, NetworkManager};
+use equilibria_smart_contracts::ownership::{self, OwnershipAction};
+use equilibria_smart_contracts::roles::{self, Role};
 
+mod asset;
 mod contract;
 mod cw20_handler;
 mod cross_chain;
 mod state;
 mod network_integration;
+mod psm;
+mod rate_limit;
 
 use crate::state::REGISTRY_ADDRESS;
 
+// Stored at instantiation and checked on migration
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[entry_point]
 pub fn instantiate(
     mut deps: DepsMut,
@@ -19,7 +32,12 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    migration::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    network::instantiate_chain_id_mappings(deps.storage, msg.chain_id_mappings.as_deref())?;
+    NetworkManager::verify_network(deps.as_ref(), &env, None)?;
+    
     // Save registry address
+    contract::validate_registry_address(deps.api, &msg.registry_address).into_result()?;
     REGISTRY_ADDRESS.save(deps.storage, &msg.registry_address)?;
     
     // Initialize the contract using branch to avoid cloning
@@ -48,20 +66,40 @@ pub fn execute(
         
         ExecuteMsg::ReceiveTokens { token_addr, amount } => {
             let registry = REGISTRY_ADDRESS.load(deps.storage)?;
-            cw20_handler::receive_tokens(deps, env, &registry, token_addr, info.sender.to_string(), amount)
+            cw20_handler::receive_tokens(deps, env, &info, &registry, token_addr, amount)
         },
         
         ExecuteMsg::SendTokens { token_addr, recipient, amount } => {
             let registry = REGISTRY_ADDRESS.load(deps.storage)?;
-            cw20_handler::send_tokens(deps, env, &registry, token_addr, recipient, amount)
+            cw20_handler::execute_send_tokens(deps, env, info, &registry, token_addr, recipient, amount)
+        },
+        
+        ExecuteMsg::SendTokensViaIbc { token_addr, channel_id, recipient, amount, timeout_seconds } => {
+            let registry = REGISTRY_ADDRESS.load(deps.storage)?;
+            cw20_handler::send_tokens_via_ibc(
+                deps, env, info, &registry, token_addr, channel_id, recipient, amount, timeout_seconds,
+            )
         },
         
+        ExecuteMsg::SetNativeDenom { asset, denom } => 
+            contract::execute_set_native_denom(deps, info, asset, denom),
+        
+        ExecuteMsg::UpdateOwnership(action) => 
+            ownership::execute_update_ownership(deps, env, info, action),
+        ExecuteMsg::GrantRole { role, address } => 
+            roles::execute_grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => 
+            roles::execute_revoke_role(deps, info, role, address),
+        ExecuteMsg::SetChainIdMapping { pattern, environment } => 
+            network::execute_set_chain_id_mapping(deps, info, pattern, environment),
+        
+        ExecuteMsg::SetCollateralEnabled { asset, enabled } => 
+            contract::execute_set_collateral_enabled(deps, info, asset, enabled),
+        
         ExecuteMsg::UpdateRegistry { new_registry_address } => {
-            let admin = crate::state::ADMIN.load(deps.storage)?;
-            if info.sender != admin {
-                return Err(ContractError::Unauthorized {});
-            }
+            roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
             
+            contract::validate_registry_address(deps.api, &new_registry_address).into_result()?;
             REGISTRY_ADDRESS.save(deps.storage, &new_registry_address)?;
             
             Ok(Response::new()
@@ -70,31 +108,112 @@ pub fn execute(
         },
         
         ExecuteMsg::SendViaAxelar { destination_chain, destination_address, amount } => 
-            cross_chain::send_via_axelar(deps, info, destination_chain, destination_address, amount),
+            cross_chain::send_via_axelar(deps, env, info, destination_chain, destination_address, amount),
             
         ExecuteMsg::SendViaNoble { recipient_chain, recipient, amount } => 
-            cross_chain::send_via_noble(deps, info, recipient_chain, recipient, amount),
+            cross_chain::send_via_noble(deps, env, info, recipient_chain, recipient, amount),
             
         ExecuteMsg::HandleAxelarMessage { source_chain, source_address, payload } => 
             cross_chain::handle_axelar_message(deps, info, source_chain, source_address, payload),
             
         ExecuteMsg::HandleNobleMessage { source_chain, sender, payload } => 
             cross_chain::handle_noble_message(deps, info, source_chain, sender, payload),
+        
+        ExecuteMsg::SetBridgeFee { gateway, destination_chain, fee } => 
+            cross_chain::execute_set_bridge_fee(deps, info, gateway, destination_chain, fee),
+        
+        ExecuteMsg::SetRateLimit { asset, destination_chain, limit } => 
+            rate_limit::execute_set_rate_limit(deps, info, asset, destination_chain, limit),
+        
+        ExecuteMsg::TightenRateLimit { asset, destination_chain, max_outflow } => 
+            rate_limit::execute_tighten_rate_limit(deps, info, asset, destination_chain, max_outflow),
+        
+        ExecuteMsg::SetTrustedRemote { source_chain, source_address, trusted } => 
+            cross_chain::execute_set_trusted_remote(deps, info, source_chain, source_address, trusted),
+        
+        ExecuteMsg::RefundTransfer { transfer_id } => 
+            cross_chain::execute_refund_transfer(deps, transfer_id),
+        
+        ExecuteMsg::ExpireTransfer { transfer_id } => 
+            cross_chain::execute_expire_transfer(deps, env, info, transfer_id),
+        
+        ExecuteMsg::ConfigurePsm { eqa_token, fee } => 
+            psm::execute_configure(deps, info, eqa_token, fee),
+        
+        ExecuteMsg::SetPsmDebtCeiling { asset, ceiling } => 
+            psm::execute_set_debt_ceiling(deps, info, asset, ceiling),
+        
+        ExecuteMsg::PsmSwapIn { token_addr, amount } => 
+            psm::execute_swap_in(deps, env, info, token_addr, amount),
+        
+        ExecuteMsg::Receive(cw20_msg) => {
+            match from_json(&cw20_msg.msg)? {
+                ReceiveMsg::PsmSwapOut { token_addr } => 
+                    psm::execute_swap_out(deps, env, info, cw20_msg.sender, token_addr, cw20_msg.amount),
+            }
+        },
     }
 }
 
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        cross_chain::REFUND_REPLY_ID => cross_chain::handle_refund_reply(deps, msg),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let res = match msg {
         QueryMsg::GetCollateralInfo {} => 
             to_json_binary(&contract::query_collateral_info(deps)?),
+        QueryMsg::GetCollateralAssets {} => 
+            to_json_binary(&contract::query_collateral_assets(deps)?),
         QueryMsg::GetTokenBalance { token_addr, account } => 
             to_json_binary(&cw20_handler::query_token_balance(deps, token_addr, account)?),
         QueryMsg::GetRegistryAddress {} => {
             let registry = REGISTRY_ADDRESS.load(deps.storage)?;
             to_json_binary(&RegistryResponse { address: registry })
         },
+        QueryMsg::ValidateConfig {} => 
+            to_json_binary(&contract::query_validate_config(deps)?),
+        QueryMsg::GetResolvedAddresses { names } => 
+            to_json_binary(&network::query_resolved_addresses(deps, names)?),
+        QueryMsg::GetTransfer { transfer_id } => 
+            to_json_binary(&cross_chain::query_transfer(deps, transfer_id)?),
+        QueryMsg::PendingTransfers { start_after, limit } => 
+            to_json_binary(&cross_chain::query_pending_transfers(deps, start_after, limit)?),
+        QueryMsg::BridgeFeeQuote { gateway, destination_chain, amount } => 
+            to_json_binary(&cross_chain::query_bridge_fee_quote(deps, gateway, destination_chain, amount)?),
+        QueryMsg::RateLimitCapacity { asset, destination_chain } => 
+            to_json_binary(&rate_limit::query_capacity(deps, env, asset, destination_chain)?),
+        QueryMsg::ListTrustedRemotes {} => 
+            to_json_binary(&cross_chain::query_trusted_remotes(deps)?),
+        QueryMsg::GetPsmInfo {} => 
+            to_json_binary(&psm::query_psm_info(deps)?),
+        QueryMsg::GetOwnership {} => 
+            to_json_binary(&ownership::get_ownership(deps.storage)?),
+        QueryMsg::HasRole { role, address } => 
+            to_json_binary(&roles::query_has_role(deps, role, address)?),
+        QueryMsg::RoleMembers { role, start_after, limit } => 
+            to_json_binary(&roles::query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::GetChainIdMappings {} => 
+            to_json_binary(&network::query_chain_id_mappings(deps, &env)?),
+    };
+    Ok(res?)
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = migration::migrate_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    
+    // Contracts without a stored version predate the ownership module
+    if previous.is_none() {
+        migration::migrate_legacy_admin(deps.storage)?;
     }
+    
+    Ok(migration::migrate_response(previous, CONTRACT_VERSION))
 }
 
 use schemars::JsonSchema;
@@ -105,20 +224,37 @@ pub struct InstantiateMsg {
     pub admin: Option<Addr>,
     pub registry_address: String,
     pub register_cross_chain: Option<bool>,
+    pub chain_id_mappings: Option<Vec<ChainIdMapping>>, // added to the default mappings before the network is verified
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     // Original functionality
     UpdateCollateral { usdc_axelar: Uint128, usdc_noble: Uint128 },
     
-    // CW20 token operations
-    ReceiveTokens { token_addr: String, amount: Uint128 },
-    SendTokens { token_addr: String, recipient: String, amount: Uint128 },
+    // Token operations; `token_addr` is a CW20 address or, for IBC-native collateral, a bank denom
+    ReceiveTokens { token_addr: String, amount: Uint128 }, // native coins are attached as funds
+    SendTokens { token_addr: String, recipient: String, amount: Uint128 }, // admin only
+    SendTokensViaIbc { 
+        token_addr: String, 
+        channel_id: String, 
+        recipient: String, 
+        amount: Uint128, 
+        timeout_seconds: Option<u64>, 
+    },
     
-    // Registry management
+    // Admin and registry management
+    UpdateOwnership(OwnershipAction),
+    GrantRole { role: Role, address: String },
+    RevokeRole { role: Role, address: String },
+    SetChainIdMapping { pattern: ChainIdPattern, environment: Option<NetworkEnvironment> }, // None removes the mapping
     UpdateRegistry { new_registry_address: String },
+    SetCollateralEnabled { asset: String, enabled: bool }, // asset is a registry token key, e.g. "axelar_usdc"
+    SetNativeDenom { asset: String, denom: Option<String> }, // None reverts to the registry's CW20
     
     // Cross-chain operations
     SendViaAxelar { destination_chain: String, destination_address: String, amount: Uint128 },
@@ -127,14 +263,137 @@ pub enum ExecuteMsg {
     // Cross-chain message handlers
     HandleAxelarMessage { source_chain: String, source_address: String, payload: Binary },
     HandleNobleMessage { source_chain: String, sender: String, payload: Binary },
+    RefundTransfer { transfer_id: u64 }, // retries a failed refund; pending transfers wait for the gateway's ack
+    ExpireTransfer { transfer_id: u64 }, // admin only: refunds a pending transfer past its timeout
+    SetTrustedRemote { source_chain: String, source_address: String, trusted: bool },
+    SetBridgeFee { gateway: String, destination_chain: String, fee: Option<BridgeFee> }, // None restores the default
+    SetRateLimit { asset: String, destination_chain: String, limit: Option<RateLimit> }, // None removes the limit
+    TightenRateLimit { asset: String, destination_chain: String, max_outflow: Uint128 }, // can only lower the limit
+    
+    // Peg stability module: 1:1 USDC <-> EQA swaps minus a fixed fee
+    ConfigurePsm { eqa_token: String, fee: Decimal },
+    SetPsmDebtCeiling { asset: String, ceiling: Uint128 }, // asset is a registry token key, e.g. "axelar_usdc"
+    PsmSwapIn { token_addr: String, amount: Uint128 },
+    Receive(Cw20ReceiveMsg),
+}
+
+// Hook messages for EQA sent to this contract through `Cw20ExecuteMsg::Send`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    PsmSwapOut { token_addr: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     GetCollateralInfo {},
+    GetCollateralAssets {},
     GetTokenBalance { token_addr: String, account: String },
     GetRegistryAddress {},
+    ValidateConfig {}, // Every invalid setting, not just the first
+    GetResolvedAddresses { names: Vec<String> }, // Addresses from the network config, with the network each came from
+    GetOwnership {},
+    HasRole { role: Role, address: String }, // The owner holds every role
+    RoleMembers { role: Role, start_after: Option<String>, limit: Option<u32> },
+    GetChainIdMappings {}, // Also reports the network the current chain id maps to
+    GetPsmInfo {},
+    GetTransfer { transfer_id: u64 },
+    PendingTransfers { start_after: Option<u64>, limit: Option<u32> },
+    ListTrustedRemotes {},
+    BridgeFeeQuote { gateway: String, destination_chain: String, amount: Uint128 },
+    RateLimitCapacity { asset: String, destination_chain: String },
+}
+
+// Envelope for payloads delivered by the gateways through `HandleAxelarMessage`/`HandleNobleMessage`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CrossChainMessage {
+    pub version: u16,
+    pub message_id: String, // unique per gateway, used to reject replays
+    pub payload: CrossChainPayload,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossChainPayload {
+    TransferAck { transfer_id: u64, success: bool },
+    DepositCollateral { amount: Uint128 },
+    MintTo { recipient: String, amount: Uint128 },
+    RepayDebt { amount: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeFeePayment {
+    Denom { denom: String }, // attached to the send as funds
+    Deducted {},             // taken out of the transferred amount
+}
+
+// Fee charged by a gateway for one destination chain; it is forwarded to the gateway with the transfer
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BridgeFee {
+    pub amount: Uint128,
+    pub payment: BridgeFeePayment,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BridgeFeeQuoteResponse {
+    pub fee: Uint128,
+    pub payment: BridgeFeePayment,
+    pub amount_delivered: Uint128,
+}
+
+// Most of an asset that may leave for one destination chain within any rolling window
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimit {
+    pub max_outflow: Uint128,
+    pub window_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimitCapacityResponse {
+    pub limit: Option<RateLimit>,
+    pub used: Uint128,
+    pub remaining: Option<Uint128>, // None when the route has no limit
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TrustedRemote {
+    pub source_chain: String,
+    pub source_address: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TrustedRemotesResponse {
+    pub remotes: Vec<TrustedRemote>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    Pending,
+    Completed,
+    Failed,
+    Refunded,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CrossChainTransfer {
+    pub id: u64,
+    pub gateway: String, // registry key of the gateway, e.g. "noble_gateway"
+    pub asset: String,   // registry token key, e.g. "noble_usdc"
+    pub sender: Addr,
+    pub destination_chain: String,
+    pub recipient: String,
+    pub amount: Uint128,
+    pub status: TransferStatus,
+    pub created_at: u64,
+    pub timeout_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TransfersResponse {
+    pub transfers: Vec<CrossChainTransfer>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -144,7 +403,41 @@ pub struct CollateralResponse {
     pub total_locked: Uint128,
 }
 
+// A collateral asset is either a CW20 token or a native (IBC) bank denom
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollateralAsset {
+    Cw20 { contract_addr: String },
+    Native { denom: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollateralAssetInfo {
+    pub asset: String,
+    pub info: CollateralAsset,
+    pub enabled: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollateralAssetsResponse {
+    pub assets: Vec<CollateralAssetInfo>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct RegistryResponse {
     pub address: String,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PsmAssetInfo {
+    pub asset: String,
+    pub debt_ceiling: Uint128,
+    pub debt: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PsmInfoResponse {
+    pub eqa_token: Option<Addr>,
+    pub fee: Decimal,
+    pub assets: Vec<PsmAssetInfo>,
+}
@@ -2,6 +2,7 @@ use cosmwasm_std::{DepsMut, Deps, Env, MessageInfo, Response, StdResult};
 use equilibria_smart_contracts::config::NetworkEnvironment;
 use equilibria_smart_contracts::network::NetworkManager;
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::roles::{self, Role};
 
 /// Re-configure network environment based on current chain
 pub fn auto_configure_network(
@@ -22,14 +23,12 @@ pub fn set_network_environment(
     network: NetworkEnvironment
 ) -> Result<Response, ContractError> {
     // Load admin to check authorization
-    let admin = crate::state::ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(ContractError::Unauthorized {});
-    }
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
     
     // Update network in configuration
     equilibria_smart_contracts::config::storage::set_active_network(
         deps.storage,
+        deps.api,
         network.clone(),
     )?;
     
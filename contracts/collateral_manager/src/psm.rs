@@ -0,0 +1,226 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Api, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use equilibria_smart_contracts::config::ConfigValidator;
+use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::roles::{self, Role};
+
+use crate::cw20_handler::{self, AXELAR_USDC_KEY, NOBLE_USDC_KEY};
+use crate::state::{PsmConfig, PSM_CONFIG, PSM_DEBT, PSM_DEBT_CEILINGS, REGISTRY_ADDRESS};
+use crate::{PsmAssetInfo, PsmInfoResponse};
+
+// The PSM fee is flat and kept small so swaps stay close to 1:1
+const MAX_PSM_FEE: Decimal = Decimal::percent(1);
+
+// Fee charged on a swap, rounded up in favour of the protocol
+fn swap_fee(config: &PsmConfig, amount: Uint128) -> Uint128 {
+    amount.mul_ceil(config.fee)
+}
+
+pub fn validate_config(api: &dyn Api, config: &PsmConfig) -> ConfigValidator {
+    let mut validator = ConfigValidator::new();
+    validator
+        .address(api, "eqa_token", config.eqa_token.as_str())
+        .range("fee", config.fee, Decimal::zero(), MAX_PSM_FEE);
+    validator
+}
+
+pub fn load_config(deps: Deps) -> Result<PsmConfig, ContractError> {
+    PSM_CONFIG.may_load(deps.storage)?.ok_or_else(|| ContractError::NotConfigured {
+        component: "peg_stability_module".to_string(),
+    })
+}
+
+pub fn execute_configure(
+    deps: DepsMut,
+    info: MessageInfo,
+    eqa_token: String,
+    fee: Decimal,
+) -> Result<Response, ContractError> {
+    // Check if caller is admin
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+
+    // The collateral manager must be a minter on the EQA token for swaps in to succeed
+    let config = PsmConfig { eqa_token: Addr::unchecked(eqa_token), fee };
+    validate_config(deps.api, &config).into_result()?;
+    PSM_CONFIG.save(deps.storage, &config)?;
+    let eqa_token = config.eqa_token;
+
+    Ok(Response::new()
+        .add_attribute("action", "configure_psm")
+        .add_attribute("eqa_token", eqa_token)
+        .add_attribute("fee", fee.to_string()))
+}
+
+pub fn execute_set_debt_ceiling(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    ceiling: Uint128,
+) -> Result<Response, ContractError> {
+    // Check if caller is admin
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+
+    if asset != AXELAR_USDC_KEY && asset != NOBLE_USDC_KEY {
+        return Err(ContractError::UnsupportedAsset { asset });
+    }
+
+    // Lowering the ceiling below the current debt only blocks new swaps in
+    PSM_DEBT_CEILINGS.save(deps.storage, &asset, &ceiling)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_psm_debt_ceiling")
+        .add_attribute("asset", asset)
+        .add_attribute("ceiling", ceiling))
+}
+
+/// Record `amount` of a USDC variant as PSM backing, within the asset's debt ceiling.
+/// Returns the fee kept and the mint message for `amount - fee` EQA.
+pub fn mint_against(
+    deps: DepsMut,
+    config: &PsmConfig,
+    token_type: &str,
+    recipient: &str,
+    amount: Uint128,
+) -> Result<(Uint128, WasmMsg), ContractError> {
+    let fee = swap_fee(config, amount);
+    let minted = amount - fee;
+
+    let ceiling = PSM_DEBT_CEILINGS.may_load(deps.storage, token_type)?.unwrap_or_default();
+    let debt = PSM_DEBT.may_load(deps.storage, token_type)?.unwrap_or_default();
+    let new_debt = debt.checked_add(minted).map_err(StdError::from)?;
+    if new_debt > ceiling {
+        return Err(ContractError::CapExceeded {
+            asset: token_type.to_string(),
+            requested: minted,
+            available: ceiling.saturating_sub(debt),
+        });
+    }
+    PSM_DEBT.save(deps.storage, token_type, &new_debt)?;
+
+    let mint_msg = WasmMsg::Execute {
+        contract_addr: config.eqa_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: minted,
+        })?,
+        funds: vec![],
+    };
+
+    Ok((fee, mint_msg))
+}
+
+/// Reduce the EQA debt outstanding against a USDC variant
+pub fn repay_debt(deps: DepsMut, token_type: &str, amount: Uint128) -> Result<(), ContractError> {
+    let debt = PSM_DEBT.may_load(deps.storage, token_type)?.unwrap_or_default();
+    if debt < amount {
+        return Err(ContractError::InsufficientCollateral { required: amount, available: debt });
+    }
+    PSM_DEBT.save(deps.storage, token_type, &(debt - amount))?;
+
+    Ok(())
+}
+
+// USDC in, EQA out: pull `amount` USDC from the sender and mint `amount - fee` EQA to them
+pub fn execute_swap_in(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_addr: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.as_ref())?;
+    if amount.is_zero() {
+        return Err(ContractError::InvalidAmount {});
+    }
+
+    let registry = REGISTRY_ADDRESS.load(deps.storage)?;
+    let token_type = cw20_handler::get_token_type(deps.as_ref(), &registry, &token_addr)?;
+
+    let (fee, mint_msg) = mint_against(deps.branch(), &config, token_type, info.sender.as_str(), amount)?;
+    let minted = amount - fee;
+
+    // The full amount, fee included, lands in the asset's collateral bucket
+    let transfer = cw20_handler::receive_tokens(deps.branch(), env, &info, &registry, token_addr, amount)?;
+
+    Ok(Response::new()
+        .add_submessages(transfer.messages)
+        .add_message(mint_msg)
+        .add_attribute("action", "psm_swap_in")
+        .add_attribute("token", token_type)
+        .add_attribute("sender", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("fee", fee)
+        .add_attribute("minted", minted))
+}
+
+// EQA in, USDC out: burn the EQA received and send back `amount - fee` of the chosen USDC
+pub fn execute_swap_out(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: String,
+    token_addr: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = load_config(deps.as_ref())?;
+
+    // Only EQA sent through its own token contract can be swapped out
+    if info.sender != config.eqa_token {
+        return Err(ContractError::InvalidToken {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::InvalidAmount {});
+    }
+
+    let registry = REGISTRY_ADDRESS.load(deps.storage)?;
+    let token_type = cw20_handler::get_token_type(deps.as_ref(), &registry, &token_addr)?;
+
+    // Each asset only backs the EQA the PSM minted against it
+    repay_debt(deps.branch(), token_type, amount)?;
+
+    let fee = swap_fee(&config, amount);
+    let returned = amount - fee;
+
+    let transfer = cw20_handler::send_tokens(
+        deps.branch(), env, &registry, token_addr, sender.clone(), returned,
+    )?;
+
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: config.eqa_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(burn_msg)
+        .add_submessages(transfer.messages)
+        .add_attribute("action", "psm_swap_out")
+        .add_attribute("token", token_type)
+        .add_attribute("sender", sender)
+        .add_attribute("amount", amount)
+        .add_attribute("fee", fee)
+        .add_attribute("returned", returned))
+}
+
+pub fn query_psm_info(deps: Deps) -> StdResult<PsmInfoResponse> {
+    let config = PSM_CONFIG.may_load(deps.storage)?;
+
+    let assets = [AXELAR_USDC_KEY, NOBLE_USDC_KEY]
+        .iter()
+        .map(|asset| {
+            Ok(PsmAssetInfo {
+                asset: asset.to_string(),
+                debt_ceiling: PSM_DEBT_CEILINGS.may_load(deps.storage, asset)?.unwrap_or_default(),
+                debt: PSM_DEBT.may_load(deps.storage, asset)?.unwrap_or_default(),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PsmInfoResponse {
+        eqa_token: config.as_ref().map(|config| config.eqa_token.clone()),
+        fee: config.map(|config| config.fee).unwrap_or_default(),
+        assets,
+    })
+}
@@ -0,0 +1,188 @@
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128};
+use equilibria_smart_contracts::config::ConfigValidator;
+use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::roles::{self, Role};
+
+use crate::cw20_handler::{AXELAR_USDC_KEY, NOBLE_USDC_KEY};
+use crate::state::{Outflow, OUTFLOWS, RATE_LIMITS};
+use crate::{RateLimit, RateLimitCapacityResponse};
+
+// Window used when a limit is first created by tightening
+pub const DEFAULT_WINDOW_SECONDS: u64 = 24 * 60 * 60;
+
+pub fn validate_limit(limit: &RateLimit) -> ConfigValidator {
+    let mut validator = ConfigValidator::new();
+    validator.check("window_seconds", limit.window_seconds > 0, "must be at least one second");
+    validator
+}
+
+fn validate_asset(asset: &str) -> Result<(), ContractError> {
+    if asset != AXELAR_USDC_KEY && asset != NOBLE_USDC_KEY {
+        return Err(ContractError::UnsupportedAsset { asset: asset.to_string() });
+    }
+
+    Ok(())
+}
+
+// Move the outflow record forward to the window containing `now`
+fn roll(mut outflow: Outflow, now: u64, window_seconds: u64) -> Outflow {
+    let elapsed_windows = now.saturating_sub(outflow.window_start) / window_seconds;
+    match elapsed_windows {
+        0 => {}
+        1 => {
+            outflow.previous = outflow.current;
+            outflow.current = Uint128::zero();
+        }
+        _ => {
+            outflow.previous = Uint128::zero();
+            outflow.current = Uint128::zero();
+        }
+    }
+    outflow.window_start += elapsed_windows * window_seconds;
+    outflow
+}
+
+// Outflow over the rolling window ending at `now`.
+// The previous fixed window is weighted by how much of it still overlaps the rolling one.
+fn used(outflow: &Outflow, now: u64, window_seconds: u64) -> Uint128 {
+    let into_window = now.saturating_sub(outflow.window_start).min(window_seconds);
+    outflow.previous.multiply_ratio(window_seconds - into_window, window_seconds) + outflow.current
+}
+
+fn load_outflow(storage: &dyn Storage, asset: &str, destination_chain: &str, now: u64, window_seconds: u64) -> StdResult<Outflow> {
+    let outflow = OUTFLOWS
+        .may_load(storage, (asset, destination_chain))?
+        .unwrap_or(Outflow { window_start: now, current: Uint128::zero(), previous: Uint128::zero() });
+
+    Ok(roll(outflow, now, window_seconds))
+}
+
+// Once an asset has a limit on any route, its routes without one are closed rather than unlimited,
+// so an unconfigured destination can't be used to get around the limits
+fn asset_is_limited(storage: &dyn Storage, asset: &str) -> bool {
+    RATE_LIMITS.prefix(asset).keys_raw(storage, None, None, Order::Ascending).next().is_some()
+}
+
+/// Record an outbound transfer, failing if it would exceed the asset's limit for the destination
+pub fn record_outflow(
+    deps: DepsMut,
+    env: &Env,
+    asset: &str,
+    destination_chain: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let limit = match RATE_LIMITS.may_load(deps.storage, (asset, destination_chain))? {
+        Some(limit) => limit,
+        None if asset_is_limited(deps.storage, asset) => {
+            return Err(ContractError::RateLimitExceeded {
+                asset: asset.to_string(),
+                destination_chain: destination_chain.to_string(),
+                requested: amount,
+                available: Uint128::zero(),
+            });
+        }
+        None => return Ok(()),
+    };
+
+    let now = env.block.time.seconds();
+    let mut outflow = load_outflow(deps.storage, asset, destination_chain, now, limit.window_seconds)?;
+    let remaining = limit.max_outflow.saturating_sub(used(&outflow, now, limit.window_seconds));
+    if amount > remaining {
+        return Err(ContractError::RateLimitExceeded {
+            asset: asset.to_string(),
+            destination_chain: destination_chain.to_string(),
+            requested: amount,
+            available: remaining,
+        });
+    }
+
+    outflow.current += amount;
+    OUTFLOWS.save(deps.storage, (asset, destination_chain), &outflow)?;
+
+    Ok(())
+}
+
+pub fn execute_set_rate_limit(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    destination_chain: String,
+    limit: Option<RateLimit>,
+) -> Result<Response, ContractError> {
+    // Check if caller is admin
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+
+    validate_asset(&asset)?;
+
+    // Outflow recorded under a different window length can't be rolled forward, so the route starts afresh
+    let previous = RATE_LIMITS.may_load(deps.storage, (&asset, &destination_chain))?;
+    if previous.map(|limit| limit.window_seconds) != limit.as_ref().map(|limit| limit.window_seconds) {
+        OUTFLOWS.remove(deps.storage, (&asset, &destination_chain));
+    }
+
+    match &limit {
+        Some(limit) => {
+            validate_limit(limit).into_result()?;
+            RATE_LIMITS.save(deps.storage, (&asset, &destination_chain), limit)?;
+        }
+        None => RATE_LIMITS.remove(deps.storage, (&asset, &destination_chain)),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_rate_limit")
+        .add_attribute("asset", asset)
+        .add_attribute("destination_chain", destination_chain)
+        .add_attribute("max_outflow", limit.map(|limit| limit.max_outflow.to_string()).unwrap_or_default()))
+}
+
+// Lower a limit without touching its window. Governance exposes this to the guardian council,
+// so it can never loosen a limit or remove one. Limiting a new route closes the asset's other unlimited routes.
+pub fn execute_tighten_rate_limit(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: String,
+    destination_chain: String,
+    max_outflow: Uint128,
+) -> Result<Response, ContractError> {
+    // Guardians may tighten limits but never set them
+    roles::assert_role(deps.storage, Role::Guardian, &info.sender)?;
+
+    validate_asset(&asset)?;
+
+    let limit = match RATE_LIMITS.may_load(deps.storage, (&asset, &destination_chain))? {
+        Some(limit) if max_outflow > limit.max_outflow => {
+            return Err(ContractError::RateLimitNotTightened { max_outflow: limit.max_outflow });
+        }
+        Some(limit) => RateLimit { max_outflow, ..limit },
+        None => RateLimit { max_outflow, window_seconds: DEFAULT_WINDOW_SECONDS },
+    };
+    RATE_LIMITS.save(deps.storage, (&asset, &destination_chain), &limit)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "tighten_rate_limit")
+        .add_attribute("asset", asset)
+        .add_attribute("destination_chain", destination_chain)
+        .add_attribute("max_outflow", max_outflow))
+}
+
+pub fn query_capacity(
+    deps: Deps,
+    env: Env,
+    asset: String,
+    destination_chain: String,
+) -> StdResult<RateLimitCapacityResponse> {
+    let limit = RATE_LIMITS.may_load(deps.storage, (&asset, &destination_chain))?;
+
+    let (used, remaining) = match &limit {
+        Some(limit) => {
+            let now = env.block.time.seconds();
+            let outflow = load_outflow(deps.storage, &asset, &destination_chain, now, limit.window_seconds)?;
+            let used = used(&outflow, now, limit.window_seconds);
+            (used, Some(limit.max_outflow.saturating_sub(used)))
+        }
+        None if asset_is_limited(deps.storage, &asset) => (Uint128::zero(), Some(Uint128::zero())),
+        None => (Uint128::zero(), None),
+    };
+
+    Ok(RateLimitCapacityResponse { limit, used, remaining })
+}
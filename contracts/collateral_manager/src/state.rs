@@ -1,5 +1,45 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{BridgeFee, CrossChainTransfer, RateLimit};
 
-pub const ADMIN: Item<Addr> = Item::new("admin");
 pub const REGISTRY_ADDRESS: Item<String> = Item::new("registry_address");
+pub const DISABLED_COLLATERAL: Map<&str, bool> = Map::new("disabled_collateral"); // token type -> disabled
+pub const NATIVE_DENOMS: Map<&str, String> = Map::new("native_denoms"); // token type -> bank denom, for IBC-native collateral
+
+// Outbound cross-chain transfers
+pub const TRANSFER_COUNT: Item<u64> = Item::new("transfer_count");
+pub const TRANSFERS: Map<u64, CrossChainTransfer> = Map::new("transfers");
+pub const PENDING_TRANSFERS: Map<u64, bool> = Map::new("pending_transfers"); // ids of transfers still awaiting their gateway
+pub const PENDING_REFUND: Item<u64> = Item::new("pending_refund"); // transfer id awaiting the refund reply
+
+pub const BRIDGE_FEES: Map<(&str, &str), BridgeFee> = Map::new("bridge_fees"); // (gateway key, destination chain)
+
+// Outbound rate limits, keyed by (token type, destination chain)
+pub const RATE_LIMITS: Map<(&str, &str), RateLimit> = Map::new("rate_limits");
+pub const OUTFLOWS: Map<(&str, &str), Outflow> = Map::new("outflows");
+
+// Amounts sent in the current fixed window and the one before it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Outflow {
+    pub window_start: u64,
+    pub current: Uint128,
+    pub previous: Uint128,
+}
+
+// Inbound cross-chain messages
+pub const TRUSTED_REMOTES: Map<(&str, &str), bool> = Map::new("trusted_remotes"); // (source chain, source address)
+pub const PROCESSED_MESSAGES: Map<(&str, &str), bool> = Map::new("processed_messages"); // (gateway key, message id)
+
+// Peg stability module: fixed-fee 1:1 swaps between USDC and EQA
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PsmConfig {
+    pub eqa_token: Addr,
+    pub fee: Decimal,
+}
+
+pub const PSM_CONFIG: Item<PsmConfig> = Item::new("psm_config");
+pub const PSM_DEBT_CEILINGS: Map<&str, Uint128> = Map::new("psm_debt_ceilings"); // token type -> max EQA minted
+pub const PSM_DEBT: Map<&str, Uint128> = Map::new("psm_debt"); // token type -> EQA minted and not yet swapped back
@@ -1,8 +1,11 @@
-use cosmwasm_std::{DepsMut, Deps, Env, MessageInfo, Response, StdResult, StdError, Addr, Decimal};
-use terra_cosmwasm::TerraQuerier;
+use cosmwasm_std::{DepsMut, Deps, Env, MessageInfo, Response, StdResult, Addr, Decimal};
+use equilibria_smart_contracts::config::{ConfigValidator, ValidateConfigResponse, MAX_PRICE_TIMEOUT, MIN_PRICE_TIMEOUT};
 use equilibria_smart_contracts::error::ContractError;
-use crate::state::{CONFIG, REGISTERED_ASSETS, Config, RegisteredAsset};
-use crate::{PriceResponse, ExchangeRateResponse, RegisteredAssetResponse};
+use equilibria_smart_contracts::dex::DexPair;
+use equilibria_smart_contracts::ownership;
+use equilibria_smart_contracts::roles::{self, Role};
+use crate::state::{CONFIG, REGISTERED_ASSETS, PRICE_PAIRS, PRICE_OBSERVATIONS, Config, PriceObservation, RegisteredAsset};
+use crate::{PriceResponse, ExchangeRateResponse, RegisteredAssetResponse, TerraQuerier};
 
 pub fn initialize(
     deps: DepsMut,
@@ -10,10 +13,13 @@ pub fn initialize(
     admin: Addr,
     price_timeout: u64,
 ) -> Result<Response, ContractError> {
+    ownership::initialize_owner(deps.storage, deps.api, admin.as_str())?;
+    
     let config = Config {
-        admin,
         price_timeout,
+        frozen: false,
     };
+    validate_config(&config).into_result()?;
     CONFIG.save(deps.storage, &config)?;
 
     // Register default assets (Luna and USD)
@@ -35,59 +41,161 @@ pub fn initialize(
         .add_attribute("price_timeout", price_timeout.to_string()))
 }
 
-pub fn update_admin(
+const TWAP_WINDOW: u64 = 30 * 60; // in seconds
+
+fn validate_config(config: &Config) -> ConfigValidator {
+    let mut validator = ConfigValidator::new();
+    validator.range("price_timeout", config.price_timeout, MIN_PRICE_TIMEOUT, MAX_PRICE_TIMEOUT);
+    validator
+}
+
+pub fn query_validate_config(deps: Deps) -> StdResult<ValidateConfigResponse> {
+    Ok(validate_config(&CONFIG.load(deps.storage)?).into_response())
+}
+
+pub fn update_price_timeout(
     deps: DepsMut,
     info: MessageInfo,
-    new_admin: Addr,
+    new_timeout: u64,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
     
-    // Check if the sender is the current admin
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
-    }
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
     
-    config.admin = new_admin.clone();
+    config.price_timeout = new_timeout;
+    validate_config(&config).into_result()?;
     CONFIG.save(deps.storage, &config)?;
     
     Ok(Response::new()
-        .add_attribute("action", "update_admin")
-        .add_attribute("new_admin", new_admin.to_string()))
+        .add_attribute("action", "update_price_timeout")
+        .add_attribute("new_timeout", new_timeout.to_string()))
 }
 
-pub fn update_price_timeout(
+pub fn set_frozen(
     deps: DepsMut,
     info: MessageInfo,
-    new_timeout: u64,
+    frozen: bool,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
     
-    // Check if the sender is the current admin
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
-    }
+    roles::assert_role(deps.storage, Role::Guardian, &info.sender)?;
     
-    config.price_timeout = new_timeout;
+    config.frozen = frozen;
     CONFIG.save(deps.storage, &config)?;
     
     Ok(Response::new()
-        .add_attribute("action", "update_price_timeout")
-        .add_attribute("new_timeout", new_timeout.to_string()))
+        .add_attribute("action", "set_frozen")
+        .add_attribute("frozen", frozen.to_string()))
 }
 
-pub fn register_asset(
+fn ensure_not_frozen(deps: Deps) -> Result<(), ContractError> {
+    if CONFIG.load(deps.storage)?.frozen {
+        return Err(ContractError::Paused { operation: "price queries".to_string() });
+    }
+    Ok(())
+}
+
+pub fn set_price_pair(
     deps: DepsMut,
     info: MessageInfo,
     denom: String,
-    symbol: String,
+    pair: Option<DexPair>,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
+    roles::assert_role(deps.storage, Role::OracleFeeder, &info.sender)?;
     
-    // Check if the sender is the current admin
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
+    // Only registered assets can be priced
+    let _asset = REGISTERED_ASSETS.load(deps.storage, &denom)?;
+    
+    let response = Response::new()
+        .add_attribute("action", "set_price_pair")
+        .add_attribute("denom", denom.clone());
+    
+    // Samples from the previous pair don't price the new one
+    PRICE_OBSERVATIONS.remove(deps.storage, &denom);
+    
+    match pair {
+        Some(pair) => {
+            deps.api.addr_validate(pair.contract_addr.as_str())?;
+            PRICE_PAIRS.save(deps.storage, &denom, &pair)?;
+            Ok(response.add_attribute("pair", pair.contract_addr))
+        }
+        None => {
+            PRICE_PAIRS.remove(deps.storage, &denom);
+            Ok(response.add_attribute("pair", "none"))
+        }
+    }
+}
+
+pub fn record_price(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    roles::assert_role(deps.storage, Role::OracleFeeder, &info.sender)?;
+    
+    let pair = PRICE_PAIRS.may_load(deps.storage, &denom)?.ok_or_else(|| ContractError::NotConfigured {
+        component: format!("price pair for {}", denom),
+    })?;
+    
+    let now = env.block.time.seconds();
+    let mut observations = PRICE_OBSERVATIONS.may_load(deps.storage, &denom)?.unwrap_or_default();
+    
+    let response = Response::new()
+        .add_attribute("action", "record_price")
+        .add_attribute("denom", denom.clone());
+    
+    // One sample per block, so a pool moved within the block can't be sampled again
+    if observations.last().is_some_and(|last| last.timestamp >= now) {
+        return Ok(response.add_attribute("recorded", "false"));
+    }
+    
+    let price = pair.query_price(&deps.querier)?;
+    observations.push(PriceObservation { timestamp: now, price });
+    
+    // Keep the last sample before the window, it prices the window's start
+    let window_start = now.saturating_sub(TWAP_WINDOW);
+    if let Some(first) = observations.iter().rposition(|o| o.timestamp <= window_start) {
+        observations.drain(..first);
+    }
+    PRICE_OBSERVATIONS.save(deps.storage, &denom, &observations)?;
+    
+    Ok(response
+        .add_attribute("recorded", "true")
+        .add_attribute("price", price.to_string()))
+}
+
+/// Average of the recorded pair prices over the last `TWAP_WINDOW` seconds.
+/// Each price holds until the next sample, the latest until now.
+fn time_weighted_price(denom: &str, observations: &[PriceObservation], now: u64) -> Result<Decimal, ContractError> {
+    let window_start = now.saturating_sub(TWAP_WINDOW);
+    let mut weighted = Decimal::zero();
+    let mut elapsed = 0u64;
+    
+    for (i, observation) in observations.iter().enumerate() {
+        let start = observation.timestamp.max(window_start);
+        let end = observations.get(i + 1).map_or(now, |next| next.timestamp);
+        if end > start {
+            weighted = weighted.checked_add(observation.price.checked_mul(Decimal::from_ratio(end - start, 1u64))?)?;
+            elapsed += end - start;
+        }
     }
     
+    // A sample taken in this block has no weight yet
+    if elapsed == 0 {
+        return Err(ContractError::InsufficientObservations { denom: denom.to_string() });
+    }
+    Ok(weighted / Decimal::from_ratio(elapsed, 1u64))
+}
+
+pub fn register_asset(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    symbol: String,
+) -> Result<Response, ContractError> {
+    roles::assert_role(deps.storage, Role::OracleFeeder, &info.sender)?;
+    
     let asset = RegisteredAsset {
         denom: denom.clone(),
         symbol: symbol.clone(),
@@ -105,10 +213,32 @@ pub fn query_price(
     deps: Deps,
     env: Env,
     denom: String,
-) -> StdResult<PriceResponse> {
+) -> Result<PriceResponse, ContractError> {
+    ensure_not_frozen(deps)?;
+    
     // Check if the asset is registered
     let _asset = REGISTERED_ASSETS.load(deps.storage, &denom)?;
     
+    // Assets with a configured DEX pair are priced from the TWAP of its recorded spot prices
+    if PRICE_PAIRS.has(deps.storage, &denom) {
+        let now = env.block.time.seconds();
+        let observations = PRICE_OBSERVATIONS.may_load(deps.storage, &denom)?.unwrap_or_default();
+        let last_updated = match observations.last() {
+            Some(last) => last.timestamp,
+            None => return Err(ContractError::NotConfigured { component: format!("recorded price for {}", denom) }),
+        };
+        let max_age = CONFIG.load(deps.storage)?.price_timeout;
+        if now.saturating_sub(last_updated) > max_age {
+            return Err(ContractError::StalePrice { denom, last_updated, max_age });
+        }
+        
+        return Ok(PriceResponse {
+            price: time_weighted_price(&denom, &observations, now)?,
+            denom,
+            last_updated,
+        });
+    }
+    
     // Query Terra Classic oracle for price
     let querier = TerraQuerier::new(&deps.querier);
     let exchange_rate = querier.query_exchange_rate(denom.clone(), "uusd".to_string())?;
@@ -128,7 +258,9 @@ pub fn query_exchange_rate(
     env: Env,
     base_denom: String,
     quote_denom: String,
-) -> StdResult<ExchangeRateResponse> {
+) -> Result<ExchangeRateResponse, ContractError> {
+    ensure_not_frozen(deps)?;
+    
     // Check if both assets are registered
     let _base_asset = REGISTERED_ASSETS.load(deps.storage, &base_denom)?;
     let _quote_asset = REGISTERED_ASSETS.load(deps.storage, &quote_denom)?;
@@ -149,11 +281,11 @@ pub fn query_exchange_rate(
 }
 
 pub fn query_registered_assets(deps: Deps) -> StdResult<RegisteredAssetResponse> {
-    let assets: Vec<RegisteredAsset> = REGISTERED_ASSETS
+    let assets: Vec<crate::RegisteredAsset> = REGISTERED_ASSETS
         .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
         .map(|item| {
             let (_, asset) = item?;
-            Ok(RegisteredAsset {
+            Ok(crate::RegisteredAsset {
                 denom: asset.denom,
                 symbol: asset.symbol,
             })
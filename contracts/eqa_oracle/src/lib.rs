@@ -1,6 +1,6 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary as to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, 
-    Addr, StdError, Decimal
+    entry_point, to_json_binary as to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, 
+    Addr, Decimal
 };
 
 // Use real TerraQuerier in non-test environments and when terra-integration feature is enabled
@@ -14,18 +14,33 @@ use crate::mock::{MockTerraQuerier as TerraQuerier};
 #[cfg(any(test, not(feature = "terra-integration")))]
 mod mock;
 
+use equilibria_smart_contracts::dex::DexPair;
+use equilibria_smart_contracts::config::{ChainIdMapping, ChainIdPattern, NetworkEnvironment};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::migration;
+use equilibria_smart_contracts::network::{self, NetworkManager};
+use equilibria_smart_contracts::ownership::{self, OwnershipAction};
+use equilibria_smart_contracts::roles::{self, Role};
 
 mod contract;
 mod state;
 
+use crate::state::CONFIG;
+
+// Stored at instantiation and checked on migration
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    migration::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    network::instantiate_chain_id_mappings(deps.storage, msg.chain_id_mappings.as_deref())?;
+    NetworkManager::verify_network(deps.as_ref(), &env, None)?;
     contract::initialize(deps, info, msg.admin, msg.price_timeout)
 }
 
@@ -37,20 +52,46 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateAdmin { new_admin } => contract::update_admin(deps, info, new_admin),
+        ExecuteMsg::UpdateOwnership(action) => ownership::execute_update_ownership(deps, env, info, action),
+        ExecuteMsg::GrantRole { role, address } => roles::execute_grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => roles::execute_revoke_role(deps, info, role, address),
+        ExecuteMsg::SetChainIdMapping { pattern, environment } => network::execute_set_chain_id_mapping(deps, info, pattern, environment),
         ExecuteMsg::UpdatePriceTimeout { new_timeout } => contract::update_price_timeout(deps, info, new_timeout),
         ExecuteMsg::RegisterAsset { denom, symbol } => contract::register_asset(deps, info, denom, symbol),
+        ExecuteMsg::SetFrozen { frozen } => contract::set_frozen(deps, info, frozen),
+        ExecuteMsg::SetPricePair { denom, pair } => contract::set_price_pair(deps, info, denom, pair),
+        ExecuteMsg::RecordPrice { denom } => contract::record_price(deps, env, info, denom),
     }
 }
 
 #[entry_point]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let res = match msg {
         QueryMsg::GetPrice { denom } => to_binary(&contract::query_price(deps, env, denom)?),
         QueryMsg::GetExchangeRate { base_denom, quote_denom } => 
             to_binary(&contract::query_exchange_rate(deps, env, base_denom, quote_denom)?),
         QueryMsg::GetRegisteredAssets {} => to_binary(&contract::query_registered_assets(deps)?),
+        QueryMsg::ValidateConfig {} => to_binary(&contract::query_validate_config(deps)?),
+        QueryMsg::GetOwnership {} => to_binary(&ownership::get_ownership(deps.storage)?),
+        QueryMsg::HasRole { role, address } => to_binary(&roles::query_has_role(deps, role, address)?),
+        QueryMsg::RoleMembers { role, start_after, limit } => to_binary(&roles::query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::GetChainIdMappings {} => to_binary(&network::query_chain_id_mappings(deps, &env)?),
+    };
+    Ok(res?)
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = migration::migrate_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    
+    // Contracts without a stored version predate the ownership module
+    if previous.is_none() && migration::migrate_legacy_config_admin(deps.storage, "config")? {
+        // Re-save the config without its inline admin
+        let config = CONFIG.load(deps.storage)?;
+        CONFIG.save(deps.storage, &config)?;
     }
+    
+    Ok(migration::migrate_response(previous, CONTRACT_VERSION))
 }
 
 use schemars::JsonSchema;
@@ -60,14 +101,24 @@ use serde::{Deserialize, Serialize};
 pub struct InstantiateMsg {
     pub admin: Addr,
     pub price_timeout: u64, // in seconds
+    pub chain_id_mappings: Option<Vec<ChainIdMapping>>, // added to the default mappings before the network is verified
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    UpdateAdmin { new_admin: Addr },
+    UpdateOwnership(OwnershipAction),
+    GrantRole { role: Role, address: String },
+    RevokeRole { role: Role, address: String },
+    SetChainIdMapping { pattern: ChainIdPattern, environment: Option<NetworkEnvironment> }, // None removes the mapping
     UpdatePriceTimeout { new_timeout: u64 },
     RegisterAsset { denom: String, symbol: String },
+    SetFrozen { frozen: bool },
+    SetPricePair { denom: String, pair: Option<DexPair> }, // None goes back to the Terra oracle
+    RecordPrice { denom: String }, // Sample the pair's spot price into the TWAP
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -76,6 +127,11 @@ pub enum QueryMsg {
     GetPrice { denom: String },
     GetExchangeRate { base_denom: String, quote_denom: String },
     GetRegisteredAssets {},
+    ValidateConfig {}, // Every invalid config field, not just the first
+    GetOwnership {},
+    HasRole { role: Role, address: String }, // The owner holds every role
+    RoleMembers { role: Role, start_after: Option<String>, limit: Option<u32> },
+    GetChainIdMappings {}, // Also reports the network the current chain id maps to
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -1,5 +1,5 @@
 // This file provides mocks to simulate Terra Classic Oracle for testing
-use cosmwasm_std::{Deps, StdResult};
+use cosmwasm_std::{QuerierWrapper, StdResult};
 
 // Mocked TerraQuerier for testing without the terra-cosmwasm dependency
 pub struct MockTerraQuerier {}
@@ -24,7 +24,7 @@ impl MockDecimal {
 }
 
 impl MockTerraQuerier {
-    pub fn new(_querier: &Deps) -> Self {
+    pub fn new(_querier: &QuerierWrapper) -> Self {
         Self {}
     }
     
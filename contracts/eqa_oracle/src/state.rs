@@ -1,13 +1,14 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::Decimal;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cw_storage_plus::{Item, Map};
+use equilibria_smart_contracts::dex::DexPair;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
-    pub admin: Addr,
     pub price_timeout: u64,  // in seconds
+    pub frozen: bool,        // Prices are not served while frozen
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -16,5 +17,14 @@ pub struct RegisteredAsset {
     pub symbol: String, // Human-readable symbol
 }
 
+/// Spot price read from a DEX pair, held until the next observation
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceObservation {
+    pub timestamp: u64,
+    pub price: Decimal,
+}
+
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const REGISTERED_ASSETS: Map<&str, RegisteredAsset> = Map::new("registered_assets");
+pub const PRICE_PAIRS: Map<&str, DexPair> = Map::new("price_pairs"); // denom -> pair its price is read from
+pub const PRICE_OBSERVATIONS: Map<&str, Vec<PriceObservation>> = Map::new("price_observations"); // oldest first
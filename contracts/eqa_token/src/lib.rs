@@ -1,26 +1,40 @@
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, Decimal
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, Decimal
 };
+use equilibria_smart_contracts::config::{ChainIdMapping, ChainIdPattern, ConfigValidator, NetworkEnvironment, ValidateConfigResponse};
 use equilibria_smart_contracts::error::ContractError;
-use equilibria_smart_contracts::state::{TOKEN_STATE, BALANCES};
+use equilibria_smart_contracts::migration;
+use equilibria_smart_contracts::network::{self, NetworkManager};
+use equilibria_smart_contracts::ownership::{self, OwnershipAction};
+use equilibria_smart_contracts::roles::{self, Role};
+use equilibria_smart_contracts::state::{TokenState, TOKEN_STATE, BALANCES};
 
 mod contract;
 
+// Stored at instantiation and checked on migration
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    let token_state = equilibria_smart_contracts::state::TokenState {
+    migration::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    network::instantiate_chain_id_mappings(deps.storage, msg.chain_id_mappings.as_deref())?;
+    NetworkManager::verify_network(deps.as_ref(), &env, None)?;
+    let token_state = TokenState {
         total_supply: Uint128::zero(),
         owner: info.sender.clone(),
         name: msg.name,
         symbol: msg.symbol,
         decimals: msg.decimals,
     };
+    validate_token_state(&token_state).into_result()?;
     TOKEN_STATE.save(deps.storage, &token_state)?;
+    ownership::initialize_owner(deps.storage, deps.api, info.sender.as_str())?;
     
     Ok(Response::new()
         .add_attribute("action", "instantiate")
@@ -37,15 +51,52 @@ pub fn execute(
     match msg {
         ExecuteMsg::Mint { amount, market_price } => contract::execute_mint(deps, env, info, amount, market_price),
         ExecuteMsg::Redeem { amount, market_price } => contract::execute_redeem(deps, env, info, amount, market_price),
+        ExecuteMsg::UpdateOwnership(action) => ownership::execute_update_ownership(deps, env, info, action),
+        ExecuteMsg::GrantRole { role, address } => roles::execute_grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => roles::execute_revoke_role(deps, info, role, address),
+        ExecuteMsg::SetChainIdMapping { pattern, environment } => network::execute_set_chain_id_mapping(deps, info, pattern, environment),
     }
 }
 
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
-        QueryMsg::TokenInfo {} => to_binary(&query_token_info(deps)?),
+        QueryMsg::Balance { address } => to_json_binary(&query_balance(deps, address)?),
+        QueryMsg::TokenInfo {} => to_json_binary(&query_token_info(deps)?),
+        QueryMsg::ValidateConfig {} => to_json_binary(&query_validate_config(deps)?),
+        QueryMsg::GetOwnership {} => to_json_binary(&ownership::get_ownership(deps.storage)?),
+        QueryMsg::HasRole { role, address } => to_json_binary(&roles::query_has_role(deps, role, address)?),
+        QueryMsg::RoleMembers { role, start_after, limit } => to_json_binary(&roles::query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::GetChainIdMappings {} => to_json_binary(&network::query_chain_id_mappings(deps, &env)?),
+    }
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = migration::migrate_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    
+    // Contracts without a stored version predate the ownership module
+    if previous.is_none() {
+        let token_state = TOKEN_STATE.load(deps.storage)?;
+        migration::migrate_legacy_owner(deps.storage, token_state.owner)?;
     }
+    
+    Ok(migration::migrate_response(previous, CONTRACT_VERSION))
+}
+
+// Same bounds as cw20-base
+fn validate_token_state(state: &TokenState) -> ConfigValidator {
+    let mut validator = ConfigValidator::new();
+    validator
+        .range("name", state.name.len(), 3, 50)
+        .range("symbol", state.symbol.len(), 3, 12)
+        .check("symbol", state.symbol.chars().all(|c| c.is_ascii_alphabetic() || c == '-'), "must be letters or '-'")
+        .range("decimals", state.decimals, 0, 18);
+    validator
+}
+
+fn query_validate_config(deps: Deps) -> StdResult<ValidateConfigResponse> {
+    Ok(validate_token_state(&TOKEN_STATE.load(deps.storage)?).into_response())
 }
 
 fn query_balance(deps: Deps, address: String) -> StdResult<BalanceResponse> {
@@ -72,13 +123,21 @@ pub struct InstantiateMsg {
     pub name: String,
     pub symbol: String,
     pub decimals: u8,
+    pub chain_id_mappings: Option<Vec<ChainIdMapping>>, // added to the default mappings before the network is verified
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     Mint { amount: Uint128, market_price: Decimal },
     Redeem { amount: Uint128, market_price: Decimal },
+    UpdateOwnership(OwnershipAction),
+    GrantRole { role: Role, address: String },
+    RevokeRole { role: Role, address: String },
+    SetChainIdMapping { pattern: ChainIdPattern, environment: Option<NetworkEnvironment> }, // None removes the mapping
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -86,6 +145,11 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     Balance { address: String },
     TokenInfo {},
+    ValidateConfig {}, // Every invalid token setting, not just the first
+    GetOwnership {},
+    HasRole { role: Role, address: String }, // The owner holds every role
+    RoleMembers { role: Role, start_after: Option<String>, limit: Option<u32> },
+    GetChainIdMappings {}, // Also reports the network the current chain id maps to
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
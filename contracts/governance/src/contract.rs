@@ -1,38 +1,166 @@
-use cosmwasm_std::{DepsMut, Deps, Env, MessageInfo, Response, StdResult, Addr, Uint128, Order};
+use cosmwasm_std::{
+    DepsMut, Deps, Env, MessageInfo, Response, StdResult, StdError, Addr, Uint128, Order, Binary, Coin,
+    BankMsg, CosmosMsg, WasmMsg, to_json_binary,
+};
+use cw_storage_plus::{Bound, Item, Map};
+use serde::{Deserialize, Serialize};
+use equilibria_smart_contracts::config::{ConfigValidator, ValidateConfigResponse};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::migration;
+use equilibria_smart_contracts::ownership::{self, OwnershipAction};
 
-use crate::state::{CONFIG, PROPOSAL_COUNT, PROPOSALS, VOTES, Config, Proposal, Vote};
-use crate::{ProposalStatus, VoteOption, ProposalResponse, ProposalListResponse};
+use crate::{delegation, guardian, parameters};
+use crate::state::{CONFIG, PROPOSAL_COUNT, PROPOSALS, VOTES, OVERRIDDEN_POWER, Config, Proposal, Vote};
+use crate::{
+    InstantiateMsg, ProposalAction, ParameterChange, GuardianCouncil, ProposalStatus, VoteOption, ConfigResponse, ProposalResponse,
+    ProposalListResponse, VoteResponse, VoteListResponse,
+};
+
+// Pagination defaults for list queries
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+// Voting rules given to contracts migrated from before they were configurable
+const LEGACY_THRESHOLD_PERCENTAGE: u64 = 50;
+const LEGACY_VETO_THRESHOLD_PERCENTAGE: u64 = 33;
+const LEGACY_EXECUTION_PERIOD: u64 = 7 * 24 * 60 * 60;
+
+// Layout from before ownership, deposits and proposal actions
+#[derive(Serialize, Deserialize)]
+struct LegacyConfig {
+    admin: Addr,
+    voting_period: u64,
+    quorum_percentage: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyProposal {
+    id: u64,
+    title: String,
+    description: String,
+    status: ProposalStatus,
+    contract_addr: Addr,
+    new_code_id: u64,
+    yes_votes: Uint128,
+    no_votes: Uint128,
+    abstain_votes: Uint128,
+    end_time: u64,
+}
+
+const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+const LEGACY_PROPOSALS: Map<u64, LegacyProposal> = Map::new("proposals");
 
 pub fn initialize(
     deps: DepsMut,
     info: MessageInfo,
-    voting_period: u64,
-    quorum_percentage: u64,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    if quorum_percentage > 100 || quorum_percentage == 0 {
-        return Err(ContractError::CustomError { 
-            msg: "Quorum percentage must be between 1-100".to_string() 
-        });
-    }
-
     let config = Config {
-        admin: info.sender.clone(),
-        voting_period,
-        quorum_percentage,
+        voting_period: msg.voting_period,
+        quorum_percentage: msg.quorum_percentage,
+        threshold_percentage: msg.threshold_percentage,
+        veto_threshold_percentage: msg.veto_threshold_percentage,
+        proposal_deposit: msg.proposal_deposit,
+        timelock_period: msg.timelock_period,
+        execution_period: msg.execution_period,
     };
+    validate_config(&config).into_result()?;
     
+    if let Some(council) = &msg.guardian_council {
+        guardian::validate_council(deps.api, council)?;
+    }
+
+    ownership::initialize_owner(deps.storage, deps.api, info.sender.as_str())?;
     CONFIG.save(deps.storage, &config)?;
     PROPOSAL_COUNT.save(deps.storage, &0u64)?;
+    guardian::save_council(deps.storage, msg.guardian_council)?;
     
     Ok(Response::new()
         .add_attribute("action", "initialize")
         .add_attribute("admin", info.sender)
-        .add_attribute("voting_period", voting_period.to_string())
-        .add_attribute("quorum_percentage", quorum_percentage.to_string()))
+        .add_attribute("voting_period", msg.voting_period.to_string())
+        .add_attribute("quorum_percentage", msg.quorum_percentage.to_string())
+        .add_attribute("threshold_percentage", msg.threshold_percentage.to_string())
+        .add_attribute("veto_threshold_percentage", msg.veto_threshold_percentage.to_string())
+        .add_attribute("timelock_period", msg.timelock_period.to_string())
+        .add_attribute("execution_period", msg.execution_period.to_string()))
+}
+
+fn validate_config(config: &Config) -> ConfigValidator {
+    let mut validator = ConfigValidator::new();
+    validator
+        .check("voting_period", config.voting_period > 0, "must be greater than zero")
+        .range("quorum_percentage", config.quorum_percentage, 1, 100)
+        .range("threshold_percentage", config.threshold_percentage, 1, 100)
+        .range("veto_threshold_percentage", config.veto_threshold_percentage, 1, 100)
+        .check("execution_period", config.execution_period > 0, "must be greater than zero");
+    if let Some(deposit) = &config.proposal_deposit {
+        validator
+            .not_empty("proposal_deposit.denom", &deposit.denom)
+            .check("proposal_deposit.amount", !deposit.amount.is_zero(), "must be greater than zero");
+    }
+    validator
+}
+
+/// Upgrade governance from the unversioned layout.
+/// The admin becomes the owner, the voting rules added since take their defaults,
+/// and every proposal becomes an upgrade proposed by that admin. Passed proposals
+/// open their execution window at the migration.
+pub fn migrate_legacy_layout(deps: DepsMut, env: &Env) -> Result<(), ContractError> {
+    let legacy = LEGACY_CONFIG.load(deps.storage)?;
+    migration::migrate_legacy_owner(deps.storage, legacy.admin.clone())?;
+
+    let config = Config {
+        voting_period: legacy.voting_period,
+        quorum_percentage: legacy.quorum_percentage,
+        threshold_percentage: LEGACY_THRESHOLD_PERCENTAGE,
+        veto_threshold_percentage: LEGACY_VETO_THRESHOLD_PERCENTAGE,
+        proposal_deposit: None,
+        timelock_period: 0,
+        execution_period: LEGACY_EXECUTION_PERIOD,
+    };
+    validate_config(&config).into_result()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    let proposals = LEGACY_PROPOSALS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, proposal) in proposals {
+        let (executable_at, expires_at) = match proposal.status {
+            ProposalStatus::Passed => {
+                let now = env.block.time.seconds();
+                (Some(now), Some(now + config.execution_period))
+            }
+            _ => (None, None),
+        };
+        PROPOSALS.save(deps.storage, id, &Proposal {
+            id: proposal.id,
+            title: proposal.title,
+            description: proposal.description,
+            proposer: legacy.admin.clone(),
+            deposit: vec![],
+            status: proposal.status,
+            action: ProposalAction::Upgrade {
+                contract_addr: proposal.contract_addr,
+                new_code_id: proposal.new_code_id,
+                migrate_msg: Binary::from(b"{}".to_vec()),
+            },
+            yes_votes: proposal.yes_votes,
+            no_votes: proposal.no_votes,
+            abstain_votes: proposal.abstain_votes,
+            veto_votes: Uint128::zero(),
+            start_height: env.block.height,
+            end_time: proposal.end_time,
+            executable_at,
+            expires_at,
+        })?;
+    }
+
+    Ok(())
 }
 
-pub fn execute_propose(
+#[allow(clippy::too_many_arguments)]
+pub fn execute_propose_upgrade(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
@@ -40,10 +168,79 @@ pub fn execute_propose(
     description: String,
     contract_addr: Addr,
     new_code_id: u64,
+    migrate_msg: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let action = ProposalAction::Upgrade {
+        contract_addr,
+        new_code_id,
+        migrate_msg: migrate_msg.unwrap_or_else(|| Binary::from(b"{}".to_vec())),
+    };
+
+    execute_propose(deps, env, info, title, description, action)
+}
+
+pub fn execute_propose_parameter_change(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    change: ParameterChange,
+) -> Result<Response, ContractError> {
+    // Reject out-of-bounds values up front rather than after a full vote
+    parameters::validate(deps.api, &change)?;
+
+    execute_propose(deps, env, info, title, description, ProposalAction::ParameterChange { change })
+}
+
+pub fn execute_propose_guardian_council(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    council: Option<GuardianCouncil>,
+) -> Result<Response, ContractError> {
+    if let Some(council) = &council {
+        guardian::validate_council(deps.api, council)?;
+    }
+
+    execute_propose(deps, env, info, title, description, ProposalAction::UpdateGuardianCouncil { council })
+}
+
+pub fn execute_propose_accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    contract_addr: Addr,
+) -> Result<Response, ContractError> {
+    deps.api.addr_validate(contract_addr.as_str())?;
+
+    execute_propose(deps, env, info, title, description, ProposalAction::AcceptOwnership { contract_addr })
+}
+
+// Ownership message every protocol contract accepts
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum OwnableMsg {
+    UpdateOwnership(OwnershipAction),
+}
+
+fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    action: ProposalAction,
 ) -> Result<Response, ContractError> {
     // In a real implementation, you would check if sender has enough voting power
     
     let config = CONFIG.load(deps.storage)?;
+    let deposit = collect_deposit(&config, &info)?;
+
     let proposal_id = PROPOSAL_COUNT.load(deps.storage)? + 1;
     PROPOSAL_COUNT.save(deps.storage, &proposal_id)?;
     
@@ -51,13 +248,18 @@ pub fn execute_propose(
         id: proposal_id,
         title,
         description,
+        proposer: info.sender.clone(),
+        deposit,
         status: ProposalStatus::Pending,
-        contract_addr,
-        new_code_id,
+        action,
         yes_votes: Uint128::zero(),
         no_votes: Uint128::zero(),
         abstain_votes: Uint128::zero(),
+        veto_votes: Uint128::zero(),
+        start_height: env.block.height,
         end_time: env.block.time.seconds() + config.voting_period,
+        executable_at: None,
+        expires_at: None,
     };
     
     PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
@@ -68,6 +270,60 @@ pub fn execute_propose(
         .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
+// Verify the proposer attached the configured deposit and return what will be held
+fn collect_deposit(config: &Config, info: &MessageInfo) -> Result<Vec<Coin>, ContractError> {
+    let required = match &config.proposal_deposit {
+        Some(required) => required,
+        None => return Ok(vec![]),
+    };
+
+    let sent = info.funds
+        .iter()
+        .find(|c| c.denom == required.denom)
+        .map(|c| c.amount)
+        .unwrap_or_default();
+
+    if sent < required.amount {
+        return Err(ContractError::InsufficientDeposit {
+            denom: required.denom.clone(),
+            required: required.amount,
+            sent,
+        });
+    }
+
+    Ok(vec![Coin {
+        denom: required.denom.clone(),
+        amount: sent,
+    }])
+}
+
+// Return the deposit to the proposer or burn it, depending on how the proposal was settled
+fn settle_deposit(proposal: &Proposal, refund: bool) -> Option<CosmosMsg> {
+    if proposal.deposit.is_empty() {
+        return None;
+    }
+
+    let msg = if refund {
+        BankMsg::Send {
+            to_address: proposal.proposer.to_string(),
+            amount: proposal.deposit.clone(),
+        }
+    } else {
+        BankMsg::Burn {
+            amount: proposal.deposit.clone(),
+        }
+    };
+
+    Some(CosmosMsg::Bank(msg))
+}
+
+fn invalid_status(proposal: &Proposal) -> ContractError {
+    ContractError::InvalidProposalStatus {
+        proposal_id: proposal.id,
+        status: format!("{:?}", proposal.status).to_lowercase(),
+    }
+}
+
 pub fn execute_vote(
     deps: DepsMut,
     env: Env,
@@ -79,19 +335,34 @@ pub fn execute_vote(
     
     // Check if proposal is still active
     if env.block.time.seconds() > proposal.end_time {
-        return Err(ContractError::CustomError {
-            msg: "Voting period has ended".to_string(),
-        });
+        return Err(ContractError::VotingClosed { proposal_id, end_time: proposal.end_time });
     }
     
     if proposal.status != ProposalStatus::Pending {
-        return Err(ContractError::CustomError {
-            msg: "Proposal is not in voting period".to_string(),
-        });
+        return Err(invalid_status(&proposal));
     }
     
-    // In a real implementation, you would calculate voting power based on token balance
-    let voting_power = Uint128::from(1u128); // Simple 1 vote per address
+    let voting_power = delegation::voting_power(deps.as_ref(), &proposal, &info.sender)?;
+    
+    let mut updated_proposal = proposal;
+    
+    // A re-vote replaces the previous one, so its power is taken back out of the tally first
+    let previous = VOTES.may_load(deps.storage, (proposal_id, &info.sender))?;
+    if let Some(previous) = &previous {
+        remove_from_tally(&mut updated_proposal, &previous.vote, previous.voting_power)?;
+    } else if let Some(delegate) = delegation::delegate_at_start(deps.as_ref(), &updated_proposal, &info.sender)? {
+        // Voting directly overrides the delegate on this proposal, so the delegate loses this voter's power
+        let own_power = delegation::base_voting_power(deps.as_ref(), &info.sender)?;
+        OVERRIDDEN_POWER.update(deps.storage, (proposal_id, &delegate), |power| -> StdResult<_> {
+            Ok(power.unwrap_or_default() + own_power)
+        })?;
+
+        if let Some(mut delegate_vote) = VOTES.may_load(deps.storage, (proposal_id, &delegate))? {
+            remove_from_tally(&mut updated_proposal, &delegate_vote.vote, own_power)?;
+            delegate_vote.voting_power = delegate_vote.voting_power.checked_sub(own_power).map_err(StdError::from)?;
+            VOTES.save(deps.storage, (proposal_id, &delegate), &delegate_vote)?;
+        }
+    }
     
     // Record the vote
     let vote_record = Vote {
@@ -104,12 +375,7 @@ pub fn execute_vote(
     VOTES.save(deps.storage, (proposal_id, &info.sender), &vote_record)?;
     
     // Update proposal vote counts
-    let mut updated_proposal = proposal;
-    match vote {
-        VoteOption::Yes => updated_proposal.yes_votes += voting_power,
-        VoteOption::No => updated_proposal.no_votes += voting_power,
-        VoteOption::Abstain => updated_proposal.abstain_votes += voting_power,
-    }
+    add_to_tally(&mut updated_proposal, &vote, voting_power);
     
     PROPOSALS.save(deps.storage, proposal_id, &updated_proposal)?;
     
@@ -117,84 +383,248 @@ pub fn execute_vote(
         .add_attribute("action", "vote")
         .add_attribute("voter", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("vote", format!("{:?}", vote)))
+        .add_attribute("vote", format!("{:?}", vote))
+        .add_attribute("previous_vote", previous
+            .map(|v| format!("{:?}", v.vote))
+            .unwrap_or_else(|| "none".to_string())))
 }
 
-pub fn execute_proposal(
+fn add_to_tally(proposal: &mut Proposal, vote: &VoteOption, power: Uint128) {
+    match vote {
+        VoteOption::Yes => proposal.yes_votes += power,
+        VoteOption::No => proposal.no_votes += power,
+        VoteOption::Abstain => proposal.abstain_votes += power,
+        VoteOption::NoWithVeto => proposal.veto_votes += power,
+    }
+}
+
+fn remove_from_tally(proposal: &mut Proposal, vote: &VoteOption, power: Uint128) -> StdResult<()> {
+    let tally = match vote {
+        VoteOption::Yes => &mut proposal.yes_votes,
+        VoteOption::No => &mut proposal.no_votes,
+        VoteOption::Abstain => &mut proposal.abstain_votes,
+        VoteOption::NoWithVeto => &mut proposal.veto_votes,
+    };
+    *tally = tally.checked_sub(power)?;
+    Ok(())
+}
+
+/// Close voting on a proposal and decide its outcome.
+/// Returns the resulting status and whether the deposit is refunded (true) or burned (false).
+fn tally(config: &Config, proposal: &Proposal) -> (ProposalStatus, bool) {
+    let total_votes = proposal.yes_votes + proposal.no_votes + proposal.abstain_votes + proposal.veto_votes;
+
+    // Check quorum
+    let quorum = Uint128::from(100u128); // In a real implementation, this would be from total supply
+    let quorum_reached = total_votes.u128() * 100 >= quorum.u128() * config.quorum_percentage as u128;
+    if !quorum_reached {
+        return (ProposalStatus::Rejected, false);
+    }
+
+    // A large enough share of vetoes rejects the proposal and burns the deposit
+    if proposal.veto_votes.u128() * 100 > total_votes.u128() * config.veto_threshold_percentage as u128 {
+        return (ProposalStatus::Vetoed, false);
+    }
+
+    // Abstentions count towards quorum but not towards the pass threshold
+    let deciding_votes = total_votes - proposal.abstain_votes;
+    if deciding_votes.is_zero() {
+        return (ProposalStatus::Rejected, true);
+    }
+
+    if proposal.yes_votes.u128() * 100 > deciding_votes.u128() * config.threshold_percentage as u128 {
+        (ProposalStatus::Passed, true)
+    } else {
+        (ProposalStatus::Rejected, true)
+    }
+}
+
+pub fn execute_tally(
     deps: DepsMut,
     env: Env,
-    info: MessageInfo,
     proposal_id: u64,
 ) -> Result<Response, ContractError> {
     let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
     let config = CONFIG.load(deps.storage)?;
-    
+
+    if proposal.status != ProposalStatus::Pending {
+        return Err(invalid_status(&proposal));
+    }
+
     // Check if voting period has ended
     if env.block.time.seconds() <= proposal.end_time {
-        return Err(ContractError::CustomError {
-            msg: "Voting period is not over yet".to_string(),
-        });
+        return Err(ContractError::VotingNotEnded { proposal_id, end_time: proposal.end_time });
     }
-    
-    // Only admin can execute proposals for safety
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
+
+    let (status, refund) = tally(&config, &proposal);
+
+    // Passed proposals wait out the timelock, then have a limited window to be executed
+    if status == ProposalStatus::Passed {
+        let executable_at = proposal.end_time + config.timelock_period;
+        proposal.executable_at = Some(executable_at);
+        proposal.expires_at = Some(executable_at + config.execution_period);
     }
+    proposal.status = status;
+
+    let deposit_msg = settle_deposit(&proposal, refund);
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new()
+        .add_messages(deposit_msg)
+        .add_attribute("action", "tally_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("result", format!("{:?}", proposal.status).to_lowercase())
+        .add_attribute("deposit", if refund { "refunded" } else { "burned" }))
+}
+
+pub fn execute_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
     
-    // Calculate total votes and check quorum
-    let total_votes = proposal.yes_votes + proposal.no_votes + proposal.abstain_votes;
-    let quorum = Uint128::from(100u128); // In a real implementation, this would be from total supply
-    let quorum_reached = total_votes.u128() * 100 >= quorum.u128() * config.quorum_percentage as u128;
+    if proposal.status != ProposalStatus::Passed {
+        return Err(invalid_status(&proposal));
+    }
     
-    if !quorum_reached {
-        proposal.status = ProposalStatus::Rejected;
+    let now = env.block.time.seconds();
+    let executable_at = proposal.executable_at.unwrap_or(proposal.end_time);
+    if now < executable_at {
+        return Err(ContractError::ProposalTimelocked { proposal_id, executable_at });
+    }
+
+    // Passed proposals that were not executed in time expire instead
+    if proposal.expires_at.is_some_and(|expires_at| now >= expires_at) {
+        proposal.status = ProposalStatus::Expired;
         PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
-        
-        return Err(ContractError::CustomError {
-            msg: "Quorum not reached".to_string(),
-        });
+
+        return Ok(Response::new()
+            .add_attribute("action", "execute_proposal")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("result", "expired"));
+    }
+
+    proposal.status = ProposalStatus::Executed;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    // Governance must be the admin of the target contract for upgrades and parameter changes to succeed
+    let msgs: Vec<CosmosMsg> = match proposal.action {
+        ProposalAction::Upgrade { contract_addr, new_code_id, migrate_msg } => vec![WasmMsg::Migrate {
+            contract_addr: contract_addr.to_string(),
+            new_code_id,
+            msg: migrate_msg,
+        }.into()],
+        ProposalAction::ParameterChange { change } => {
+            // Bounds can change with a governance upgrade while a proposal is pending
+            parameters::validate(deps.api, &change)?;
+            vec![parameters::build_msg(change)?]
+        }
+        ProposalAction::UpdateGuardianCouncil { council } => {
+            guardian::save_council(deps.storage, council)?;
+            vec![]
+        }
+        ProposalAction::AcceptOwnership { contract_addr } => vec![WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&OwnableMsg::UpdateOwnership(OwnershipAction::AcceptOwnership {}))?,
+            funds: vec![],
+        }.into()],
+    };
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "execute_proposal")
+        .add_attribute("executor", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("result", "executed"))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    if info.sender != proposal.proposer {
+        return Err(ContractError::Unauthorized {});
     }
     
-    // Check if proposal has passed
-    let passed = proposal.yes_votes > proposal.no_votes;
+    // Proposals can only be cancelled during their voting period
+    if proposal.status != ProposalStatus::Pending {
+        return Err(invalid_status(&proposal));
+    }
+    if env.block.time.seconds() > proposal.end_time {
+        return Err(ContractError::VotingClosed { proposal_id, end_time: proposal.end_time });
+    }
     
-    if passed {
-        proposal.status = ProposalStatus::Executed;
-        
-        // In a real implementation, this would actually execute the upgrade
-        // through a MsgMigrateContract or similar
-    } else {
-        proposal.status = ProposalStatus::Rejected;
+    // Once votes are in, cancelling could dodge a veto and its deposit burn
+    let has_votes = VOTES
+        .prefix(proposal_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    if has_votes {
+        return Err(ContractError::VotingStarted { proposal_id });
     }
     
+    proposal.status = ProposalStatus::Cancelled;
     PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
     
     Ok(Response::new()
-        .add_attribute("action", "execute_proposal")
-        .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("result", if passed { "passed" } else { "rejected" }))
+        .add_messages(settle_deposit(&proposal, true))
+        .add_attribute("action", "cancel_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
-pub fn query_proposal(deps: Deps, id: u64) -> StdResult<ProposalResponse> {
-    let proposal = PROPOSALS.load(deps.storage, id)?;
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
     
-    Ok(ProposalResponse {
+    Ok(ConfigResponse {
+        voting_period: config.voting_period,
+        quorum_percentage: config.quorum_percentage,
+        threshold_percentage: config.threshold_percentage,
+        veto_threshold_percentage: config.veto_threshold_percentage,
+        proposal_deposit: config.proposal_deposit,
+        timelock_period: config.timelock_period,
+        execution_period: config.execution_period,
+    })
+}
+
+pub fn query_validate_config(deps: Deps) -> StdResult<ValidateConfigResponse> {
+    Ok(validate_config(&CONFIG.load(deps.storage)?).into_response())
+}
+
+fn to_proposal_response(proposal: Proposal) -> ProposalResponse {
+    ProposalResponse {
         id: proposal.id,
         title: proposal.title,
         description: proposal.description,
+        proposer: proposal.proposer,
+        deposit: proposal.deposit,
         status: proposal.status,
-        contract_addr: proposal.contract_addr,
-        new_code_id: proposal.new_code_id,
+        action: proposal.action,
         yes_votes: proposal.yes_votes,
         no_votes: proposal.no_votes,
         abstain_votes: proposal.abstain_votes,
+        veto_votes: proposal.veto_votes,
         end_time: proposal.end_time,
-    })
+        executable_at: proposal.executable_at,
+        expires_at: proposal.expires_at,
+    }
+}
+
+pub fn query_proposal(deps: Deps, id: u64) -> StdResult<ProposalResponse> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+
+    Ok(to_proposal_response(proposal))
 }
 
 pub fn list_proposals(deps: Deps, start_after: Option<u64>, limit: Option<u32>) -> StdResult<ProposalListResponse> {
-    let limit = limit.unwrap_or(30) as usize;
-    let start = start_after.map(|s| s + 1); // exclusive
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
     
     let proposal_iter = PROPOSALS
         .range(deps.storage, start, None, Order::Ascending)
@@ -203,19 +633,50 @@ pub fn list_proposals(deps: Deps, start_after: Option<u64>, limit: Option<u32>)
     let mut proposals: Vec<ProposalResponse> = vec![];
     for item in proposal_iter {
         let (_, proposal) = item?;
-        proposals.push(ProposalResponse {
-            id: proposal.id,
-            title: proposal.title,
-            description: proposal.description,
-            status: proposal.status,
-            contract_addr: proposal.contract_addr,
-            new_code_id: proposal.new_code_id,
-            yes_votes: proposal.yes_votes,
-            no_votes: proposal.no_votes,
-            abstain_votes: proposal.abstain_votes,
-            end_time: proposal.end_time,
-        });
+        proposals.push(to_proposal_response(proposal));
     }
     
     Ok(ProposalListResponse { proposals })
 }
+
+pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<VoteResponse> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let vote = VOTES.load(deps.storage, (proposal_id, &voter))?;
+    
+    Ok(VoteResponse {
+        proposal_id: vote.proposal_id,
+        voter: vote.voter,
+        vote: vote.vote,
+        voting_power: vote.voting_power,
+    })
+}
+
+pub fn list_votes(
+    deps: Deps,
+    proposal_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<VoteListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    
+    let votes = VOTES
+        .prefix(proposal_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, vote) = item?;
+            Ok(VoteResponse {
+                proposal_id: vote.proposal_id,
+                voter: vote.voter,
+                vote: vote.vote,
+                voting_power: vote.voting_power,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    
+    Ok(VoteListResponse { votes })
+}
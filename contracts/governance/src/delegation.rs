@@ -0,0 +1,156 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128};
+use cw_storage_plus::Bound;
+use equilibria_smart_contracts::error::ContractError;
+
+use crate::state::{Proposal, DELEGATED_POWER, DELEGATIONS, DELEGATORS, OVERRIDDEN_POWER};
+use crate::{DelegationResponse, DelegatorInfo, DelegatorListResponse, VotingPowerResponse};
+
+// Pagination defaults for list queries
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+/// Voting power an address holds in its own right
+pub fn base_voting_power(_deps: Deps, _addr: &Addr) -> StdResult<Uint128> {
+    // In a real implementation, you would calculate voting power based on token balance
+    Ok(Uint128::from(1u128)) // Simple 1 vote per address
+}
+
+/// Power `voter` can cast on `proposal`: its own power plus whatever was delegated to it
+/// when the proposal started, minus delegators that have since voted for themselves
+pub fn voting_power(deps: Deps, proposal: &Proposal, voter: &Addr) -> StdResult<Uint128> {
+    let own_power = base_voting_power(deps, voter)?;
+    let delegated_power = DELEGATED_POWER
+        .may_load_at_height(deps.storage, voter, proposal.start_height)?
+        .unwrap_or_default();
+    let overridden_power = OVERRIDDEN_POWER
+        .may_load(deps.storage, (proposal.id, voter))?
+        .unwrap_or_default();
+
+    Ok(own_power + delegated_power.checked_sub(overridden_power)?)
+}
+
+/// Delegate `voter` had assigned its power to when `proposal` started, if any
+pub fn delegate_at_start(deps: Deps, proposal: &Proposal, voter: &Addr) -> StdResult<Option<Addr>> {
+    DELEGATIONS.may_load_at_height(deps.storage, voter, proposal.start_height)
+}
+
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to: String,
+) -> Result<Response, ContractError> {
+    let delegate = deps.api.addr_validate(&to)?;
+    if delegate == info.sender {
+        return Err(ContractError::SelfDelegation {});
+    }
+
+    let height = env.block.height;
+
+    // Moving a delegation takes the power back from the previous delegate first
+    if let Some(previous) = DELEGATIONS.may_load(deps.storage, &info.sender)? {
+        remove_delegated_power(deps.storage, height, &previous, &info.sender)?;
+    }
+
+    let power = base_voting_power(deps.as_ref(), &info.sender)?;
+    DELEGATIONS.save(deps.storage, &info.sender, &delegate, height)?;
+    DELEGATORS.save(deps.storage, (&delegate, &info.sender), &power)?;
+    DELEGATED_POWER.update(deps.storage, &delegate, height, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default() + power)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate)
+        .add_attribute("voting_power", power))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let delegate = DELEGATIONS.may_load(deps.storage, &info.sender)?.ok_or(ContractError::NoDelegation {})?;
+
+    let height = env.block.height;
+    remove_delegated_power(deps.storage, height, &delegate, &info.sender)?;
+    DELEGATIONS.remove(deps.storage, &info.sender, height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "undelegate")
+        .add_attribute("delegator", info.sender)
+        .add_attribute("delegate", delegate))
+}
+
+fn remove_delegated_power(
+    storage: &mut dyn cosmwasm_std::Storage,
+    height: u64,
+    delegate: &Addr,
+    delegator: &Addr,
+) -> StdResult<()> {
+    let power = DELEGATORS.may_load(storage, (delegate, delegator))?.unwrap_or_default();
+    DELEGATORS.remove(storage, (delegate, delegator));
+
+    let total = DELEGATED_POWER.may_load(storage, delegate)?.unwrap_or_default();
+    let remaining = total.checked_sub(power)?;
+    if remaining.is_zero() {
+        DELEGATED_POWER.remove(storage, delegate, height)
+    } else {
+        DELEGATED_POWER.save(storage, delegate, &remaining, height)
+    }
+}
+
+pub fn query_delegation(deps: Deps, delegator: String) -> StdResult<DelegationResponse> {
+    let delegator = deps.api.addr_validate(&delegator)?;
+    let delegate = DELEGATIONS.may_load(deps.storage, &delegator)?;
+
+    Ok(DelegationResponse { delegator, delegate })
+}
+
+pub fn list_delegators(
+    deps: Deps,
+    delegate: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DelegatorListResponse> {
+    let delegate = deps.api.addr_validate(&delegate)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let delegators = DELEGATORS
+        .prefix(&delegate)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (delegator, voting_power) = item?;
+            Ok(DelegatorInfo { delegator, voting_power })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(DelegatorListResponse { delegate, delegators })
+}
+
+pub fn query_voting_power(deps: Deps, address: String) -> StdResult<VotingPowerResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let own_power = base_voting_power(deps, &address)?;
+    let delegated_power = DELEGATED_POWER.may_load(deps.storage, &address)?.unwrap_or_default();
+    let delegated_away = DELEGATIONS.may_load(deps.storage, &address)?.is_some();
+
+    // Power handed to a delegate is not usable unless the holder votes directly
+    let total_power = if delegated_away {
+        delegated_power
+    } else {
+        own_power + delegated_power
+    };
+
+    Ok(VotingPowerResponse {
+        address,
+        own_power,
+        delegated_power,
+        total_power,
+    })
+}
@@ -0,0 +1,203 @@
+use cosmwasm_std::{Addr, Api, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage};
+use cw_storage_plus::Bound;
+use equilibria_smart_contracts::error::ContractError;
+
+use crate::parameters;
+use crate::state::{GuardianActionRecord, GUARDIAN_ACTIONS, GUARDIAN_ACTION_COUNT, GUARDIAN_COUNCIL};
+use crate::{
+    GuardianAction, GuardianActionListResponse, GuardianActionResponse, GuardianActionStatus, GuardianCouncil,
+    GuardianCouncilResponse, ParameterChange,
+};
+
+// Pagination defaults for list queries
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+/// Check that a council has distinct, valid members and an achievable threshold
+pub fn validate_council(api: &dyn Api, council: &GuardianCouncil) -> Result<(), ContractError> {
+    let mut members: Vec<&Addr> = Vec::with_capacity(council.members.len());
+    for member in &council.members {
+        api.addr_validate(member.as_str())?;
+        if members.contains(&member) {
+            return Err(ContractError::DuplicateGuardian { guardian: member.to_string() });
+        }
+        members.push(member);
+    }
+
+    if council.threshold == 0 || council.threshold > council.members.len() as u64 {
+        return Err(ContractError::InvalidConfig {
+            field: "threshold".to_string(),
+            reason: format!("must be between 1 and {}, got {}", council.members.len(), council.threshold),
+        });
+    }
+
+    Ok(())
+}
+
+/// Replace the council, or dissolve it when `council` is None.
+/// Pending actions keep their approvals, but only current members count towards the threshold.
+pub fn save_council(storage: &mut dyn Storage, council: Option<GuardianCouncil>) -> StdResult<()> {
+    match council {
+        Some(council) => GUARDIAN_COUNCIL.save(storage, &council),
+        None => {
+            GUARDIAN_COUNCIL.remove(storage);
+            Ok(())
+        }
+    }
+}
+
+/// The parameter change a guardian action applies.
+/// Each one can be undone by a governance proposal carrying the opposite change.
+fn to_parameter_change(action: GuardianAction) -> ParameterChange {
+    match action {
+        GuardianAction::PauseLiquidations { liquidation_engine } =>
+            ParameterChange::LiquidationsActive { liquidation_engine, is_active: false },
+        GuardianAction::FreezeOracle { eqa_oracle } =>
+            ParameterChange::OracleFrozen { eqa_oracle, frozen: true },
+        GuardianAction::DisableCollateral { collateral_manager, asset } =>
+            ParameterChange::CollateralEnabled { collateral_manager, asset, enabled: false },
+        GuardianAction::TightenBridgeRateLimit { collateral_manager, asset, destination_chain, max_outflow } =>
+            ParameterChange::BridgeRateLimitCap { collateral_manager, asset, destination_chain, max_outflow },
+    }
+}
+
+fn load_council_member(deps: Deps, sender: &Addr) -> Result<GuardianCouncil, ContractError> {
+    let council = GUARDIAN_COUNCIL.may_load(deps.storage)?.ok_or_else(|| ContractError::NotConfigured {
+        component: "guardian_council".to_string(),
+    })?;
+
+    if !council.members.contains(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(council)
+}
+
+pub fn execute_propose_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: GuardianAction,
+) -> Result<Response, ContractError> {
+    let council = load_council_member(deps.as_ref(), &info.sender)?;
+    parameters::validate(deps.api, &to_parameter_change(action.clone()))?;
+
+    let action_id = GUARDIAN_ACTION_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    GUARDIAN_ACTION_COUNT.save(deps.storage, &action_id)?;
+
+    // Proposing counts as the proposer's approval
+    let record = GuardianActionRecord {
+        id: action_id,
+        action,
+        proposer: info.sender.clone(),
+        approvals: vec![info.sender.clone()],
+        status: GuardianActionStatus::Pending,
+        created_at: env.block.time.seconds(),
+        executed_at: None,
+    };
+
+    let response = Response::new()
+        .add_attribute("action", "propose_guardian_action")
+        .add_attribute("guardian", info.sender)
+        .add_attribute("action_id", action_id.to_string());
+
+    execute_if_approved(deps, env, &council, record, response)
+}
+
+pub fn execute_approve_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action_id: u64,
+) -> Result<Response, ContractError> {
+    let council = load_council_member(deps.as_ref(), &info.sender)?;
+    let mut record = GUARDIAN_ACTIONS.load(deps.storage, action_id)?;
+
+    if record.status != GuardianActionStatus::Pending {
+        return Err(ContractError::GuardianActionExecuted { action_id });
+    }
+
+    if record.approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApproved { action_id, guardian: info.sender.to_string() });
+    }
+
+    record.approvals.push(info.sender.clone());
+
+    let response = Response::new()
+        .add_attribute("action", "approve_guardian_action")
+        .add_attribute("guardian", info.sender)
+        .add_attribute("action_id", action_id.to_string());
+
+    execute_if_approved(deps, env, &council, record, response)
+}
+
+// Save the action, dispatching it immediately once enough current members have approved
+fn execute_if_approved(
+    deps: DepsMut,
+    env: Env,
+    council: &GuardianCouncil,
+    mut record: GuardianActionRecord,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let approvals = record
+        .approvals
+        .iter()
+        .filter(|guardian| council.members.contains(guardian))
+        .count() as u64;
+
+    if approvals < council.threshold {
+        GUARDIAN_ACTIONS.save(deps.storage, record.id, &record)?;
+        return Ok(response.add_attribute("result", "pending"));
+    }
+
+    record.status = GuardianActionStatus::Executed;
+    record.executed_at = Some(env.block.time.seconds());
+    GUARDIAN_ACTIONS.save(deps.storage, record.id, &record)?;
+
+    // Governance must be the admin of the target contract for the action to succeed
+    let msg = parameters::build_msg(to_parameter_change(record.action))?;
+
+    Ok(response
+        .add_message(msg)
+        .add_attribute("result", "executed"))
+}
+
+pub fn query_council(deps: Deps) -> StdResult<GuardianCouncilResponse> {
+    Ok(GuardianCouncilResponse {
+        council: GUARDIAN_COUNCIL.may_load(deps.storage)?,
+    })
+}
+
+fn to_action_response(record: GuardianActionRecord) -> GuardianActionResponse {
+    GuardianActionResponse {
+        id: record.id,
+        action: record.action,
+        proposer: record.proposer,
+        approvals: record.approvals,
+        status: record.status,
+        created_at: record.created_at,
+        executed_at: record.executed_at,
+    }
+}
+
+pub fn query_action(deps: Deps, id: u64) -> StdResult<GuardianActionResponse> {
+    let record = GUARDIAN_ACTIONS.load(deps.storage, id)?;
+
+    Ok(to_action_response(record))
+}
+
+pub fn list_actions(deps: Deps, start_after: Option<u64>, limit: Option<u32>) -> StdResult<GuardianActionListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let actions = GUARDIAN_ACTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, record) = item?;
+            Ok(to_action_response(record))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GuardianActionListResponse { actions })
+}
@@ -1,19 +1,35 @@
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Addr, Uint128,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Addr, Uint128, Coin,
+    Decimal,
 };
+use equilibria_smart_contracts::config::{ChainIdMapping, ChainIdPattern, NetworkEnvironment};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::migration;
+use equilibria_smart_contracts::network::{self, NetworkManager};
+use equilibria_smart_contracts::ownership::{self, OwnershipAction};
+use equilibria_smart_contracts::roles::{self, Role};
 
 mod contract;
+mod delegation;
+mod guardian;
+mod parameters;
 mod state;
 
+// Stored at instantiation and checked on migration
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    contract::initialize(deps, info, msg.voting_period, msg.quorum_percentage)
+    migration::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    network::instantiate_chain_id_mappings(deps.storage, msg.chain_id_mappings.as_deref())?;
+    NetworkManager::verify_network(deps.as_ref(), &env, None)?;
+    contract::initialize(deps, info, msg)
 }
 
 #[entry_point]
@@ -24,23 +40,89 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::ProposeUpgrade { title, description, contract_addr, new_code_id } => 
-            contract::execute_propose(deps, env, info, title, description, contract_addr, new_code_id),
+        ExecuteMsg::ProposeUpgrade { title, description, contract_addr, new_code_id, migrate_msg } => 
+            contract::execute_propose_upgrade(deps, env, info, title, description, contract_addr, new_code_id, migrate_msg),
+        ExecuteMsg::ProposeParameterChange { title, description, change } => 
+            contract::execute_propose_parameter_change(deps, env, info, title, description, change),
+        ExecuteMsg::ProposeGuardianCouncil { title, description, council } => 
+            contract::execute_propose_guardian_council(deps, env, info, title, description, council),
+        ExecuteMsg::ProposeAcceptOwnership { title, description, contract_addr } => 
+            contract::execute_propose_accept_ownership(deps, env, info, title, description, contract_addr),
         ExecuteMsg::Vote { proposal_id, vote } => 
             contract::execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::TallyProposal { proposal_id } => 
+            contract::execute_tally(deps, env, proposal_id),
         ExecuteMsg::ExecuteProposal { proposal_id } => 
             contract::execute_proposal(deps, env, info, proposal_id),
+        ExecuteMsg::CancelProposal { proposal_id } => 
+            contract::execute_cancel(deps, env, info, proposal_id),
+        ExecuteMsg::Delegate { to } => 
+            delegation::execute_delegate(deps, env, info, to),
+        ExecuteMsg::Undelegate {} => 
+            delegation::execute_undelegate(deps, env, info),
+        ExecuteMsg::ProposeGuardianAction { action } => 
+            guardian::execute_propose_action(deps, env, info, action),
+        ExecuteMsg::ApproveGuardianAction { action_id } => 
+            guardian::execute_approve_action(deps, env, info, action_id),
+        ExecuteMsg::UpdateOwnership(action) => 
+            ownership::execute_update_ownership(deps, env, info, action),
+        ExecuteMsg::GrantRole { role, address } => 
+            roles::execute_grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => 
+            roles::execute_revoke_role(deps, info, role, address),
+        ExecuteMsg::SetChainIdMapping { pattern, environment } => 
+            network::execute_set_chain_id_mapping(deps, info, pattern, environment),
     }
 }
 
 #[entry_point]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::GetConfig {} => 
+            to_json_binary(&contract::query_config(deps)?),
+        QueryMsg::ValidateConfig {} => 
+            to_json_binary(&contract::query_validate_config(deps)?),
         QueryMsg::GetProposal { id } => 
-            to_binary(&contract::query_proposal(deps, id)?),
+            to_json_binary(&contract::query_proposal(deps, id)?),
         QueryMsg::ListProposals { start_after, limit } => 
-            to_binary(&contract::list_proposals(deps, start_after, limit)?),
+            to_json_binary(&contract::list_proposals(deps, start_after, limit)?),
+        QueryMsg::GetVote { proposal_id, voter } => 
+            to_json_binary(&contract::query_vote(deps, proposal_id, voter)?),
+        QueryMsg::ListVotes { proposal_id, start_after, limit } => 
+            to_json_binary(&contract::list_votes(deps, proposal_id, start_after, limit)?),
+        QueryMsg::GetDelegation { delegator } => 
+            to_json_binary(&delegation::query_delegation(deps, delegator)?),
+        QueryMsg::ListDelegators { delegate, start_after, limit } => 
+            to_json_binary(&delegation::list_delegators(deps, delegate, start_after, limit)?),
+        QueryMsg::GetVotingPower { address } => 
+            to_json_binary(&delegation::query_voting_power(deps, address)?),
+        QueryMsg::GetGuardianCouncil {} => 
+            to_json_binary(&guardian::query_council(deps)?),
+        QueryMsg::GetGuardianAction { id } => 
+            to_json_binary(&guardian::query_action(deps, id)?),
+        QueryMsg::ListGuardianActions { start_after, limit } => 
+            to_json_binary(&guardian::list_actions(deps, start_after, limit)?),
+        QueryMsg::GetOwnership {} => 
+            to_json_binary(&ownership::get_ownership(deps.storage)?),
+        QueryMsg::HasRole { role, address } => 
+            to_json_binary(&roles::query_has_role(deps, role, address)?),
+        QueryMsg::RoleMembers { role, start_after, limit } => 
+            to_json_binary(&roles::query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::GetChainIdMappings {} => 
+            to_json_binary(&network::query_chain_id_mappings(deps, &env)?),
+    }
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = migration::migrate_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    
+    // Contracts without a stored version predate the ownership module and proposal actions
+    if previous.is_none() {
+        contract::migrate_legacy_layout(deps, &env)?;
     }
+    
+    Ok(migration::migrate_response(previous, CONTRACT_VERSION))
 }
 
 use schemars::JsonSchema;
@@ -50,8 +132,18 @@ use serde::{Deserialize, Serialize};
 pub struct InstantiateMsg {
     pub voting_period: u64, // in seconds
     pub quorum_percentage: u64, // percentage (1-100)
+    pub threshold_percentage: u64, // share of non-abstain votes that must be yes (1-100)
+    pub veto_threshold_percentage: u64, // share of all votes that vetoes a proposal (1-100)
+    pub proposal_deposit: Option<Coin>, // deposit required to submit a proposal
+    pub timelock_period: u64, // delay between passing and execution, in seconds
+    pub execution_period: u64, // window after the timelock in which a proposal can be executed, in seconds
+    pub guardian_council: Option<GuardianCouncil>, // emergency council, changeable only through proposals
+    pub chain_id_mappings: Option<Vec<ChainIdMapping>>, // added to the default mappings before the network is verified
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -60,24 +152,160 @@ pub enum ExecuteMsg {
         description: String,
         contract_addr: Addr,
         new_code_id: u64,
+        migrate_msg: Option<Binary>, // defaults to an empty JSON object
+    },
+    ProposeParameterChange { 
+        title: String,
+        description: String,
+        change: ParameterChange,
+    },
+    ProposeGuardianCouncil { 
+        title: String,
+        description: String,
+        council: Option<GuardianCouncil>, // None dissolves the council
+    },
+    ProposeAcceptOwnership { 
+        title: String,
+        description: String,
+        contract_addr: Addr, // must already have proposed governance as its new owner
     },
     Vote { 
         proposal_id: u64,
         vote: VoteOption,
     },
+    TallyProposal { 
+        proposal_id: u64,
+    },
     ExecuteProposal { 
         proposal_id: u64,
     },
+    CancelProposal { // Only before the first vote is cast
+        proposal_id: u64,
+    },
+    Delegate { 
+        to: String,
+    },
+    Undelegate {},
+    ProposeGuardianAction { 
+        action: GuardianAction,
+    },
+    ApproveGuardianAction { 
+        action_id: u64,
+    },
+    UpdateOwnership(OwnershipAction),
+    GrantRole { role: Role, address: String },
+    RevokeRole { role: Role, address: String },
+    SetChainIdMapping { pattern: ChainIdPattern, environment: Option<NetworkEnvironment> }, // None removes the mapping
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+    GetConfig {},
+    ValidateConfig {}, // Every invalid config field, not just the first
     GetProposal { id: u64 },
     ListProposals { 
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    GetVote {
+        proposal_id: u64,
+        voter: String,
+    },
+    ListVotes {
+        proposal_id: u64,
+        start_after: Option<String>, // voter address
+        limit: Option<u32>,
+    },
+    GetDelegation { 
+        delegator: String,
+    },
+    ListDelegators { 
+        delegate: String,
+        start_after: Option<String>, // delegator address
+        limit: Option<u32>,
+    },
+    GetVotingPower { 
+        address: String,
+    },
+    GetGuardianCouncil {},
+    GetGuardianAction { id: u64 },
+    ListGuardianActions { 
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    GetOwnership {},
+    HasRole { role: Role, address: String }, // The owner holds every role
+    RoleMembers { role: Role, start_after: Option<String>, limit: Option<u32> },
+    GetChainIdMappings {}, // Also reports the network the current chain id maps to
+}
+
+/// What a proposal does once it is executed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalAction {
+    Upgrade {
+        contract_addr: Addr,
+        new_code_id: u64,
+        migrate_msg: Binary,
+    },
+    ParameterChange {
+        change: ParameterChange,
+    },
+    UpdateGuardianCouncil {
+        council: Option<GuardianCouncil>,
+    },
+    AcceptOwnership {
+        contract_addr: Addr,
+    },
+}
+
+/// Protocol parameters owned by governance, each paired with the contract that stores it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterChange {
+    LiquidationThresholdRatio { liquidation_engine: Addr, threshold_ratio: u64 },
+    LiquidationFee { liquidation_engine: Addr, liquidation_fee: u64 },
+    ArbitrageRewardPercentage { arbitrage_module: Addr, reward_percentage: Decimal },
+    OraclePriceTimeout { eqa_oracle: Addr, price_timeout: u64 },
+    RegistryContractAddress { registry: Addr, name: String, address: String },
+    LiquidationsActive { liquidation_engine: Addr, is_active: bool },
+    OracleFrozen { eqa_oracle: Addr, frozen: bool },
+    CollateralEnabled { collateral_manager: Addr, asset: String, enabled: bool },
+    BridgeRateLimit {
+        collateral_manager: Addr,
+        asset: String,
+        destination_chain: String,
+        max_outflow: Uint128,
+        window_seconds: u64,
+    },
+    // Lowers an outbound limit, keeping its window; rejected if it would raise it
+    BridgeRateLimitCap { collateral_manager: Addr, asset: String, destination_chain: String, max_outflow: Uint128 },
+}
+
+/// N-of-M guardians that can act without a vote in an emergency
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GuardianCouncil {
+    pub members: Vec<Addr>,
+    pub threshold: u64, // approvals needed to execute an action
+}
+
+/// Protective actions the guardian council may take. None of them move funds or upgrade code,
+/// and each is reverted by the matching `ParameterChange` through a normal proposal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardianAction {
+    PauseLiquidations { liquidation_engine: Addr },
+    FreezeOracle { eqa_oracle: Addr },
+    DisableCollateral { collateral_manager: Addr, asset: String },
+    TightenBridgeRateLimit { collateral_manager: Addr, asset: String, destination_chain: String, max_outflow: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GuardianActionStatus {
+    Pending,
+    Executed,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -86,6 +314,8 @@ pub enum VoteOption {
     Yes,
     No,
     Abstain,
+    #[serde(rename = "no_with_veto")]
+    NoWithVeto,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -94,7 +324,21 @@ pub enum ProposalStatus {
     Pending,
     Passed,
     Rejected,
+    Vetoed,
     Executed,
+    Expired,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub voting_period: u64,
+    pub quorum_percentage: u64,
+    pub threshold_percentage: u64,
+    pub veto_threshold_percentage: u64,
+    pub proposal_deposit: Option<Coin>,
+    pub timelock_period: u64,
+    pub execution_period: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -102,16 +346,80 @@ pub struct ProposalResponse {
     pub id: u64,
     pub title: String,
     pub description: String,
+    pub proposer: Addr,
+    pub deposit: Vec<Coin>,
     pub status: ProposalStatus,
-    pub contract_addr: Addr,
-    pub new_code_id: u64,
+    pub action: ProposalAction,
     pub yes_votes: Uint128,
     pub no_votes: Uint128,
     pub abstain_votes: Uint128,
+    pub veto_votes: Uint128,
     pub end_time: u64,
+    pub executable_at: Option<u64>,
+    pub expires_at: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ProposalListResponse {
     pub proposals: Vec<ProposalResponse>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteResponse {
+    pub proposal_id: u64,
+    pub voter: Addr,
+    pub vote: VoteOption,
+    pub voting_power: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteListResponse {
+    pub votes: Vec<VoteResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegationResponse {
+    pub delegator: Addr,
+    pub delegate: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegatorInfo {
+    pub delegator: Addr,
+    pub voting_power: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegatorListResponse {
+    pub delegate: Addr,
+    pub delegators: Vec<DelegatorInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VotingPowerResponse {
+    pub address: Addr,
+    pub own_power: Uint128,
+    pub delegated_power: Uint128,
+    pub total_power: Uint128, // power usable on a new proposal
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GuardianCouncilResponse {
+    pub council: Option<GuardianCouncil>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GuardianActionResponse {
+    pub id: u64,
+    pub action: GuardianAction,
+    pub proposer: Addr,
+    pub approvals: Vec<Addr>,
+    pub status: GuardianActionStatus,
+    pub created_at: u64,
+    pub executed_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GuardianActionListResponse {
+    pub actions: Vec<GuardianActionResponse>,
+}
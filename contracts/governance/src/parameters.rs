@@ -0,0 +1,218 @@
+use cosmwasm_std::{to_json_binary, Addr, Api, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
+use equilibria_smart_contracts::config::{MAX_PRICE_TIMEOUT, MIN_PRICE_TIMEOUT};
+use equilibria_smart_contracts::error::ContractError;
+
+use crate::ParameterChange;
+
+// Bounds governance enforces on each parameter it owns
+pub const MIN_THRESHOLD_RATIO: u64 = 100; // percentage
+pub const MAX_THRESHOLD_RATIO: u64 = 300; // percentage
+pub const MAX_LIQUIDATION_FEE: u64 = 20; // percentage
+pub const MAX_REWARD_PERCENTAGE: Decimal = Decimal::percent(50);
+
+// Update messages of the contracts governance owns parameters on
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LiquidationEngineMsg {
+    UpdateConfig {
+        oracle_address: Option<Addr>,
+        threshold_ratio: Option<u64>,
+        liquidation_fee: Option<u64>,
+        is_active: Option<bool>,
+    },
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ArbitrageModuleMsg {
+    UpdateRewardPercentage { reward_percentage: Decimal },
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum OracleMsg {
+    UpdatePriceTimeout { new_timeout: u64 },
+    SetFrozen { frozen: bool },
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CollateralManagerMsg {
+    SetCollateralEnabled { asset: String, enabled: bool },
+    SetRateLimit { asset: String, destination_chain: String, limit: Option<RateLimit> },
+    TightenRateLimit { asset: String, destination_chain: String, max_outflow: Uint128 },
+}
+
+#[derive(serde::Serialize)]
+struct RateLimit {
+    max_outflow: Uint128,
+    window_seconds: u64,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RegistryMsg {
+    SetContractAddress { name: String, address: String },
+}
+
+fn out_of_bounds(parameter: &str, value: impl ToString, min: impl ToString, max: impl ToString) -> ContractError {
+    ContractError::InvalidConfig {
+        field: parameter.to_string(),
+        reason: format!("must be between {} and {}, got {}", min.to_string(), max.to_string(), value.to_string()),
+    }
+}
+
+fn empty(field: &str) -> ContractError {
+    ContractError::InvalidConfig { field: field.to_string(), reason: "cannot be empty".to_string() }
+}
+
+fn validate_rate_limit_route(
+    api: &dyn Api,
+    collateral_manager: &Addr,
+    asset: &str,
+    destination_chain: &str,
+) -> Result<(), ContractError> {
+    api.addr_validate(collateral_manager.as_str())?;
+    if asset.is_empty() {
+        return Err(empty("asset"));
+    }
+    if destination_chain.is_empty() {
+        return Err(empty("destination_chain"));
+    }
+
+    Ok(())
+}
+
+/// Check a proposed parameter value against its bounds
+pub fn validate(api: &dyn Api, change: &ParameterChange) -> Result<(), ContractError> {
+    match change {
+        ParameterChange::LiquidationThresholdRatio { liquidation_engine, threshold_ratio } => {
+            api.addr_validate(liquidation_engine.as_str())?;
+            if !(MIN_THRESHOLD_RATIO..=MAX_THRESHOLD_RATIO).contains(threshold_ratio) {
+                return Err(out_of_bounds("threshold_ratio", threshold_ratio, MIN_THRESHOLD_RATIO, MAX_THRESHOLD_RATIO));
+            }
+        }
+        ParameterChange::LiquidationFee { liquidation_engine, liquidation_fee } => {
+            api.addr_validate(liquidation_engine.as_str())?;
+            if *liquidation_fee > MAX_LIQUIDATION_FEE {
+                return Err(out_of_bounds("liquidation_fee", liquidation_fee, 0, MAX_LIQUIDATION_FEE));
+            }
+        }
+        ParameterChange::ArbitrageRewardPercentage { arbitrage_module, reward_percentage } => {
+            api.addr_validate(arbitrage_module.as_str())?;
+            if *reward_percentage > MAX_REWARD_PERCENTAGE {
+                return Err(out_of_bounds("reward_percentage", reward_percentage, 0, MAX_REWARD_PERCENTAGE));
+            }
+        }
+        ParameterChange::OraclePriceTimeout { eqa_oracle, price_timeout } => {
+            api.addr_validate(eqa_oracle.as_str())?;
+            if !(MIN_PRICE_TIMEOUT..=MAX_PRICE_TIMEOUT).contains(price_timeout) {
+                return Err(out_of_bounds("price_timeout", price_timeout, MIN_PRICE_TIMEOUT, MAX_PRICE_TIMEOUT));
+            }
+        }
+        ParameterChange::RegistryContractAddress { registry, name, address } => {
+            api.addr_validate(registry.as_str())?;
+            api.addr_validate(address)?;
+            if name.is_empty() {
+                return Err(empty("name"));
+            }
+        }
+        ParameterChange::LiquidationsActive { liquidation_engine, .. } => {
+            api.addr_validate(liquidation_engine.as_str())?;
+        }
+        ParameterChange::OracleFrozen { eqa_oracle, .. } => {
+            api.addr_validate(eqa_oracle.as_str())?;
+        }
+        ParameterChange::CollateralEnabled { collateral_manager, asset, .. } => {
+            api.addr_validate(collateral_manager.as_str())?;
+            if asset.is_empty() {
+                return Err(empty("asset"));
+            }
+        }
+        ParameterChange::BridgeRateLimit { collateral_manager, asset, destination_chain, window_seconds, .. } => {
+            validate_rate_limit_route(api, collateral_manager, asset, destination_chain)?;
+            if *window_seconds == 0 {
+                return Err(ContractError::InvalidConfig {
+                    field: "window_seconds".to_string(),
+                    reason: "must be at least one second".to_string(),
+                });
+            }
+        }
+        ParameterChange::BridgeRateLimitCap { collateral_manager, asset, destination_chain, .. } => {
+            validate_rate_limit_route(api, collateral_manager, asset, destination_chain)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Build the update message that applies a parameter change on its owning contract
+pub fn build_msg(change: ParameterChange) -> StdResult<CosmosMsg> {
+    let (contract_addr, msg) = match change {
+        ParameterChange::LiquidationThresholdRatio { liquidation_engine, threshold_ratio } => (
+            liquidation_engine,
+            to_json_binary(&LiquidationEngineMsg::UpdateConfig {
+                oracle_address: None,
+                threshold_ratio: Some(threshold_ratio),
+                liquidation_fee: None,
+                is_active: None,
+            })?,
+        ),
+        ParameterChange::LiquidationFee { liquidation_engine, liquidation_fee } => (
+            liquidation_engine,
+            to_json_binary(&LiquidationEngineMsg::UpdateConfig {
+                oracle_address: None,
+                threshold_ratio: None,
+                liquidation_fee: Some(liquidation_fee),
+                is_active: None,
+            })?,
+        ),
+        ParameterChange::ArbitrageRewardPercentage { arbitrage_module, reward_percentage } => (
+            arbitrage_module,
+            to_json_binary(&ArbitrageModuleMsg::UpdateRewardPercentage { reward_percentage })?,
+        ),
+        ParameterChange::OraclePriceTimeout { eqa_oracle, price_timeout } => (
+            eqa_oracle,
+            to_json_binary(&OracleMsg::UpdatePriceTimeout { new_timeout: price_timeout })?,
+        ),
+        ParameterChange::RegistryContractAddress { registry, name, address } => (
+            registry,
+            to_json_binary(&RegistryMsg::SetContractAddress { name, address })?,
+        ),
+        ParameterChange::LiquidationsActive { liquidation_engine, is_active } => (
+            liquidation_engine,
+            to_json_binary(&LiquidationEngineMsg::UpdateConfig {
+                oracle_address: None,
+                threshold_ratio: None,
+                liquidation_fee: None,
+                is_active: Some(is_active),
+            })?,
+        ),
+        ParameterChange::OracleFrozen { eqa_oracle, frozen } => (
+            eqa_oracle,
+            to_json_binary(&OracleMsg::SetFrozen { frozen })?,
+        ),
+        ParameterChange::CollateralEnabled { collateral_manager, asset, enabled } => (
+            collateral_manager,
+            to_json_binary(&CollateralManagerMsg::SetCollateralEnabled { asset, enabled })?,
+        ),
+        ParameterChange::BridgeRateLimit { collateral_manager, asset, destination_chain, max_outflow, window_seconds } => (
+            collateral_manager,
+            to_json_binary(&CollateralManagerMsg::SetRateLimit {
+                asset,
+                destination_chain,
+                limit: Some(RateLimit { max_outflow, window_seconds }),
+            })?,
+        ),
+        ParameterChange::BridgeRateLimitCap { collateral_manager, asset, destination_chain, max_outflow } => (
+            collateral_manager,
+            to_json_binary(&CollateralManagerMsg::TightenRateLimit { asset, destination_chain, max_outflow })?,
+        ),
+    };
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg,
+        funds: vec![],
+    }))
+}
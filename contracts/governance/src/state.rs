@@ -1,15 +1,19 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cw_storage_plus::{Item, Map};
-use crate::{ProposalStatus, VoteOption};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
+use crate::{GuardianAction, GuardianActionStatus, GuardianCouncil, ProposalAction, ProposalStatus, VoteOption};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
-    pub admin: Addr,
     pub voting_period: u64, // in seconds
     pub quorum_percentage: u64, // percentage (1-100)
+    pub threshold_percentage: u64, // percentage (1-100)
+    pub veto_threshold_percentage: u64, // percentage (1-100)
+    pub proposal_deposit: Option<Coin>,
+    pub timelock_period: u64, // in seconds
+    pub execution_period: u64, // in seconds
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -17,13 +21,18 @@ pub struct Proposal {
     pub id: u64,
     pub title: String,
     pub description: String,
+    pub proposer: Addr,
+    pub deposit: Vec<Coin>, // refunded or burned once the proposal is settled
     pub status: ProposalStatus,
-    pub contract_addr: Addr,
-    pub new_code_id: u64,
+    pub action: ProposalAction,
     pub yes_votes: Uint128,
     pub no_votes: Uint128,
     pub abstain_votes: Uint128,
+    pub veto_votes: Uint128,
+    pub start_height: u64, // delegations are read as of this height
     pub end_time: u64,
+    pub executable_at: Option<u64>, // set once the proposal passes
+    pub expires_at: Option<u64>,    // set once the proposal passes
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -34,7 +43,39 @@ pub struct Vote {
     pub voting_power: Uint128,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GuardianActionRecord {
+    pub id: u64,
+    pub action: GuardianAction,
+    pub proposer: Addr,
+    pub approvals: Vec<Addr>,
+    pub status: GuardianActionStatus,
+    pub created_at: u64,
+    pub executed_at: Option<u64>,
+}
+
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
 pub const VOTES: Map<(u64, &Addr), Vote> = Map::new("votes"); // (proposal_id, voter) -> Vote
+
+// Delegation state is snapshotted so proposals can read it as of their start height
+pub const DELEGATIONS: SnapshotMap<&Addr, Addr> = SnapshotMap::new(
+    "delegations",
+    "delegations__checkpoints",
+    "delegations__changelog",
+    Strategy::EveryBlock,
+); // delegator -> delegate
+pub const DELEGATED_POWER: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "delegated_power",
+    "delegated_power__checkpoints",
+    "delegated_power__changelog",
+    Strategy::EveryBlock,
+); // delegate -> total power delegated to it
+pub const DELEGATORS: Map<(&Addr, &Addr), Uint128> = Map::new("delegators"); // (delegate, delegator) -> delegated power
+pub const OVERRIDDEN_POWER: Map<(u64, &Addr), Uint128> = Map::new("overridden_power"); // (proposal_id, delegate) -> power withdrawn by delegators voting directly
+
+// Guardian actions are kept after execution as an audit log
+pub const GUARDIAN_COUNCIL: Item<GuardianCouncil> = Item::new("guardian_council");
+pub const GUARDIAN_ACTION_COUNT: Item<u64> = Item::new("guardian_action_count");
+pub const GUARDIAN_ACTIONS: Map<u64, GuardianActionRecord> = Map::new("guardian_actions");
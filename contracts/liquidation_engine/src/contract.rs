@@ -1,70 +1,42 @@
 use cosmwasm_std::{
     DepsMut, Deps, Env, MessageInfo, Response, Uint128, StdResult,
-    Addr, WasmQuery, QueryRequest, to_json_binary as to_binary, Decimal
+    Addr, Api
 };
+use equilibria_smart_contracts::config::{ConfigValidator, ValidateConfigResponse};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::roles::{self, Role};
 use equilibria_smart_contracts::state::COLLATERAL;
-use cw_storage_plus::Item;
 
 use crate::state::{CONFIG, Config};
 use crate::{LiquidationStatusResponse, ConfigResponse};
 
-// Oracle query types
-#[derive(serde::Serialize)]
-#[serde(rename_all = "snake_case")]
-struct OracleQuery {
-    get_price: GetPrice,
-}
-
-#[derive(serde::Serialize)]
-struct GetPrice {
-    denom: String,
-}
-
-#[derive(serde::Deserialize)]
-struct PriceResponse {
-    denom: String,
-    price: Decimal,
-    last_updated: u64,
-}
-
-const MIN_THRESHOLD: Item<u64> = Item::new("min_threshold");
-
-pub fn initialize(
-    deps: DepsMut,
-    info: MessageInfo,
-    threshold_ratio: u64,
-) -> Result<Response, ContractError> {
-    if threshold_ratio < 100 {
-        return Err(ContractError::CustomError { 
-            msg: "Threshold ratio must be at least 100%".to_string() 
-        });
-    }
-    
-    MIN_THRESHOLD.save(deps.storage, &threshold_ratio)?;
-    
-    Ok(Response::new()
-        .add_attribute("action", "initialize")
-        .add_attribute("admin", info.sender)
-        .add_attribute("threshold_ratio", threshold_ratio.to_string()))
-}
-
 pub fn execute_check_liquidation(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     eqa_supply: Uint128,
-    price: Uint128,
+    _price: Uint128,
 ) -> Result<Response, ContractError> {
+    // Only whitelisted liquidators may trigger a liquidation check
+    roles::assert_role(deps.storage, Role::LiquidatorWhitelist, &info.sender)?;
+    
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_active {
+        return Err(ContractError::Paused { operation: "liquidation".to_string() });
+    }
+    
     let collateral = COLLATERAL.load(deps.storage)?;
-    let threshold = MIN_THRESHOLD.load(deps.storage)?;
+    let threshold = config.threshold_ratio;
     
     let total_collateral_value = collateral.total_locked;
     let required_collateral = eqa_supply.checked_mul(Uint128::from(threshold))?.checked_div(Uint128::from(100u64))?;
     
     if total_collateral_value < required_collateral {
         // In a real implementation, this would trigger actual liquidation logic
-        return Err(ContractError::InsufficientCollateral {});
+        return Err(ContractError::InsufficientCollateral { 
+            required: required_collateral, 
+            available: total_collateral_value, 
+        });
     }
     
     Ok(Response::new()
@@ -80,7 +52,7 @@ pub fn query_liquidation_status(
     price: Uint128,
 ) -> StdResult<LiquidationStatusResponse> {
     let collateral = COLLATERAL.load(deps.storage)?;
-    let threshold = MIN_THRESHOLD.load(deps.storage)?;
+    let threshold = CONFIG.load(deps.storage)?.threshold_ratio;
     
     let total_collateral_value = collateral.total_locked;
     let backed_value = eqa_supply * price / Uint128::from(1_000_000u64); // assuming 6 decimal places
@@ -110,33 +82,25 @@ pub fn execute_update_config(
     liquidation_fee: Option<u64>,
     is_active: Option<bool>,
 ) -> Result<Response, ContractError> {
+    // Guardians may pause liquidations; resuming them and every other change is up to the admin
+    let pause_only = is_active == Some(false)
+        && oracle_address.is_none()
+        && threshold_ratio.is_none()
+        && liquidation_fee.is_none();
+    let role = if pause_only { Role::Guardian } else { Role::Admin };
+    roles::assert_role(deps.storage, role, &info.sender)?;
     let mut config = CONFIG.load(deps.storage)?;
     
-    // Check if the sender is the admin
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
-    }
-    
     // Update config values if provided
     if let Some(oracle) = oracle_address {
         config.oracle_address = Some(oracle);
     }
     
     if let Some(ratio) = threshold_ratio {
-        if ratio < 100 {
-            return Err(ContractError::CustomError { 
-                msg: "Threshold ratio must be at least 100%".to_string() 
-            });
-        }
         config.threshold_ratio = ratio;
     }
     
     if let Some(fee) = liquidation_fee {
-        if fee > 20 {
-            return Err(ContractError::CustomError { 
-                msg: "Liquidation fee cannot exceed 20%".to_string() 
-            });
-        }
         config.liquidation_fee = fee;
     }
     
@@ -144,6 +108,7 @@ pub fn execute_update_config(
         config.is_active = active;
     }
     
+    validate_config(deps.api, &config).into_result()?;
     CONFIG.save(deps.storage, &config)?;
     
     Ok(Response::new()
@@ -151,12 +116,29 @@ pub fn execute_update_config(
         .add_attribute("admin", info.sender))
 }
 
+const MIN_THRESHOLD_RATIO: u64 = 100; // percentage
+const MAX_LIQUIDATION_FEE: u64 = 20; // percentage
+
+pub fn validate_config(api: &dyn Api, config: &Config) -> ConfigValidator {
+    let mut validator = ConfigValidator::new();
+    if let Some(oracle) = &config.oracle_address {
+        validator.address(api, "oracle_address", oracle.as_str());
+    }
+    validator
+        .range("threshold_ratio", config.threshold_ratio, MIN_THRESHOLD_RATIO, u64::MAX)
+        .range("liquidation_fee", config.liquidation_fee, 0, MAX_LIQUIDATION_FEE);
+    validator
+}
+
+pub fn query_validate_config(deps: Deps) -> StdResult<ValidateConfigResponse> {
+    Ok(validate_config(deps.api, &CONFIG.load(deps.storage)?).into_response())
+}
+
 // Fix QueryConfig function which was missing
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
     
     Ok(ConfigResponse {
-        admin: config.admin,
         oracle_address: config.oracle_address,
         threshold_ratio: config.threshold_ratio,
         liquidation_fee: config.liquidation_fee,
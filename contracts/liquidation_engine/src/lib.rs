@@ -1,8 +1,13 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, 
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, 
     Uint128, Addr, QueryRequest, WasmQuery
 };
+use equilibria_smart_contracts::config::{ChainIdMapping, ChainIdPattern, NetworkEnvironment};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::migration;
+use equilibria_smart_contracts::network::{self, NetworkManager};
+use equilibria_smart_contracts::ownership::{self, OwnershipAction};
+use equilibria_smart_contracts::roles::{self, Role};
 use equilibria_smart_contracts::oracle::{OracleQueryMsg, PriceResponse};
 
 mod contract;
@@ -10,20 +15,28 @@ mod state;
 
 use crate::state::{Config, CONFIG};
 
+// Stored at instantiation and checked on migration
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    migration::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    network::instantiate_chain_id_mappings(deps.storage, msg.chain_id_mappings.as_deref())?;
+    NetworkManager::verify_network(deps.as_ref(), &env, None)?;
+    ownership::initialize_owner(deps.storage, deps.api, info.sender.as_str())?;
     let config = Config {
-        admin: info.sender.clone(),
         oracle_address: msg.oracle_address,
         threshold_ratio: msg.threshold_ratio,
         liquidation_fee: msg.liquidation_fee.unwrap_or(5), // Default 5%
         is_active: true,
     };
+    contract::validate_config(deps.api, &config).into_result()?;
     
     CONFIG.save(deps.storage, &config)?;
     
@@ -53,6 +66,14 @@ pub fn execute(
         },
         ExecuteMsg::UpdateConfig { oracle_address, threshold_ratio, liquidation_fee, is_active } => 
             contract::execute_update_config(deps, env, info, oracle_address, threshold_ratio, liquidation_fee, is_active),
+        ExecuteMsg::UpdateOwnership(action) => 
+            ownership::execute_update_ownership(deps, env, info, action),
+        ExecuteMsg::GrantRole { role, address } => 
+            roles::execute_grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => 
+            roles::execute_revoke_role(deps, info, role, address),
+        ExecuteMsg::SetChainIdMapping { pattern, environment } => 
+            network::execute_set_chain_id_mapping(deps, info, pattern, environment),
     }
 }
 
@@ -67,10 +88,8 @@ fn query_oracle_price(deps: Deps, oracle_addr: Addr) -> Result<Uint128, Contract
         msg: to_json_binary(&query_msg)?,
     });
     
-    let price_response: PriceResponse = deps.querier.query(&query)
-        .map_err(|e| ContractError::OracleError { 
-            msg: format!("Failed to query oracle: {}", e) 
-        })?;
+    // wasmd redacts why a sub-query failed, so an oracle error arrives as a plain querier error
+    let price_response: PriceResponse = deps.querier.query(&query)?;
     
     // Convert Decimal to Uint128 (assuming 6 decimal places)
     let price_u128 = (price_response.price.atomics().u128() * 1_000_000u128) 
@@ -80,13 +99,13 @@ fn query_oracle_price(deps: Deps, oracle_addr: Addr) -> Result<Uint128, Contract
 }
 
 #[entry_point]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let res = match msg {
         QueryMsg::GetLiquidationStatus { eqa_supply } => {
-            // Get price from oracle if configured
+            // Get price from oracle if configured; an unreachable oracle fails the query
             let config = CONFIG.load(deps.storage)?;
             let price = match config.oracle_address {
-                Some(oracle_addr) => query_oracle_price(deps, oracle_addr).unwrap_or(Uint128::new(1_000_000)),
+                Some(oracle_addr) => query_oracle_price(deps, oracle_addr)?,
                 None => Uint128::new(1_000_000), // Default price (1.0 with 6 decimals)
             };
             
@@ -94,7 +113,32 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         },
         QueryMsg::GetConfig {} => 
             to_json_binary(&contract::query_config(deps)?),
+        QueryMsg::ValidateConfig {} => 
+            to_json_binary(&contract::query_validate_config(deps)?),
+        QueryMsg::GetOwnership {} => 
+            to_json_binary(&ownership::get_ownership(deps.storage)?),
+        QueryMsg::HasRole { role, address } => 
+            to_json_binary(&roles::query_has_role(deps, role, address)?),
+        QueryMsg::RoleMembers { role, start_after, limit } => 
+            to_json_binary(&roles::query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::GetChainIdMappings {} => 
+            to_json_binary(&network::query_chain_id_mappings(deps, &env)?),
+    };
+    Ok(res?)
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = migration::migrate_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    
+    // Contracts without a stored version predate the ownership module
+    if previous.is_none() && migration::migrate_legacy_config_admin(deps.storage, "config")? {
+        // Re-save the config without its inline admin
+        let config = CONFIG.load(deps.storage)?;
+        CONFIG.save(deps.storage, &config)?;
     }
+    
+    Ok(migration::migrate_response(previous, CONTRACT_VERSION))
 }
 
 use schemars::JsonSchema;
@@ -106,8 +150,12 @@ pub struct InstantiateMsg {
     pub threshold_ratio: u64,   // Minimum collateralization ratio (e.g. 110%)
     pub liquidation_fee: Option<u64>,  // Fee charged during liquidation (e.g. 5%)
     pub oracle_address: Option<Addr>, // Optional oracle address
+    pub chain_id_mappings: Option<Vec<ChainIdMapping>>, // added to the default mappings before the network is verified
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -116,8 +164,12 @@ pub enum ExecuteMsg {
         oracle_address: Option<Addr>, 
         threshold_ratio: Option<u64>,
         liquidation_fee: Option<u64>,
-        is_active: Option<bool>,
+        is_active: Option<bool>, // guardians may set false on its own to pause
     },
+    UpdateOwnership(OwnershipAction),
+    GrantRole { role: Role, address: String },
+    RevokeRole { role: Role, address: String },
+    SetChainIdMapping { pattern: ChainIdPattern, environment: Option<NetworkEnvironment> }, // None removes the mapping
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -125,6 +177,11 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     GetLiquidationStatus { eqa_supply: Uint128 },
     GetConfig {},
+    ValidateConfig {}, // Every invalid config field, not just the first
+    GetOwnership {},
+    HasRole { role: Role, address: String }, // The owner holds every role
+    RoleMembers { role: Role, start_after: Option<String>, limit: Option<u32> },
+    GetChainIdMappings {}, // Also reports the network the current chain id maps to
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -134,12 +191,10 @@ pub struct LiquidationStatusResponse {
     pub required_ratio: u64,
     pub collateral_value: Uint128,
     pub backed_value: Uint128,
-    pub price: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
-    pub admin: Addr,
     pub oracle_address: Option<Addr>,
     pub threshold_ratio: u64,
     pub liquidation_fee: u64,
@@ -1,93 +1,349 @@
-use cosmwasm_std::{Deps, DepsMut, MessageInfo, Response, Addr, Order, StdResult};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Addr, Api, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Map};
+use equilibria_smart_contracts::config::{ConfigValidator, ValidateConfigResponse};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::migration;
+use equilibria_smart_contracts::network::NetworkManager;
+use equilibria_smart_contracts::ownership;
+use equilibria_smart_contracts::roles::{self, Role};
 
-use crate::{ContractAddressResponse, AllContractsResponse};
-use crate::state::{ADMIN, CONTRACTS};
+use crate::{
+    ContractAddressResponse, AllContractsResponse, ContractAtHeightResponse, ContractChange, ContractEntry,
+    ContractHistoryResponse, ContractInfoResponse, DefaultNetworkResponse,
+};
+use crate::state::{CONTRACTS, CONTRACT_HISTORY, DEFAULT_NETWORK, HISTORY_LENGTH};
+
+// Pagination for contract listings
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+// Layout from before network namespaces: name => address
+const LEGACY_CONTRACTS: Map<&str, String> = Map::new("contracts");
 
 pub fn initialize(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     admin: Option<Addr>,
 ) -> Result<Response, ContractError> {
+    // Un-namespaced calls resolve against the network this registry is deployed on
+    let network = NetworkManager::verify_network(deps.as_ref(), &env, None)?.to_string();
+    DEFAULT_NETWORK.save(deps.storage, &network)?;
+    
     let admin_addr = admin.unwrap_or_else(|| info.sender.clone());
-    ADMIN.save(deps.storage, &admin_addr)?;
+    ownership::initialize_owner(deps.storage, deps.api, admin_addr.as_str())?;
     
     Ok(Response::new()
         .add_attribute("action", "initialize")
         .add_attribute("admin", admin_addr.to_string()))
 }
 
+/// Upgrade a registry that predates ownership and network namespaces.
+/// Its entries move into the network it is deployed on, with the migration as their first history record.
+pub fn migrate_legacy_layout(deps: DepsMut, env: &Env) -> Result<(), ContractError> {
+    migration::migrate_legacy_admin(deps.storage)?;
+    
+    // Registries that already record a default network are on the namespaced layout
+    if DEFAULT_NETWORK.may_load(deps.storage)?.is_some() {
+        return Ok(());
+    }
+    let network = NetworkManager::verify_network(deps.as_ref(), env, None)?.to_string();
+    DEFAULT_NETWORK.save(deps.storage, &network)?;
+    
+    let legacy = LEGACY_CONTRACTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (name, address) in legacy {
+        LEGACY_CONTRACTS.remove(deps.storage, &name);
+        let entry = ContractEntry {
+            address,
+            code_id: None,
+            version: None,
+            description: None,
+            deploy_height: env.block.height,
+        };
+        record_change(deps.storage, env, &network, &name, Some(entry))?;
+    }
+    
+    Ok(())
+}
+
+fn resolve_network(storage: &dyn Storage, network: Option<String>) -> StdResult<String> {
+    match network {
+        Some(network) => Ok(network),
+        None => DEFAULT_NETWORK.load(storage),
+    }
+}
+
+fn resolve_write_network(storage: &dyn Storage, network: Option<String>) -> Result<String, ContractError> {
+    let network = resolve_network(storage, network)?;
+    if network.is_empty() {
+        return Err(ContractError::InvalidConfig {
+            field: "network".to_string(),
+            reason: "cannot be empty".to_string(),
+        });
+    }
+    
+    Ok(network)
+}
+
+// Apply a change to a name and append it to the name's history; `None` removes the entry.
+// Returns the entry it replaced.
+fn record_change(
+    storage: &mut dyn Storage,
+    env: &Env,
+    network: &str,
+    name: &str,
+    entry: Option<ContractEntry>,
+) -> StdResult<Option<ContractEntry>> {
+    let previous = CONTRACTS.may_load(storage, (network, name))?;
+    match &entry {
+        Some(entry) => CONTRACTS.save(storage, (network, name), entry)?,
+        None => CONTRACTS.remove(storage, (network, name)),
+    }
+    
+    let sequence = HISTORY_LENGTH.may_load(storage, (network, name))?.unwrap_or_default();
+    let change = ContractChange { sequence, height: env.block.height, entry };
+    CONTRACT_HISTORY.save(storage, (network, name, sequence), &change)?;
+    HISTORY_LENGTH.save(storage, (network, name), &(sequence + 1))?;
+    
+    Ok(previous)
+}
+
+fn validate_entry(api: &dyn Api, name: &str, address: &str) -> ConfigValidator {
+    let mut validator = ConfigValidator::new();
+    validator.not_empty("name", name).address(api, "address", address);
+    validator
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn execute_set_contract_address(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
+    network: Option<String>,
     name: String,
     address: String,
+    code_id: Option<u64>,
+    version: Option<String>,
+    description: Option<String>,
 ) -> Result<Response, ContractError> {
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(ContractError::Unauthorized {});
-    }
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+    
+    validate_entry(deps.api, &name, &address).into_result()?;
     
-    // Validate address
-    deps.api.addr_validate(&address)?;
+    let network = resolve_write_network(deps.storage, network)?;
     
-    // Store contract address
-    CONTRACTS.save(deps.storage, &name, &address)?;
+    let entry = ContractEntry {
+        address: address.clone(),
+        code_id,
+        version,
+        description,
+        deploy_height: env.block.height,
+    };
+    let previous = record_change(deps.storage, &env, &network, &name, Some(entry))?;
     
     Ok(Response::new()
         .add_attribute("action", "set_contract_address")
+        .add_attribute("network", network)
         .add_attribute("name", name)
-        .add_attribute("address", address))
+        .add_attribute("address", address)
+        .add_attribute("previous_address", previous.map(|entry| entry.address).unwrap_or_default()))
 }
 
-pub fn execute_update_config(
+pub fn execute_set_contract_addresses(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    new_admin: Option<Addr>,
+    network: Option<String>,
+    entries: Vec<(String, String)>,
 ) -> Result<Response, ContractError> {
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(ContractError::Unauthorized {});
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+    
+    let network = resolve_write_network(deps.storage, network)?;
+    if entries.is_empty() {
+        return Err(ContractError::InvalidConfig {
+            field: "entries".to_string(),
+            reason: "cannot be empty".to_string(),
+        });
+    }
+    
+    // Validate every entry before writing any of them
+    for (name, address) in &entries {
+        validate_entry(deps.api, name, address).into_result()?;
     }
     
-    if let Some(admin) = new_admin {
-        ADMIN.save(deps.storage, &admin)?;
-        
-        Ok(Response::new()
-            .add_attribute("action", "update_config")
-            .add_attribute("new_admin", admin.to_string()))
-    } else {
-        Ok(Response::new()
-            .add_attribute("action", "update_config")
-            .add_attribute("admin", "unchanged"))
+    for (name, address) in &entries {
+        let entry = ContractEntry {
+            address: address.clone(),
+            code_id: None,
+            version: None,
+            description: None,
+            deploy_height: env.block.height,
+        };
+        record_change(deps.storage, &env, &network, name, Some(entry))?;
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", "set_contract_addresses")
+        .add_attribute("network", network)
+        .add_attribute("count", entries.len().to_string()))
+}
+
+pub fn execute_remove_contract_address(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    network: Option<String>,
+    name: String,
+) -> Result<Response, ContractError> {
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+    
+    let network = resolve_write_network(deps.storage, network)?;
+    if !CONTRACTS.has(deps.storage, (&network, &name)) {
+        return Err(ContractError::ContractNotFound { name, network });
     }
+    
+    // The removal is kept in the history so past lookups still resolve
+    let previous = record_change(deps.storage, &env, &network, &name, None)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "remove_contract_address")
+        .add_attribute("network", network)
+        .add_attribute("name", name)
+        .add_attribute("previous_address", previous.map(|entry| entry.address).unwrap_or_default()))
 }
 
 pub fn query_contract_address(
     deps: Deps,
+    network: Option<String>,
     name: String,
 ) -> StdResult<ContractAddressResponse> {
-    let address = CONTRACTS.load(deps.storage, &name)?;
+    let network = resolve_network(deps.storage, network)?;
+    let entry = CONTRACTS.load(deps.storage, (&network, &name))?;
     
     Ok(ContractAddressResponse {
         name,
-        address,
+        address: entry.address,
     })
 }
 
+pub fn query_try_contract_address(
+    deps: Deps,
+    network: Option<String>,
+    name: String,
+) -> StdResult<Option<ContractAddressResponse>> {
+    let network = resolve_network(deps.storage, network)?;
+    let entry = CONTRACTS.may_load(deps.storage, (&network, &name))?;
+    
+    Ok(entry.map(|entry| ContractAddressResponse {
+        name,
+        address: entry.address,
+    }))
+}
+
 pub fn query_all_contracts(
     deps: Deps,
+    network: Option<String>,
+    start_after: Option<String>,
+    limit: Option<u32>,
 ) -> StdResult<AllContractsResponse> {
+    let network = resolve_network(deps.storage, network)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    
     let contracts: Vec<ContractAddressResponse> = CONTRACTS
-        .range(deps.storage, None, None, Order::Ascending)
+        .prefix(&network)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
         .map(|item| {
-            let (name, address) = item?;
+            let (name, entry) = item?;
             Ok(ContractAddressResponse {
-                name: name.to_string(),
-                address,
+                name,
+                address: entry.address,
             })
         })
         .collect::<StdResult<_>>()?;
     
     Ok(AllContractsResponse { contracts })
 }
+
+pub fn query_contract_info(
+    deps: Deps,
+    network: Option<String>,
+    name: String,
+) -> StdResult<ContractInfoResponse> {
+    let network = resolve_network(deps.storage, network)?;
+    let entry = CONTRACTS.load(deps.storage, (&network, &name))?;
+    
+    Ok(ContractInfoResponse { name, entry })
+}
+
+pub fn query_contract_history(
+    deps: Deps,
+    network: Option<String>,
+    name: String,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<ContractHistoryResponse> {
+    let network = resolve_network(deps.storage, network)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    
+    let history = CONTRACT_HISTORY
+        .prefix((&network, &name))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, change)| change))
+        .collect::<StdResult<_>>()?;
+    
+    Ok(ContractHistoryResponse { name, history })
+}
+
+pub fn query_contract_at_height(
+    deps: Deps,
+    network: Option<String>,
+    name: String,
+    height: u64,
+) -> StdResult<ContractAtHeightResponse> {
+    let network = resolve_network(deps.storage, network)?;
+    
+    // Walk back from the newest change to the last one made at or before `height`
+    let change = CONTRACT_HISTORY
+        .prefix((&network, &name))
+        .range(deps.storage, None, None, Order::Descending)
+        .map(|item| item.map(|(_, change)| change))
+        .find(|change| change.as_ref().map_or(true, |change| change.height <= height))
+        .transpose()?;
+    let entry = change.and_then(|change| change.entry);
+    
+    Ok(ContractAtHeightResponse { name, entry })
+}
+
+pub fn query_default_network(deps: Deps) -> StdResult<DefaultNetworkResponse> {
+    Ok(DefaultNetworkResponse {
+        network: DEFAULT_NETWORK.load(deps.storage)?,
+    })
+}
+
+/// Every invalid setting: the default network and a page of the entries registered on `network`
+pub fn query_validate_config(
+    deps: Deps,
+    network: Option<String>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ValidateConfigResponse> {
+    let mut validator = ConfigValidator::new();
+    validator.not_empty("default_network", &DEFAULT_NETWORK.load(deps.storage)?);
+    
+    let network = resolve_network(deps.storage, network)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    
+    for item in CONTRACTS.prefix(&network).range(deps.storage, start, None, Order::Ascending).take(limit) {
+        let (name, entry) = item?;
+        validator.nested(&format!("contracts.{}.{}", network, name), validate_entry(deps.api, &name, &entry.address));
+    }
+    
+    Ok(validator.into_response())
+}
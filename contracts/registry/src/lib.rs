@@ -1,44 +1,97 @@
 use cosmwasm_std::{
     entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Addr,
 };
+use equilibria_smart_contracts::config::{ChainIdMapping, ChainIdPattern, NetworkEnvironment};
 use equilibria_smart_contracts::error::ContractError;
+use equilibria_smart_contracts::migration;
+use equilibria_smart_contracts::network;
+use equilibria_smart_contracts::ownership::{self, OwnershipAction};
+use equilibria_smart_contracts::roles::{self, Role};
 
 mod state;
 mod contract;
 
+// Stored at instantiation and checked on migration
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    contract::initialize(deps, info, msg.admin)
+    migration::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    network::instantiate_chain_id_mappings(deps.storage, msg.chain_id_mappings.as_deref())?;
+    contract::initialize(deps, env, info, msg.admin)
 }
 
 #[entry_point]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::SetContractAddress { name, address } => 
-            contract::execute_set_contract_address(deps, info, name, address),
-        ExecuteMsg::UpdateConfig { new_admin } => 
-            contract::execute_update_config(deps, info, new_admin),
+        ExecuteMsg::SetContractAddress { network, name, address, code_id, version, description } => 
+            contract::execute_set_contract_address(deps, env, info, network, name, address, code_id, version, description),
+        ExecuteMsg::SetContractAddresses { network, entries } => 
+            contract::execute_set_contract_addresses(deps, env, info, network, entries),
+        ExecuteMsg::RemoveContractAddress { network, name } => 
+            contract::execute_remove_contract_address(deps, env, info, network, name),
+        ExecuteMsg::UpdateOwnership(action) => 
+            ownership::execute_update_ownership(deps, env, info, action),
+        ExecuteMsg::GrantRole { role, address } => 
+            roles::execute_grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => 
+            roles::execute_revoke_role(deps, info, role, address),
+        ExecuteMsg::SetChainIdMapping { pattern, environment } => 
+            network::execute_set_chain_id_mapping(deps, info, pattern, environment),
     }
 }
 
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::GetContractAddress { name } => 
-            to_json_binary(&contract::query_contract_address(deps, name)?),
-        QueryMsg::GetAllContracts {} => 
-            to_json_binary(&contract::query_all_contracts(deps)?),
+        QueryMsg::GetContractAddress { name, network } => 
+            to_json_binary(&contract::query_contract_address(deps, network, name)?),
+        QueryMsg::TryGetContractAddress { name, network } => 
+            to_json_binary(&contract::query_try_contract_address(deps, network, name)?),
+        QueryMsg::GetAllContracts { network, start_after, limit } => 
+            to_json_binary(&contract::query_all_contracts(deps, network, start_after, limit)?),
+        QueryMsg::GetContractInfo { name, network } => 
+            to_json_binary(&contract::query_contract_info(deps, network, name)?),
+        QueryMsg::GetContractHistory { name, network, start_after, limit } => 
+            to_json_binary(&contract::query_contract_history(deps, network, name, start_after, limit)?),
+        QueryMsg::GetContractAtHeight { name, network, height } => 
+            to_json_binary(&contract::query_contract_at_height(deps, network, name, height)?),
+        QueryMsg::GetDefaultNetwork {} => 
+            to_json_binary(&contract::query_default_network(deps)?),
+        QueryMsg::ValidateConfig { network, start_after, limit } => 
+            to_json_binary(&contract::query_validate_config(deps, network, start_after, limit)?),
+        QueryMsg::GetOwnership {} => 
+            to_json_binary(&ownership::get_ownership(deps.storage)?),
+        QueryMsg::HasRole { role, address } => 
+            to_json_binary(&roles::query_has_role(deps, role, address)?),
+        QueryMsg::RoleMembers { role, start_after, limit } => 
+            to_json_binary(&roles::query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::GetChainIdMappings {} => 
+            to_json_binary(&network::query_chain_id_mappings(deps, &env)?),
+    }
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let previous = migration::migrate_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    
+    // Contracts without a stored version predate the ownership module and network namespaces
+    if previous.is_none() {
+        contract::migrate_legacy_layout(deps, &env)?;
     }
+    
+    Ok(migration::migrate_response(previous, CONTRACT_VERSION))
 }
 
 use schemars::JsonSchema;
@@ -47,20 +100,55 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub admin: Option<Addr>,
+    pub chain_id_mappings: Option<Vec<ChainIdMapping>>, // added to the default mappings before the network is verified
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    SetContractAddress { name: String, address: String },
-    UpdateConfig { new_admin: Option<Addr> },
+    // Metadata is optional so callers that only know the address keep working
+    SetContractAddress { 
+        network: Option<String>, 
+        name: String, 
+        address: String, 
+        code_id: Option<u64>, 
+        version: Option<String>, 
+        description: Option<String>, 
+    },
+    SetContractAddresses { network: Option<String>, entries: Vec<(String, String)> }, // (name, address) pairs
+    RemoveContractAddress { network: Option<String>, name: String },
+    UpdateOwnership(OwnershipAction),
+    GrantRole { role: Role, address: String },
+    RevokeRole { role: Role, address: String },
+    SetChainIdMapping { pattern: ChainIdPattern, environment: Option<NetworkEnvironment> }, // None removes the mapping
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    GetContractAddress { name: String },
-    GetAllContracts {},
+    GetContractAddress { name: String, network: Option<String> },
+    TryGetContractAddress { name: String, network: Option<String> }, // None instead of an error when the name is missing
+    GetAllContracts { network: Option<String>, start_after: Option<String>, limit: Option<u32> },
+    GetContractInfo { name: String, network: Option<String> },
+    GetContractHistory { name: String, network: Option<String>, start_after: Option<u32>, limit: Option<u32> }, // start_after is a change's sequence
+    GetContractAtHeight { name: String, network: Option<String>, height: u64 }, // the entry in force at the end of that block
+    GetDefaultNetwork {},
+    ValidateConfig { network: Option<String>, start_after: Option<String>, limit: Option<u32> }, // Every invalid setting in a page of a network's entries
+    GetOwnership {},
+    HasRole { role: Role, address: String }, // The owner holds every role
+    RoleMembers { role: Role, start_after: Option<String>, limit: Option<u32> },
+    GetChainIdMappings {}, // Also reports the network the current chain id maps to
+}
+
+// Entries are namespaced by network: a `NetworkEnvironment` name such as "mainnet", or any chain id.
+// Messages that leave `network` out use the registry's default, detected from its own chain at instantiation.
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DefaultNetworkResponse {
+    pub network: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -73,3 +161,39 @@ pub struct ContractAddressResponse {
 pub struct AllContractsResponse {
     pub contracts: Vec<ContractAddressResponse>,
 }
+
+// One registration of a contract under a name; every change to a name is kept in its history
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractEntry {
+    pub address: String,
+    pub code_id: Option<u64>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub deploy_height: u64, // block height the entry was registered at
+}
+
+// A change to a name at some height; `entry` is None when the name was removed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractChange {
+    pub sequence: u32, // position in the name's history, starting at 0
+    pub height: u64,
+    pub entry: Option<ContractEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractInfoResponse {
+    pub name: String,
+    pub entry: ContractEntry,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractHistoryResponse {
+    pub name: String,
+    pub history: Vec<ContractChange>, // oldest first
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractAtHeightResponse {
+    pub name: String,
+    pub entry: Option<ContractEntry>, // None if the name was not registered at that height
+}
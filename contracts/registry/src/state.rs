@@ -1,5 +1,8 @@
-use cosmwasm_std::Addr;
 use cw_storage_plus::{Item, Map};
 
-pub const ADMIN: Item<Addr> = Item::new("admin");
-pub const CONTRACTS: Map<&str, String> = Map::new("contracts");
+use crate::{ContractChange, ContractEntry};
+
+pub const DEFAULT_NETWORK: Item<String> = Item::new("default_network");
+pub const CONTRACTS: Map<(&str, &str), ContractEntry> = Map::new("contracts"); // (network, name)
+pub const CONTRACT_HISTORY: Map<(&str, &str, u32), ContractChange> = Map::new("contract_history"); // (network, name, sequence)
+pub const HISTORY_LENGTH: Map<(&str, &str), u32> = Map::new("history_length");
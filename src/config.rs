@@ -1,8 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use cosmwasm_std::{Api, StdResult};
+use cosmwasm_std::Api;
 use std::fmt;
 
+use crate::error::ContractError;
+
 /// Network environments that the contract can operate in
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum NetworkEnvironment {
@@ -21,21 +23,64 @@ impl fmt::Display for NetworkEnvironment {
     }
 }
 
-/// Gateway configuration for cross-chain operations
+/// How a chain id is matched when detecting the network
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainIdPattern {
+    Exact(String),
+    Prefix(String), // e.g. "bombay" matches every bombay-N testnet
+}
+
+impl ChainIdPattern {
+    pub fn matches(&self, chain_id: &str) -> bool {
+        match self {
+            ChainIdPattern::Exact(id) => chain_id == id,
+            ChainIdPattern::Prefix(prefix) => chain_id.starts_with(prefix.as_str()),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct GatewayConfig {
-    pub axelar_gateway: String,
-    pub noble_gateway: String,
+pub struct ChainIdMapping {
+    pub pattern: ChainIdPattern,
+    pub environment: NetworkEnvironment,
+}
+
+/// Mappings used until an admin stores its own
+pub fn default_chain_id_mappings() -> Vec<ChainIdMapping> {
+    let mapping = |pattern, environment| ChainIdMapping { pattern, environment };
+    vec![
+        mapping(ChainIdPattern::Exact("phoenix-1".to_string()), NetworkEnvironment::Mainnet),
+        mapping(ChainIdPattern::Exact("columbus-5".to_string()), NetworkEnvironment::Mainnet),
+        mapping(ChainIdPattern::Exact("pisco-1".to_string()), NetworkEnvironment::Testnet),
+        mapping(ChainIdPattern::Prefix("bombay".to_string()), NetworkEnvironment::Testnet),
+        mapping(ChainIdPattern::Prefix("localterra".to_string()), NetworkEnvironment::LocalNet),
+    ]
 }
 
-/// Configuration for different environments
+/// Networks whose addresses stand in for `network`'s, in order, when one is missing there
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FallbackChain {
+    pub network: NetworkEnvironment,
+    pub fallbacks: Vec<NetworkEnvironment>,
+}
+
+/// Fallback chains used until others are stored.
+/// Test deployments never fall back onto mainnet addresses.
+pub fn default_fallback_chains() -> Vec<FallbackChain> {
+    vec![
+        FallbackChain { network: NetworkEnvironment::Mainnet, fallbacks: vec![] },
+        FallbackChain { network: NetworkEnvironment::Testnet, fallbacks: vec![] },
+        FallbackChain { network: NetworkEnvironment::LocalNet, fallbacks: vec![NetworkEnvironment::Testnet] },
+    ]
+}
+
+/// Configuration for different environments.
+/// Every other contract address is looked up in the registry under the environment's namespace.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct NetworkConfig {
     pub environment: NetworkEnvironment,
-    pub gateway_config: GatewayConfig,
     pub registry_address: String,
-    pub oracle_address: String,
-    pub fee_collector_address: String,
 }
 
 /// Global configuration including active network and fallback order
@@ -46,64 +91,154 @@ pub struct GlobalConfig {
     pub testnet_config: NetworkConfig,
     pub localnet_config: NetworkConfig,
     pub fallback_enabled: bool,
+    #[serde(default = "default_fallback_chains")]
+    pub fallback_chains: Vec<FallbackChain>,
 }
 
 impl GlobalConfig {
-    /// Get the current active configuration, following the fallback chain if enabled
+    /// Get the configuration of the active network.
+    /// Fallback never swaps the whole configuration; it applies per address, see `fallback_chain`.
     pub fn get_active_config(&self) -> &NetworkConfig {
-        match self.active_network {
+        self.network_config(&self.active_network)
+    }
+    
+    pub fn network_config(&self, network: &NetworkEnvironment) -> &NetworkConfig {
+        match network {
             NetworkEnvironment::Mainnet => &self.mainnet_config,
-            NetworkEnvironment::Testnet => {
-                if self.fallback_enabled && matches!(self.mainnet_config.environment, NetworkEnvironment::Mainnet) {
-                    &self.mainnet_config
-                } else {
-                    &self.testnet_config
-                }
-            }
-            NetworkEnvironment::LocalNet => {
-                if self.fallback_enabled {
-                    if matches!(self.mainnet_config.environment, NetworkEnvironment::Mainnet) {
-                        &self.mainnet_config
-                    } else if matches!(self.testnet_config.environment, NetworkEnvironment::Testnet) {
-                        &self.testnet_config
-                    } else {
-                        &self.localnet_config
-                    }
-                } else {
-                    &self.localnet_config
-                }
-            }
+            NetworkEnvironment::Testnet => &self.testnet_config,
+            NetworkEnvironment::LocalNet => &self.localnet_config,
         }
     }
     
-    /// Get gateway address for a specific gateway type
-    pub fn get_gateway_address(&self, gateway_type: &str) -> String {
-        let config = self.get_active_config();
-        match gateway_type {
-            "axelar" => config.gateway_config.axelar_gateway.clone(),
-            "noble" => config.gateway_config.noble_gateway.clone(),
-            _ => "".to_string(),
+    /// Networks to look an address up on, in order: `network` itself, then its fallbacks if enabled
+    pub fn fallback_chain(&self, network: &NetworkEnvironment) -> Vec<NetworkEnvironment> {
+        let mut chain = vec![network.clone()];
+        if !self.fallback_enabled {
+            return chain;
         }
+        
+        let fallbacks = self.fallback_chains.iter().filter(|chain| &chain.network == network).flat_map(|chain| &chain.fallbacks);
+        for fallback in fallbacks {
+            if !chain.contains(fallback) {
+                chain.push(fallback.clone());
+            }
+        }
+        chain
     }
     
-    /// Validate addresses in the current active configuration
-    pub fn validate_addresses(&self, api: &dyn Api) -> StdResult<()> {
-        let config = self.get_active_config();
-        
-        // Validate registry address
-        api.addr_validate(&config.registry_address)?;
-        
-        // Validate oracle address
-        api.addr_validate(&config.oracle_address)?;
+    /// Registry address for `network`, from the first network in its fallback chain that sets one
+    pub fn resolve_registry_address(&self, network: &NetworkEnvironment) -> Option<(String, NetworkEnvironment)> {
+        self.fallback_chain(network)
+            .into_iter()
+            .map(|network| (self.network_config(&network).registry_address.clone(), network))
+            .find(|(address, _)| !address.is_empty())
+    }
+    
+    /// Check every field; run before each write
+    pub fn validate(&self, api: &dyn Api) -> ConfigValidator {
+        let mut validator = ConfigValidator::new();
+        let networks = [NetworkEnvironment::Mainnet, NetworkEnvironment::Testnet, NetworkEnvironment::LocalNet];
         
-        // Validate fee collector address
-        api.addr_validate(&config.fee_collector_address)?;
+        for network in networks.iter() {
+            let config = self.network_config(network);
+            let field = format!("{}_config", network);
+            validator.check(&format!("{}.environment", field), &config.environment == network, format!("must be {}", network));
+            // An empty registry address leaves the network to its fallbacks
+            if !config.registry_address.is_empty() {
+                validator.address(api, &format!("{}.registry_address", field), &config.registry_address);
+            }
+        }
         
-        // Validate gateway addresses
-        api.addr_validate(&config.gateway_config.axelar_gateway)?;
-        api.addr_validate(&config.gateway_config.noble_gateway)?;
+        validator.check(
+            "active_network",
+            self.resolve_registry_address(&self.active_network).is_some(),
+            format!("no registry address on {} or its fallbacks", self.active_network),
+        );
+        for network in networks.iter() {
+            let chains = self.fallback_chains.iter().filter(|chain| &chain.network == network).count();
+            validator.check("fallback_chains", chains <= 1, format!("more than one chain for {}", network));
+        }
         
-        Ok(())
+        validator
+    }
+}
+
+/// Bounds on how long an oracle price stays usable, in seconds.
+/// Shared by the oracle's own validation and the governance parameter that sets it.
+pub const MIN_PRICE_TIMEOUT: u64 = 60;
+pub const MAX_PRICE_TIMEOUT: u64 = 86_400;
+
+/// A config field that failed validation
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InvalidField {
+    pub field: String,
+    pub reason: String,
+}
+
+/// Response for the `ValidateConfig` query of every contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ValidateConfigResponse {
+    pub valid: bool,
+    pub invalid_fields: Vec<InvalidField>,
+}
+
+/// Collects every invalid field of a config.
+/// Writes reject the config on the first one, the `ValidateConfig` query reports them all.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigValidator {
+    invalid_fields: Vec<InvalidField>,
+}
+
+impl ConfigValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn check(&mut self, field: &str, valid: bool, reason: impl Into<String>) -> &mut Self {
+        if !valid {
+            self.invalid_fields.push(InvalidField { field: field.to_string(), reason: reason.into() });
+        }
+        self
+    }
+    
+    pub fn address(&mut self, api: &dyn Api, field: &str, address: &str) -> &mut Self {
+        if let Err(err) = api.addr_validate(address) {
+            self.check(field, false, format!("invalid address {}: {}", address, err));
+        }
+        self
+    }
+    
+    pub fn not_empty(&mut self, field: &str, value: &str) -> &mut Self {
+        self.check(field, !value.is_empty(), "cannot be empty")
+    }
+    
+    /// `min <= value <= max`
+    pub fn range<T: PartialOrd + fmt::Display>(&mut self, field: &str, value: T, min: T, max: T) -> &mut Self {
+        let valid = min <= value && value <= max;
+        self.check(field, valid, format!("must be between {} and {}, got {}", min, max, value))
+    }
+    
+    /// Take over the invalid fields of a nested config, as `prefix.field`
+    pub fn nested(&mut self, prefix: &str, other: ConfigValidator) -> &mut Self {
+        for InvalidField { field, reason } in other.invalid_fields {
+            self.invalid_fields.push(InvalidField { field: format!("{}.{}", prefix, field), reason });
+        }
+        self
+    }
+    
+    pub fn is_valid(&self) -> bool {
+        self.invalid_fields.is_empty()
+    }
+    
+    pub fn into_result(self) -> Result<(), ContractError> {
+        match self.invalid_fields.into_iter().next() {
+            Some(InvalidField { field, reason }) => Err(ContractError::InvalidConfig { field, reason }),
+            None => Ok(()),
+        }
+    }
+    
+    pub fn into_response(self) -> ValidateConfigResponse {
+        ValidateConfigResponse { valid: self.is_valid(), invalid_fields: self.invalid_fields }
     }
 }
 
@@ -116,72 +251,117 @@ pub mod storage {
     // Storage key for global configuration
     pub const GLOBAL_CONFIG: Item<GlobalConfig> = Item::new("global_config");
     
+    /// Save the global configuration if every field is valid
+    pub fn save_config(storage: &mut dyn Storage, api: &dyn Api, config: &GlobalConfig) -> Result<(), ContractError> {
+        config.validate(api).into_result()?;
+        GLOBAL_CONFIG.save(storage, config)?;
+        Ok(())
+    }
+    
+    fn update_config(
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        action: impl FnOnce(&mut GlobalConfig),
+    ) -> Result<(), ContractError> {
+        let mut config = GLOBAL_CONFIG.load(storage)?;
+        action(&mut config);
+        save_config(storage, api, &config)
+    }
+    
+    /// Every invalid field of the stored global configuration
+    pub fn validate_config(storage: &dyn Storage, api: &dyn Api) -> StdResult<ValidateConfigResponse> {
+        Ok(GLOBAL_CONFIG.load(storage)?.validate(api).into_response())
+    }
+    
+    // Chain id to environment mappings; the defaults apply until one is stored
+    pub const CHAIN_ID_MAPPINGS: Item<Vec<ChainIdMapping>> = Item::new("chain_id_mappings");
+    
+    pub fn load_chain_id_mappings(storage: &dyn Storage) -> StdResult<Vec<ChainIdMapping>> {
+        Ok(CHAIN_ID_MAPPINGS.may_load(storage)?.unwrap_or_else(default_chain_id_mappings))
+    }
+    
+    /// Map a chain id pattern to an environment, replacing any mapping for the same pattern.
+    /// `None` removes the mapping.
+    pub fn set_chain_id_mapping(
+        storage: &mut dyn Storage,
+        pattern: ChainIdPattern,
+        environment: Option<NetworkEnvironment>,
+    ) -> Result<(), ContractError> {
+        let value = match &pattern {
+            ChainIdPattern::Exact(id) => id,
+            ChainIdPattern::Prefix(prefix) => prefix,
+        };
+        let mut validator = ConfigValidator::new();
+        validator.not_empty("chain_id_pattern", value);
+        validator.into_result()?;
+        
+        let mut mappings = load_chain_id_mappings(storage)?;
+        mappings.retain(|mapping| mapping.pattern != pattern);
+        if let Some(environment) = environment {
+            mappings.push(ChainIdMapping { pattern, environment });
+        }
+        
+        CHAIN_ID_MAPPINGS.save(storage, &mappings)?;
+        Ok(())
+    }
+    
     /// Initialize configuration with default mainnet priority
     pub fn initialize_config(
         storage: &mut dyn Storage,
+        api: &dyn Api,
         mainnet_registry: String,
         testnet_registry: String,
         localnet_registry: String,
-    ) -> StdResult<()> {
+    ) -> Result<(), ContractError> {
         let config = GlobalConfig {
             active_network: NetworkEnvironment::Mainnet,
             fallback_enabled: true,
             mainnet_config: NetworkConfig {
                 environment: NetworkEnvironment::Mainnet,
-                gateway_config: GatewayConfig {
-                    axelar_gateway: "terra1axelar_mainnet".to_string(),
-                    noble_gateway: "terra1noble_mainnet".to_string(),
-                },
                 registry_address: mainnet_registry,
-                oracle_address: "terra1oracle_mainnet".to_string(),
-                fee_collector_address: "terra1fee_collector_mainnet".to_string(),
             },
             testnet_config: NetworkConfig {
                 environment: NetworkEnvironment::Testnet,
-                gateway_config: GatewayConfig {
-                    axelar_gateway: "terra1axelar_testnet".to_string(),
-                    noble_gateway: "terra1noble_testnet".to_string(),
-                },
                 registry_address: testnet_registry,
-                oracle_address: "terra1oracle_testnet".to_string(),
-                fee_collector_address: "terra1fee_collector_testnet".to_string(),
             },
             localnet_config: NetworkConfig {
                 environment: NetworkEnvironment::LocalNet,
-                gateway_config: GatewayConfig {
-                    axelar_gateway: "terra1axelar_localnet".to_string(), 
-                    noble_gateway: "terra1noble_localnet".to_string(),
-                },
                 registry_address: localnet_registry,
-                oracle_address: "terra1oracle_localnet".to_string(),
-                fee_collector_address: "terra1fee_collector_localnet".to_string(),
             },
+            fallback_chains: default_fallback_chains(),
         };
         
-        GLOBAL_CONFIG.save(storage, &config)
+        save_config(storage, api, &config)
     }
     
     /// Update the active network environment
     pub fn set_active_network(
         storage: &mut dyn Storage,
+        api: &dyn Api,
         network: NetworkEnvironment,
-    ) -> StdResult<()> {
-        GLOBAL_CONFIG.update(storage, |mut config| -> StdResult<_> {
-            config.active_network = network;
-            Ok(config)
-        })?;
-        Ok(())
+    ) -> Result<(), ContractError> {
+        update_config(storage, api, |config| config.active_network = network)
     }
     
     /// Toggle fallback functionality
     pub fn set_fallback_enabled(
         storage: &mut dyn Storage,
+        api: &dyn Api,
         enabled: bool,
-    ) -> StdResult<()> {
-        GLOBAL_CONFIG.update(storage, |mut config| -> StdResult<_> {
-            config.fallback_enabled = enabled;
-            Ok(config)
-        })?;
-        Ok(())
+    ) -> Result<(), ContractError> {
+        update_config(storage, api, |config| config.fallback_enabled = enabled)
+    }
+    
+    /// Replace the ordered fallbacks of `network`
+    pub fn set_fallback_chain(
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        network: NetworkEnvironment,
+        fallbacks: Vec<NetworkEnvironment>,
+    ) -> Result<(), ContractError> {
+        update_config(storage, api, |config| {
+            config.fallback_chains.retain(|chain| chain.network != network);
+            config.fallback_chains.push(FallbackChain { network, fallbacks });
+        })
     }
 }
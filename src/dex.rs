@@ -0,0 +1,159 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, CosmosMsg, Decimal, QuerierWrapper, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::oracle::PoolReserves;
+
+// Asset types shared by Terraswap and Astroport pair contracts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfo {
+    Token { contract_addr: String },
+    NativeToken { denom: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairQueryMsg {
+    Pool {},
+    Simulation { offer_asset: Asset },
+    ReverseSimulation { ask_asset: Asset },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PairExecuteMsg {
+    Swap {
+        offer_asset: Asset,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+// Hook message for swapping a CW20 offer asset through `Cw20ExecuteMsg::Send`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    Swap {
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolResponse {
+    pub assets: Vec<Asset>,
+    pub total_share: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulationResponse {
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReverseSimulationResponse {
+    pub offer_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
+
+/// A pair contract trading EQA against a collateral asset
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DexPair {
+    pub contract_addr: Addr,
+    pub eqa: AssetInfo,
+    pub collateral: AssetInfo,
+}
+
+impl DexPair {
+    pub fn query_pool(&self, querier: &QuerierWrapper) -> StdResult<PoolResponse> {
+        querier.query_wasm_smart(self.contract_addr.to_string(), &PairQueryMsg::Pool {})
+    }
+
+    /// Read the EQA and collateral reserves, failing if the pair trades anything else
+    pub fn query_reserves(&self, querier: &QuerierWrapper) -> StdResult<PoolReserves> {
+        let pool = self.query_pool(querier)?;
+
+        let mut reserves = PoolReserves { eqa: Uint128::zero(), collateral: Uint128::zero() };
+        for asset in pool.assets {
+            if asset.info == self.eqa {
+                reserves.eqa = asset.amount;
+            } else if asset.info == self.collateral {
+                reserves.collateral = asset.amount;
+            } else {
+                return Err(StdError::generic_err(format!(
+                    "Pair {} does not trade EQA against the configured collateral",
+                    self.contract_addr
+                )));
+            }
+        }
+
+        Ok(reserves)
+    }
+
+    /// Spot price of EQA in collateral, from the pool reserves
+    pub fn query_price(&self, querier: &QuerierWrapper) -> StdResult<Decimal> {
+        let reserves = self.query_reserves(querier)?;
+        if reserves.eqa.is_zero() {
+            return Err(StdError::generic_err("Pair has no EQA liquidity"));
+        }
+
+        Ok(Decimal::from_ratio(reserves.collateral, reserves.eqa))
+    }
+
+    pub fn simulate(&self, querier: &QuerierWrapper, offer_asset: Asset) -> StdResult<SimulationResponse> {
+        querier.query_wasm_smart(self.contract_addr.to_string(), &PairQueryMsg::Simulation { offer_asset })
+    }
+
+    pub fn reverse_simulate(&self, querier: &QuerierWrapper, ask_asset: Asset) -> StdResult<ReverseSimulationResponse> {
+        querier.query_wasm_smart(self.contract_addr.to_string(), &PairQueryMsg::ReverseSimulation { ask_asset })
+    }
+
+    /// Build the message that swaps `offer_asset` on the pair.
+    /// Native assets are attached as funds, CW20 assets go through the token's `Send` hook.
+    pub fn swap_msg(
+        &self,
+        offer_asset: Asset,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = match &offer_asset.info {
+            AssetInfo::NativeToken { denom } => WasmMsg::Execute {
+                contract_addr: self.contract_addr.to_string(),
+                msg: to_json_binary(&PairExecuteMsg::Swap {
+                    offer_asset: offer_asset.clone(),
+                    belief_price,
+                    max_spread,
+                    to,
+                })?,
+                funds: vec![Coin { denom: denom.clone(), amount: offer_asset.amount }],
+            },
+            AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: self.contract_addr.to_string(),
+                    amount: offer_asset.amount,
+                    msg: to_json_binary(&Cw20HookMsg::Swap { belief_price, max_spread, to })?,
+                })?,
+                funds: vec![],
+            },
+        };
+
+        Ok(msg.into())
+    }
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{DivideByZeroError, OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,9 +6,58 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    DivideByZero(#[from] DivideByZeroError),
+
+    // Access control
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("No ownership transfer is pending")]
+    NoPendingOwner {},
+
+    #[error("Ownership proposal has expired")]
+    OwnershipProposalExpired {},
+
+    #[error("Missing role: {role}")]
+    MissingRole { role: String },
+
+    // Migration and deployment
+    #[error("Cannot migrate {found} to {expected}")]
+    ContractMismatch { expected: String, found: String },
+
+    #[error("Cannot downgrade from {from} to {to}")]
+    CannotDowngrade { from: String, to: String },
+
+    #[error("Invalid contract version: {version}")]
+    InvalidVersion { version: String },
+
+    #[error("Unknown network for chain id {chain_id}")]
+    UnknownNetwork { chain_id: String },
+
+    #[error("Network mismatch: expected {expected}, detected {detected}")]
+    NetworkMismatch { expected: String, detected: String },
+
+    #[error("Contract {name} is not registered on {network}")]
+    ContractNotFound { name: String, network: String },
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    // Configuration
+    #[error("Invalid config field {field}: {reason}")]
+    InvalidConfig { field: String, reason: String },
+
+    #[error("{component} is not configured")]
+    NotConfigured { component: String },
+
+    #[error("Operation is paused: {operation}")]
+    Paused { operation: String },
+
+    // Assets and amounts
     #[error("Invalid funds")]
     InvalidFunds {},
 
@@ -21,8 +70,20 @@ pub enum ContractError {
     #[error("Invalid amount")]
     InvalidAmount {},
 
+    #[error("Unsupported asset: {asset}")]
+    UnsupportedAsset { asset: String },
+
+    #[error("Collateral asset is disabled: {asset}")]
+    AssetDisabled { asset: String },
+
+    #[error("Insufficient funds: required {required}, available {available}")]
+    InsufficientFunds { required: Uint128, available: Uint128 },
+
     #[error("Insufficient collateral: required {required}, available {available}")]
-    InsufficientCollateral { required: String, available: String },
+    InsufficientCollateral { required: Uint128, available: Uint128 },
+
+    #[error("Cap exceeded for {asset}: requested {requested}, available {available}")]
+    CapExceeded { asset: String, requested: Uint128, available: Uint128 },
     
     #[error("Collateralization below minimum threshold")]
     CollateralizationTooLow {},
@@ -30,6 +91,84 @@ pub enum ContractError {
     #[error("Operation not supported in the current market conditions")]
     UnsupportedMarketCondition {},
     
-    #[error("Custom error: {msg}")]
-    CustomError { msg: String },
+    // Oracle
+    #[error("Price for {denom} is stale: last updated at {last_updated}, max age {max_age}s")]
+    StalePrice { denom: String, last_updated: u64, max_age: u64 },
+
+    #[error("Not enough price observations for {denom}")]
+    InsufficientObservations { denom: String },
+
+    // Bridging
+    #[error("Unknown gateway: {gateway}")]
+    UnknownGateway { gateway: String },
+
+    #[error("Rate limit exceeded for {asset} to {destination_chain}: requested {requested}, available {available}")]
+    RateLimitExceeded { asset: String, destination_chain: String, requested: Uint128, available: Uint128 },
+
+    #[error("Rate limit can only be tightened below {max_outflow}")]
+    RateLimitNotTightened { max_outflow: Uint128 },
+
+    #[error("Amount {amount} does not cover the bridge fee of {fee}")]
+    BridgeFeeNotCovered { amount: Uint128, fee: Uint128 },
+
+    #[error("Bridge fees in {denom} must be deducted from the transfer")]
+    BridgeFeeNotDeducted { denom: String },
+
+    #[error("Bridge fee must be exactly {expected}{denom}, sent {sent}{denom}")]
+    IncorrectBridgeFee { denom: String, expected: Uint128, sent: Uint128 },
+
+    #[error("Only native collateral can be sent over IBC, got {asset}")]
+    NativeAssetRequired { asset: String },
+
+    #[error("Transfer {transfer_id} is no longer pending")]
+    TransferNotPending { transfer_id: u64 },
+
+    #[error("Transfer {transfer_id} cannot be refunded")]
+    TransferNotRefundable { transfer_id: u64 },
+
+    #[error("Transfer {transfer_id} does not time out until {timeout_at}")]
+    TransferNotExpired { transfer_id: u64, timeout_at: u64 },
+
+    #[error("Untrusted remote sender {address} on {chain}")]
+    UntrustedRemote { chain: String, address: String },
+
+    #[error("Unsupported payload version: {version}")]
+    UnsupportedPayloadVersion { version: u16 },
+
+    #[error("Message already processed: {message_id}")]
+    MessageAlreadyProcessed { message_id: String },
+
+    // Governance
+    #[error("Insufficient proposal deposit: sent {sent}{denom}, required {required}{denom}")]
+    InsufficientDeposit { denom: String, required: Uint128, sent: Uint128 },
+
+    #[error("Voting on proposal {proposal_id} ended at {end_time}")]
+    VotingClosed { proposal_id: u64, end_time: u64 },
+
+    #[error("Proposal {proposal_id} already has votes")]
+    VotingStarted { proposal_id: u64 },
+
+    #[error("Voting on proposal {proposal_id} is open until {end_time}")]
+    VotingNotEnded { proposal_id: u64, end_time: u64 },
+
+    #[error("Proposal {proposal_id} is {status}")]
+    InvalidProposalStatus { proposal_id: u64, status: String },
+
+    #[error("Proposal {proposal_id} is timelocked until {executable_at}")]
+    ProposalTimelocked { proposal_id: u64, executable_at: u64 },
+
+    #[error("Cannot delegate to yourself")]
+    SelfDelegation {},
+
+    #[error("No delegation to remove")]
+    NoDelegation {},
+
+    #[error("Duplicate guardian: {guardian}")]
+    DuplicateGuardian { guardian: String },
+
+    #[error("Guardian action {action_id} has already been executed")]
+    GuardianActionExecuted { action_id: u64 },
+
+    #[error("Guardian {guardian} has already approved action {action_id}")]
+    AlreadyApproved { action_id: u64, guardian: String },
 }
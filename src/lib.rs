@@ -3,7 +3,11 @@ pub mod error;
 pub mod math;
 pub mod config;
 pub mod network;
+pub mod migration;
+pub mod ownership;
+pub mod roles;
 pub mod oracle;
+pub mod dex;
 pub mod mocks;
 
 use cosmwasm_std::{StdError, StdResult, Storage, Uint128};
@@ -0,0 +1,121 @@
+use cosmwasm_std::{Addr, Response, StdResult, Storage};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+use crate::ownership::{Ownership, OWNERSHIP};
+
+/// Contract name and version, stored under the same key and layout as cw2
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContractVersion {
+    pub contract: String, // e.g. "crates.io:registry"
+    pub version: String,  // semver, e.g. "0.1.0"
+}
+
+pub const CONTRACT_INFO: Item<ContractVersion> = Item::new("contract_info");
+
+// Admin item used by contracts that predate the ownership module
+const LEGACY_ADMIN: Item<Addr> = Item::new("admin");
+
+/// Record the contract name and version at instantiation
+pub fn set_contract_version(storage: &mut dyn Storage, contract: &str, version: &str) -> StdResult<()> {
+    CONTRACT_INFO.save(storage, &ContractVersion {
+        contract: contract.to_string(),
+        version: version.to_string(),
+    })
+}
+
+pub fn get_contract_version(storage: &dyn Storage) -> StdResult<ContractVersion> {
+    CONTRACT_INFO.load(storage)
+}
+
+// major.minor.patch; pre-release and build suffixes are ignored
+fn parse_version(version: &str) -> Result<(u64, u64, u64), ContractError> {
+    let invalid = || ContractError::InvalidVersion { version: version.to_string() };
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let parts = core
+        .split('.')
+        .map(|part| part.parse::<u64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match parts[..] {
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Check a migration to `version` and record it.
+/// Returns the version migrated from, or `None` for contracts instantiated before versions were stored.
+pub fn migrate_version(
+    storage: &mut dyn Storage,
+    contract: &str,
+    version: &str,
+) -> Result<Option<String>, ContractError> {
+    let new_version = parse_version(version)?;
+    let previous = CONTRACT_INFO.may_load(storage)?;
+
+    if let Some(previous) = &previous {
+        if previous.contract != contract {
+            return Err(ContractError::ContractMismatch {
+                expected: contract.to_string(),
+                found: previous.contract.clone(),
+            });
+        }
+        if parse_version(&previous.version)? > new_version {
+            return Err(ContractError::CannotDowngrade {
+                from: previous.version.clone(),
+                to: version.to_string(),
+            });
+        }
+    }
+
+    set_contract_version(storage, contract, version)?;
+    Ok(previous.map(|previous| previous.version))
+}
+
+/// Make `admin` the owner of a contract that predates the ownership module
+pub fn migrate_legacy_owner(storage: &mut dyn Storage, admin: Addr) -> StdResult<()> {
+    if OWNERSHIP.may_load(storage)?.is_some() {
+        return Ok(());
+    }
+
+    OWNERSHIP.save(storage, &Ownership { owner: Some(admin), pending_owner: None, pending_expiry: None })
+}
+
+/// Move the pre-ownership `admin` item, if any, into the ownership record
+pub fn migrate_legacy_admin(storage: &mut dyn Storage) -> StdResult<()> {
+    if let Some(admin) = LEGACY_ADMIN.may_load(storage)? {
+        migrate_legacy_owner(storage, admin)?;
+        LEGACY_ADMIN.remove(storage);
+    }
+
+    Ok(())
+}
+
+// Configs that predate the ownership module kept the admin inline
+#[derive(Serialize, Deserialize)]
+struct LegacyConfig {
+    admin: Option<Addr>,
+}
+
+/// Move an admin kept inline in the config stored under `config_key` into the ownership record.
+/// Returns whether one was found; the caller should then re-save its config to drop the field.
+pub fn migrate_legacy_config_admin(storage: &mut dyn Storage, config_key: &str) -> StdResult<bool> {
+    let legacy: Item<LegacyConfig> = Item::new(config_key);
+    match legacy.may_load(storage)?.and_then(|config| config.admin) {
+        Some(admin) => {
+            migrate_legacy_owner(storage, admin)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Response for a contract's `migrate` entry point
+pub fn migrate_response(previous: Option<String>, version: &str) -> Response {
+    Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", previous.unwrap_or_else(|| "none".to_string()))
+        .add_attribute("to_version", version)
+}
@@ -2,9 +2,15 @@
 
 use cosmwasm_std::{
     testing::{MockApi, MockQuerier, MockStorage},
-    OwnedDeps, Decimal, to_json_binary, Empty, Querier,
+    OwnedDeps, Decimal, to_json_binary, Empty, Querier, Binary, Uint128, QuerierResult, StdResult, StdError,
+    SystemResult, ContractResult, CosmosMsg, WasmMsg,
 };
+use cw20::Cw20ExecuteMsg;
 
+use crate::dex::{
+    Asset, AssetInfo, Cw20HookMsg, PairExecuteMsg, PairQueryMsg, PoolResponse, ReverseSimulationResponse,
+    SimulationResponse,
+};
 use crate::oracle::OracleResponse;
 
 // Create mock dependencies with a custom querier
@@ -89,6 +95,157 @@ impl Querier for MockCustomQuerier {
     }
 }
 
+/// Constant product pair answering Terraswap-style queries, for use from a mock querier
+pub struct MockPair {
+    pub assets: [Asset; 2],
+    pub commission: Decimal, // Taken from the return amount
+}
+
+impl MockPair {
+    pub fn new(first: Asset, second: Asset, commission: Decimal) -> Self {
+        Self {
+            assets: [first, second],
+            commission,
+        }
+    }
+
+    // Reserves of (offer, ask) for a swap offering `offer`
+    fn reserves(&self, offer: &AssetInfo) -> StdResult<(Uint128, Uint128)> {
+        if *offer == self.assets[0].info {
+            Ok((self.assets[0].amount, self.assets[1].amount))
+        } else if *offer == self.assets[1].info {
+            Ok((self.assets[1].amount, self.assets[0].amount))
+        } else {
+            Err(StdError::generic_err("Asset is not traded by this pair"))
+        }
+    }
+
+    fn other(&self, info: &AssetInfo) -> StdResult<AssetInfo> {
+        if *info == self.assets[0].info {
+            Ok(self.assets[1].info.clone())
+        } else if *info == self.assets[1].info {
+            Ok(self.assets[0].info.clone())
+        } else {
+            Err(StdError::generic_err("Asset is not traded by this pair"))
+        }
+    }
+
+    pub fn simulate(&self, offer_asset: &Asset) -> StdResult<SimulationResponse> {
+        let (offer_pool, ask_pool) = self.reserves(&offer_asset.info)?;
+        
+        // x * y = k, with the commission taken from the output
+        let return_amount = ask_pool - ask_pool.multiply_ratio(offer_pool, offer_pool + offer_asset.amount);
+        let spread_amount = offer_asset.amount.multiply_ratio(ask_pool, offer_pool).saturating_sub(return_amount);
+        let commission_amount = return_amount * self.commission;
+        
+        Ok(SimulationResponse {
+            return_amount: return_amount - commission_amount,
+            spread_amount,
+            commission_amount,
+        })
+    }
+
+    pub fn reverse_simulate(&self, ask_asset: &Asset) -> StdResult<ReverseSimulationResponse> {
+        let offer_info = self.other(&ask_asset.info)?;
+        let (offer_pool, ask_pool) = self.reserves(&offer_info)?;
+        
+        // Gross up the ask amount for the commission, then invert x * y = k
+        let before_commission = ask_asset.amount * (Decimal::one() / (Decimal::one() - self.commission));
+        if before_commission >= ask_pool {
+            return Err(StdError::generic_err("Not enough liquidity in the pair"));
+        }
+        let offer_amount = offer_pool.multiply_ratio(ask_pool, ask_pool - before_commission) - offer_pool;
+        
+        Ok(ReverseSimulationResponse {
+            offer_amount,
+            spread_amount: offer_amount.multiply_ratio(ask_pool, offer_pool).saturating_sub(before_commission),
+            commission_amount: before_commission - ask_asset.amount,
+        })
+    }
+
+    /// Trade `offer_asset` against the pool, moving its reserves, and return the asset paid out.
+    /// Like Terraswap, fails if the return falls short of `belief_price` by more than `max_spread`.
+    pub fn swap(
+        &mut self,
+        offer_asset: &Asset,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+    ) -> StdResult<Asset> {
+        let ask_info = self.other(&offer_asset.info)?;
+        let simulation = self.simulate(offer_asset)?;
+        
+        if let (Some(belief_price), Some(max_spread)) = (belief_price, max_spread) {
+            let expected_return = offer_asset.amount * (Decimal::one() / belief_price);
+            let spread = expected_return.saturating_sub(simulation.return_amount);
+            if !expected_return.is_zero() && Decimal::from_ratio(spread, expected_return) > max_spread {
+                return Err(StdError::generic_err("Operation exceeds max spread limit"));
+            }
+        }
+        
+        // The commission stays in the pool
+        for asset in self.assets.iter_mut() {
+            if asset.info == offer_asset.info {
+                asset.amount += offer_asset.amount;
+            } else {
+                asset.amount -= simulation.return_amount;
+            }
+        }
+        
+        Ok(Asset { info: ask_info, amount: simulation.return_amount })
+    }
+
+    /// Execute a swap message as built by `DexPair::swap_msg`, native or through a CW20 `Send`
+    pub fn execute(&mut self, msg: &CosmosMsg) -> StdResult<Asset> {
+        let (contract_addr, msg, funds) = match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => (contract_addr, msg, funds),
+            other => return Err(StdError::generic_err(format!("Not a swap: {:?}", other))),
+        };
+        
+        if let Ok(PairExecuteMsg::Swap { offer_asset, belief_price, max_spread, .. }) = cosmwasm_std::from_json(msg) {
+            let sent = match &offer_asset.info {
+                AssetInfo::NativeToken { denom } => funds.iter().find(|c| &c.denom == denom).map(|c| c.amount),
+                AssetInfo::Token { .. } => None,
+            };
+            if sent != Some(offer_asset.amount) {
+                return Err(StdError::generic_err("Native offer asset must be sent with the swap"));
+            }
+            return self.swap(&offer_asset, belief_price, max_spread);
+        }
+        
+        match cosmwasm_std::from_json(msg)? {
+            Cw20ExecuteMsg::Send { amount, msg, .. } => {
+                let Cw20HookMsg::Swap { belief_price, max_spread, .. } = cosmwasm_std::from_json(&msg)?;
+                let offer_asset = Asset {
+                    info: AssetInfo::Token { contract_addr: contract_addr.clone() },
+                    amount,
+                };
+                self.swap(&offer_asset, belief_price, max_spread)
+            }
+            _ => Err(StdError::generic_err("Not a swap")),
+        }
+    }
+
+    /// Answer a smart query sent to the pair
+    pub fn query(&self, msg: &Binary) -> QuerierResult {
+        let response = match cosmwasm_std::from_json(msg) {
+            Ok(PairQueryMsg::Pool {}) => to_json_binary(&PoolResponse {
+                assets: self.assets.to_vec(),
+                total_share: Uint128::zero(),
+            }),
+            Ok(PairQueryMsg::Simulation { offer_asset }) => self.simulate(&offer_asset).and_then(|r| to_json_binary(&r)),
+            Ok(PairQueryMsg::ReverseSimulation { ask_asset }) => {
+                self.reverse_simulate(&ask_asset).and_then(|r| to_json_binary(&r))
+            }
+            Err(err) => Err(err),
+        };
+        
+        match response {
+            Ok(binary) => SystemResult::Ok(ContractResult::Ok(binary)),
+            Err(err) => SystemResult::Ok(ContractResult::Err(err.to_string())),
+        }
+    }
+}
+
 // Mock implementations for external dependencies
 #[cfg(test)]
 pub mod terra_cosmwasm {
@@ -1,61 +1,104 @@
-use cosmwasm_std::{Deps, DepsMut, Env, StdError, StdResult};
-use crate::config::{NetworkEnvironment, storage::GLOBAL_CONFIG};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::config::{default_chain_id_mappings, ChainIdMapping, ChainIdPattern, NetworkEnvironment};
+use crate::config::storage::{load_chain_id_mappings, set_active_network, set_chain_id_mapping, GLOBAL_CONFIG};
+use crate::error::ContractError;
+use crate::roles::{self, Role};
+
+// The registry's address query; the registry contract depends on this crate, so its types are mirrored here
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RegistryQueryMsg {
+    TryGetContractAddress { name: String, network: Option<String> },
+}
+
+#[derive(Deserialize)]
+struct ContractAddressResponse {
+    address: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ChainIdMappingsResponse {
+    pub mappings: Vec<ChainIdMapping>,
+    pub chain_id: String,
+    pub network: Option<NetworkEnvironment>, // None when the current chain id matches no mapping
+}
+
+/// A contract address and the network it was resolved on
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ResolvedAddress {
+    pub name: String,
+    pub address: String,
+    pub network: NetworkEnvironment, // differs from the active network when the address came from a fallback
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ResolvedAddressesResponse {
+    pub active_network: NetworkEnvironment,
+    pub fallback_chain: Vec<NetworkEnvironment>,
+    pub addresses: Vec<ResolvedAddress>,
+    pub missing: Vec<String>, // names found on no network of the chain
+}
 
 /// Network detection and management functions
 pub struct NetworkManager {}
 
 impl NetworkManager {
-    /// Detect the current network from the chain_id in the environment
-    pub fn detect_network(env: &Env) -> NetworkEnvironment {
-        let chain_id = &env.block.chain_id;
-        
-        // Check for mainnet chain ids
-        if chain_id == "phoenix-1" || chain_id == "columbus-5" {
-            return NetworkEnvironment::Mainnet;
-        }
-        
-        // Check for testnet chain ids
-        if chain_id == "pisco-1" || chain_id.starts_with("bombay") {
-            return NetworkEnvironment::Testnet;
-        }
-        
-        // Default to localnet for any other chain id
-        NetworkEnvironment::LocalNet
+    /// Match a chain id against a set of mappings.
+    /// Exact matches win over prefixes, and longer prefixes over shorter ones; `None` means the chain is unknown.
+    pub fn match_chain_id(mappings: &[ChainIdMapping], chain_id: &str) -> Option<NetworkEnvironment> {
+        mappings
+            .iter()
+            .filter(|mapping| mapping.pattern.matches(chain_id))
+            .max_by_key(|mapping| match &mapping.pattern {
+                ChainIdPattern::Exact(_) => usize::MAX,
+                ChainIdPattern::Prefix(prefix) => prefix.len(),
+            })
+            .map(|mapping| mapping.environment.clone())
+    }
+    
+    /// Detect the current network from the chain_id in the environment, using the default mappings
+    pub fn detect_network(env: &Env) -> Option<NetworkEnvironment> {
+        Self::match_chain_id(&default_chain_id_mappings(), &env.block.chain_id)
     }
     
-    /// Verify that we're running in the expected network environment
+    /// Detect the current network using the mappings stored in this contract
+    pub fn detect_configured_network(storage: &dyn Storage, env: &Env) -> StdResult<Option<NetworkEnvironment>> {
+        Ok(Self::match_chain_id(&load_chain_id_mappings(storage)?, &env.block.chain_id))
+    }
+    
+    /// Verify that we're running on a known network, and on `expected` if one is given.
+    /// Every contract calls this at instantiation.
     pub fn verify_network(
         deps: Deps, 
         env: &Env, 
-        expected: NetworkEnvironment,
-    ) -> StdResult<()> {
-        let current = Self::detect_network(env);
+        expected: Option<NetworkEnvironment>,
+    ) -> Result<NetworkEnvironment, ContractError> {
+        let current = Self::detect_configured_network(deps.storage, env)?
+            .ok_or_else(|| ContractError::UnknownNetwork { chain_id: env.block.chain_id.clone() })?;
         
-        // Get saved configuration
-        let config = GLOBAL_CONFIG.load(deps.storage)?;
-        
-        // If fallback is disabled, enforce strict network matching
-        if !config.fallback_enabled && current != expected {
-            return Err(StdError::generic_err(format!(
-                "Network mismatch: expected {:?}, got {:?}", expected, current
-            )));
+        if let Some(expected) = expected {
+            if current != expected {
+                return Err(ContractError::NetworkMismatch {
+                    expected: expected.to_string(),
+                    detected: current.to_string(),
+                });
+            }
         }
         
-        Ok(())
+        Ok(current)
     }
     
     /// Automatically set the active network based on environment
     pub fn auto_configure_network(
         deps: DepsMut,
         env: &Env,
-    ) -> StdResult<NetworkEnvironment> {
-        let detected = Self::detect_network(env);
+    ) -> Result<NetworkEnvironment, ContractError> {
+        let detected = Self::verify_network(deps.as_ref(), env, None)?;
         
         // Update the config to use the detected network
-        GLOBAL_CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
-            config.active_network = detected.clone();
-            Ok(config)
-        })?;
+        set_active_network(deps.storage, deps.api, detected.clone())?;
         
         Ok(detected)
     }
@@ -65,18 +108,112 @@ impl NetworkManager {
         deps: Deps,
         contract_name: &str,
     ) -> StdResult<String> {
+        Ok(Self::resolve_contract_address(deps, contract_name)?.address)
+    }
+    
+    /// Resolve a contract address on the active network.
+    /// Only when the name is missing there is it looked up on the next network of the fallback chain.
+    pub fn resolve_contract_address(
+        deps: Deps,
+        contract_name: &str,
+    ) -> StdResult<ResolvedAddress> {
         let config = GLOBAL_CONFIG.load(deps.storage)?;
-        let active_config = config.get_active_config();
+        let chain = config.fallback_chain(&config.active_network);
+        
+        if contract_name == "registry" {
+            let (address, network) = config
+                .resolve_registry_address(&config.active_network)
+                .ok_or_else(|| StdError::not_found(format!("registry address for {}", config.active_network)))?;
+            return Ok(ResolvedAddress { name: contract_name.to_string(), address, network });
+        }
+        
+        for network in chain.iter() {
+            let registry = match config.resolve_registry_address(network) {
+                Some((registry, _)) => registry,
+                None => continue,
+            };
         
-        // In a real implementation, this would query the registry
-        // For this example, we're just returning sample addresses based on contract name
-        match contract_name {
-            "registry" => Ok(active_config.registry_address.clone()),
-            "oracle" => Ok(active_config.oracle_address.clone()),
-            "fee_collector" => Ok(active_config.fee_collector_address.clone()),
-            "axelar_gateway" => Ok(active_config.gateway_config.axelar_gateway.clone()),
-            "noble_gateway" => Ok(active_config.gateway_config.noble_gateway.clone()),
-            _ => Err(StdError::generic_err(format!("Unknown contract: {}", contract_name))),
+            // Each network's contracts live under its own namespace in the registry
+            // Only a missing name falls through; any query error, e.g. an unreachable registry, is returned
+            let response: Option<ContractAddressResponse> = deps.querier.query_wasm_smart(
+                registry,
+                &RegistryQueryMsg::TryGetContractAddress {
+                    name: contract_name.to_string(),
+                    network: Some(network.to_string()),
+                },
+            )?;
+            if let Some(response) = response {
+                return Ok(ResolvedAddress {
+                    name: contract_name.to_string(),
+                    address: response.address,
+                    network: network.clone(),
+                });
+            }
+        }
+        
+        let networks = chain.iter().map(|network| network.to_string()).collect::<Vec<_>>().join(", ");
+        Err(StdError::not_found(format!("{} on {}", contract_name, networks)))
+    }
+}
+
+/// Store the chain id mappings passed at instantiation on top of the defaults.
+/// Call before `verify_network` so a deployment can map a chain id the defaults don't know.
+pub fn instantiate_chain_id_mappings(
+    storage: &mut dyn Storage,
+    mappings: Option<&[ChainIdMapping]>,
+) -> Result<(), ContractError> {
+    for mapping in mappings.unwrap_or_default() {
+        set_chain_id_mapping(storage, mapping.pattern.clone(), Some(mapping.environment.clone()))?;
+    }
+    Ok(())
+}
+
+/// Handler for `ExecuteMsg::SetChainIdMapping`, shared by every contract.
+/// `None` removes the mapping for `pattern`.
+pub fn execute_set_chain_id_mapping(
+    deps: DepsMut,
+    info: MessageInfo,
+    pattern: ChainIdPattern,
+    environment: Option<NetworkEnvironment>,
+) -> Result<Response, ContractError> {
+    roles::assert_role(deps.storage, Role::Admin, &info.sender)?;
+    
+    let (kind, value) = match &pattern {
+        ChainIdPattern::Exact(id) => ("exact", id.clone()),
+        ChainIdPattern::Prefix(prefix) => ("prefix", prefix.clone()),
+    };
+    set_chain_id_mapping(deps.storage, pattern, environment.clone())?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "set_chain_id_mapping")
+        .add_attribute(kind, value)
+        .add_attribute("environment", environment.map(|environment| environment.to_string()).unwrap_or_else(|| "none".to_string())))
+}
+
+pub fn query_chain_id_mappings(deps: Deps, env: &Env) -> StdResult<ChainIdMappingsResponse> {
+    let mappings = load_chain_id_mappings(deps.storage)?;
+    let network = NetworkManager::match_chain_id(&mappings, &env.block.chain_id);
+    
+    Ok(ChainIdMappingsResponse { mappings, chain_id: env.block.chain_id.clone(), network })
+}
+
+/// Resolve `names` on the active network, reporting the network each address came from
+pub fn query_resolved_addresses(deps: Deps, names: Vec<String>) -> StdResult<ResolvedAddressesResponse> {
+    let config = GLOBAL_CONFIG.load(deps.storage)?;
+    let mut addresses = vec![];
+    let mut missing = vec![];
+    for name in names {
+        match NetworkManager::resolve_contract_address(deps, &name) {
+            Ok(resolved) => addresses.push(resolved),
+            Err(StdError::NotFound { .. }) => missing.push(name),
+            Err(err) => return Err(err),
         }
     }
+    
+    Ok(ResolvedAddressesResponse {
+        fallback_chain: config.fallback_chain(&config.active_network),
+        active_network: config.active_network,
+        addresses,
+        missing,
+    })
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Decimal, StdResult, Uint128};
+use cosmwasm_std::{Decimal, Isqrt, StdError, StdResult, Uint128, Uint256};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -86,17 +86,128 @@ pub fn calculate_price(collateral: Uint128, supply: Uint128) -> StdResult<Decima
     Ok(Decimal::from_ratio(collateral, supply))
 }
 
-/// Calculate ideal trade size based on deviation and market depth
+/// Reserves of an EQA/collateral pool, in base units of each asset
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolReserves {
+    pub eqa: Uint128,
+    pub collateral: Uint128,
+}
+
+/// Pricing curve of the pool EQA trades in
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolModel {
+    ConstantProduct,
+    StableSwap { amp: u64 }, // Curve-style amplification coefficient
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeDirection {
+    Mint,   // EQA trades above peg: mint at peg and sell EQA into the pool
+    Redeem, // EQA trades below peg: buy EQA from the pool and redeem it at peg
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OptimalTrade {
+    pub direction: TradeDirection,
+    pub offer_amount: Uint128,    // EQA sold for Mint, collateral spent for Redeem
+    pub return_amount: Uint128,   // What the pool pays out after its fee
+    pub expected_profit: Uint128, // In collateral, since EQA is minted and redeemed at 1:1
+}
+
+// Newton iterations allowed when solving the stableswap invariant
+const MAX_INVARIANT_ITERATIONS: u32 = 255;
+
+/// Calculate the trade that brings the pool price back to 1.0, assuming both assets use the same decimals.
+/// `fee` is the pool commission, taken from the return amount as Terraswap and Astroport pairs do.
+/// Returns None when the pool is at peg or restoring it would not be profitable.
 pub fn calculate_optimal_trade_size(
-    deviation: Decimal,
-    market_depth: Uint128,
-) -> StdResult<Uint128> {
-    // A simple model: optimal size = market_depth * deviation * 10
-    // This is a simplified version; real models would be more complex
-    let percentage = deviation.to_string().parse::<f64>().unwrap_or(0.0);
-    let trade_size = market_depth.u128() as f64 * percentage * 10.0;
-    
-    Ok(Uint128::new(trade_size as u128))
+    reserves: &PoolReserves,
+    model: &PoolModel,
+    fee: Decimal,
+) -> StdResult<Option<OptimalTrade>> {
+    if reserves.eqa.is_zero() || reserves.collateral.is_zero() {
+        return Err(StdError::generic_err("Pool has no liquidity"));
+    }
+    if fee >= Decimal::one() {
+        return Err(StdError::generic_err("Pool fee must be below 100%"));
+    }
+    
+    let eqa = Uint256::from(reserves.eqa);
+    let collateral = Uint256::from(reserves.collateral);
+    let product = eqa.checked_mul(collateral)?;
+    
+    // Both curves price the assets at 1.0 exactly where their reserves are equal
+    let target = match model {
+        PoolModel::ConstantProduct => product.isqrt(),
+        PoolModel::StableSwap { amp } => stableswap_invariant(eqa, collateral, *amp)? / Uint256::from(2u8),
+    };
+    
+    let (direction, offer_reserve, return_reserve) = if eqa < target {
+        (TradeDirection::Mint, eqa, collateral)
+    } else if collateral < target {
+        (TradeDirection::Redeem, collateral, eqa)
+    } else {
+        return Ok(None);
+    };
+    
+    let offer_amount = target - offer_reserve;
+    let gross_return = match model {
+        // Round the post-trade reserve up so the estimate never exceeds what the pool pays
+        PoolModel::ConstantProduct => {
+            let new_return_reserve = product.checked_add(target - Uint256::one())?.checked_div(target)?;
+            return_reserve.saturating_sub(new_return_reserve)
+        }
+        PoolModel::StableSwap { .. } => return_reserve.saturating_sub(target),
+    };
+    
+    let offer_amount = Uint128::try_from(offer_amount)?;
+    let gross_return = Uint128::try_from(gross_return)?;
+    let return_amount = gross_return - gross_return.mul_ceil(fee);
+    
+    if return_amount <= offer_amount {
+        return Ok(None);
+    }
+    
+    Ok(Some(OptimalTrade {
+        direction,
+        offer_amount,
+        return_amount,
+        expected_profit: return_amount - offer_amount,
+    }))
+}
+
+/// Solve the two-asset stableswap invariant D for the given reserves:
+/// 4A(x + y) + D = 4AD + D^3 / 4xy
+fn stableswap_invariant(x: Uint256, y: Uint256, amp: u64) -> StdResult<Uint256> {
+    if amp == 0 {
+        return Err(StdError::generic_err("Amplification must be greater than zero"));
+    }
+    
+    let two = Uint256::from(2u8);
+    let ann = Uint256::from(amp).checked_mul(Uint256::from(4u8))?;
+    let sum = x.checked_add(y)?;
+    let mut d = sum;
+    
+    for _ in 0..MAX_INVARIANT_ITERATIONS {
+        // D^3 / 4xy, built up one factor at a time to stay within 256 bits
+        let d_product = d.checked_mul(d)?.checked_div(x.checked_mul(two)?)?
+            .checked_mul(d)?.checked_div(y.checked_mul(two)?)?;
+        let previous = d;
+        
+        let numerator = ann.checked_mul(sum)?.checked_add(d_product.checked_mul(two)?)?.checked_mul(d)?;
+        let denominator = (ann - Uint256::one()).checked_mul(d)?
+            .checked_add(d_product.checked_mul(Uint256::from(3u8))?)?;
+        d = numerator.checked_div(denominator)?;
+        
+        let delta = if d > previous { d - previous } else { previous - d };
+        if delta <= Uint256::one() {
+            return Ok(d);
+        }
+    }
+    
+    Err(StdError::generic_err("Stableswap invariant did not converge"))
 }
 
 #[cfg(test)]
@@ -140,4 +251,59 @@ mod tests {
         let price = calculate_price(Uint128::new(100), Uint128::zero()).unwrap();
         assert_eq!(price, Decimal::zero());
     }
+
+    #[test]
+    fn test_optimal_trade_constant_product() {
+        // 900 EQA against 1100 collateral prices EQA above peg; the balanced point is isqrt(990000) = 994
+        let reserves = PoolReserves { eqa: Uint128::new(900), collateral: Uint128::new(1100) };
+        let trade = calculate_optimal_trade_size(&reserves, &PoolModel::ConstantProduct, Decimal::zero())
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade.direction, TradeDirection::Mint);
+        assert_eq!(trade.offer_amount, Uint128::new(94));
+        // 1100 - ceil(990000 / 994) = 1100 - 996
+        assert_eq!(trade.return_amount, Uint128::new(104));
+        assert_eq!(trade.expected_profit, Uint128::new(10));
+        
+        // The mirrored pool needs the opposite trade
+        let reserves = PoolReserves { eqa: Uint128::new(1100), collateral: Uint128::new(900) };
+        let trade = calculate_optimal_trade_size(&reserves, &PoolModel::ConstantProduct, Decimal::zero())
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade.direction, TradeDirection::Redeem);
+        assert_eq!(trade.offer_amount, Uint128::new(94));
+        
+        // A fee larger than the spread leaves nothing to gain
+        let trade = calculate_optimal_trade_size(&reserves, &PoolModel::ConstantProduct, Decimal::percent(10)).unwrap();
+        assert_eq!(trade, None);
+        
+        // A balanced pool is already at peg
+        let reserves = PoolReserves { eqa: Uint128::new(1000), collateral: Uint128::new(1000) };
+        let trade = calculate_optimal_trade_size(&reserves, &PoolModel::ConstantProduct, Decimal::zero()).unwrap();
+        assert_eq!(trade, None);
+    }
+    
+    #[test]
+    fn test_optimal_trade_stableswap() {
+        // Rebalancing lands on D/2 = 992_242_478 for each reserve
+        let reserves = PoolReserves { eqa: Uint128::new(500_000_000), collateral: Uint128::new(1_500_000_000) };
+        let model = PoolModel::StableSwap { amp: 10 };
+        let trade = calculate_optimal_trade_size(&reserves, &model, Decimal::permille(1))
+            .unwrap()
+            .unwrap();
+        assert_eq!(trade.direction, TradeDirection::Mint);
+        assert_eq!(trade.offer_amount, Uint128::new(492_242_478));
+        assert_eq!(trade.return_amount, Uint128::new(507_249_764));
+        assert_eq!(trade.expected_profit, Uint128::new(15_007_286));
+        
+        // The same imbalance barely moves a highly amplified pool off peg, so the fee eats the spread
+        let reserves = PoolReserves { eqa: Uint128::new(900_000_000), collateral: Uint128::new(1_100_000_000) };
+        let trade = calculate_optimal_trade_size(&reserves, &PoolModel::StableSwap { amp: 100 }, Decimal::permille(1))
+            .unwrap();
+        assert_eq!(trade, None);
+        
+        // The same reserves in a constant product pool are well off peg and still worth trading
+        let trade = calculate_optimal_trade_size(&reserves, &PoolModel::ConstantProduct, Decimal::permille(1)).unwrap();
+        assert!(trade.is_some());
+    }
 }
@@ -0,0 +1,120 @@
+use cosmwasm_std::{Addr, Api, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+
+/// Owner of a contract, plus any transfer waiting to be accepted
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Ownership {
+    pub owner: Option<Addr>, // None once ownership is renounced
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<u64>, // block time in seconds after which the proposal can no longer be accepted
+}
+
+/// Ownership changes, wrapped in each contract's `ExecuteMsg::UpdateOwnership`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OwnershipAction {
+    ProposeNewOwner { new_owner: String, expiry: Option<u64> },
+    AcceptOwnership {},
+    CancelOwnershipProposal {},
+    RenounceOwnership {},
+}
+
+pub const OWNERSHIP: Item<Ownership> = Item::new("ownership");
+
+/// Set the initial owner at instantiation
+pub fn initialize_owner(storage: &mut dyn Storage, api: &dyn Api, owner: &str) -> StdResult<Ownership> {
+    let ownership = Ownership {
+        owner: Some(api.addr_validate(owner)?),
+        pending_owner: None,
+        pending_expiry: None,
+    };
+    OWNERSHIP.save(storage, &ownership)?;
+
+    Ok(ownership)
+}
+
+pub fn get_ownership(storage: &dyn Storage) -> StdResult<Ownership> {
+    OWNERSHIP.load(storage)
+}
+
+/// Fail unless `sender` is the current owner
+pub fn assert_owner(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let ownership = OWNERSHIP.load(storage)?;
+    if ownership.owner.as_ref() != Some(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    Ok(())
+}
+
+/// Apply an ownership change on behalf of `sender`.
+/// Only the owner may propose, cancel or renounce; only the proposed owner may accept.
+pub fn update_ownership(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    action: OwnershipAction,
+) -> Result<Ownership, ContractError> {
+    let mut ownership = OWNERSHIP.load(deps.storage)?;
+
+    match action {
+        OwnershipAction::ProposeNewOwner { new_owner, expiry } => {
+            assert_owner(deps.storage, sender)?;
+            if let Some(expiry) = expiry {
+                if expiry <= env.block.time.seconds() {
+                    return Err(ContractError::OwnershipProposalExpired {});
+                }
+            }
+            // A new proposal replaces any earlier one
+            ownership.pending_owner = Some(deps.api.addr_validate(&new_owner)?);
+            ownership.pending_expiry = expiry;
+        }
+        OwnershipAction::AcceptOwnership {} => {
+            let pending_owner = ownership.pending_owner.take().ok_or(ContractError::NoPendingOwner {})?;
+            if pending_owner != *sender {
+                return Err(ContractError::Unauthorized {});
+            }
+            if let Some(expiry) = ownership.pending_expiry.take() {
+                if env.block.time.seconds() >= expiry {
+                    return Err(ContractError::OwnershipProposalExpired {});
+                }
+            }
+            ownership.owner = Some(pending_owner);
+        }
+        OwnershipAction::CancelOwnershipProposal {} => {
+            assert_owner(deps.storage, sender)?;
+            if ownership.pending_owner.is_none() {
+                return Err(ContractError::NoPendingOwner {});
+            }
+            ownership.pending_owner = None;
+            ownership.pending_expiry = None;
+        }
+        OwnershipAction::RenounceOwnership {} => {
+            // Irreversible: owner-only operations are disabled for good
+            assert_owner(deps.storage, sender)?;
+            ownership = Ownership { owner: None, pending_owner: None, pending_expiry: None };
+        }
+    }
+
+    OWNERSHIP.save(deps.storage, &ownership)?;
+    Ok(ownership)
+}
+
+/// Handler for `ExecuteMsg::UpdateOwnership`, shared by every contract
+pub fn execute_update_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: OwnershipAction,
+) -> Result<Response, ContractError> {
+    let ownership = update_ownership(deps, &env, &info.sender, action)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_ownership")
+        .add_attribute("owner", ownership.owner.map(String::from).unwrap_or_else(|| "none".to_string()))
+        .add_attribute("pending_owner", ownership.pending_owner.map(String::from).unwrap_or_else(|| "none".to_string())))
+}
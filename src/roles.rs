@@ -0,0 +1,155 @@
+use std::fmt;
+
+use cosmwasm_std::{Addr, Deps, DepsMut, Empty, MessageInfo, Order, Response, StdResult, Storage};
+use cw_storage_plus::{Bound, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+use crate::ownership;
+
+// Pagination defaults for role member listings
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+/// Permissions a contract can hand out on top of ownership.
+/// The owner holds every role implicitly.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    Guardian,
+    OracleFeeder,
+    Minter,
+    LiquidatorWhitelist,
+    FeeManager,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Guardian => "guardian",
+            Role::OracleFeeder => "oracle_feeder",
+            Role::Minter => "minter",
+            Role::LiquidatorWhitelist => "liquidator_whitelist",
+            Role::FeeManager => "fee_manager",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HasRoleResponse {
+    pub has_role: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleMembersResponse {
+    pub role: Role,
+    pub members: Vec<Addr>, // Explicit grants only; the owner is not listed
+}
+
+// (role, member)
+pub const ROLES: Map<(&str, &Addr), Empty> = Map::new("roles");
+
+pub fn has_role(storage: &dyn Storage, role: Role, addr: &Addr) -> StdResult<bool> {
+    let ownership = ownership::get_ownership(storage)?;
+    if ownership.owner.as_ref() == Some(addr) {
+        return Ok(true);
+    }
+
+    Ok(ROLES.has(storage, (role.as_str(), addr)))
+}
+
+/// Grant a role without an authorization check, for use at instantiation
+pub fn grant_role(storage: &mut dyn Storage, role: Role, addr: &Addr) -> StdResult<()> {
+    ROLES.save(storage, (role.as_str(), addr), &Empty {})
+}
+
+/// Fail unless `sender` is the owner or has been granted `role`.
+/// Every permissioned execute handler goes through this check.
+pub fn assert_role(storage: &dyn Storage, role: Role, sender: &Addr) -> Result<(), ContractError> {
+    if !has_role(storage, role, sender)? {
+        return Err(ContractError::MissingRole { role: role.to_string() });
+    }
+
+    Ok(())
+}
+
+// Admins manage every other role; only the owner manages admins
+fn assert_can_manage(storage: &dyn Storage, role: Role, sender: &Addr) -> Result<(), ContractError> {
+    match role {
+        Role::Admin => ownership::assert_owner(storage, sender),
+        _ => assert_role(storage, Role::Admin, sender),
+    }
+}
+
+pub fn execute_grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    assert_can_manage(deps.storage, role, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    grant_role(deps.storage, role, &address)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address)
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    assert_can_manage(deps.storage, role, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    if !ROLES.has(deps.storage, (role.as_str(), &address)) {
+        return Err(ContractError::MissingRole { role: role.to_string() });
+    }
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address)
+        .add_attribute("sender", info.sender))
+}
+
+pub fn query_has_role(deps: Deps, role: Role, address: String) -> StdResult<HasRoleResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    Ok(HasRoleResponse { has_role: has_role(deps.storage, role, &address)? })
+}
+
+pub fn query_role_members(
+    deps: Deps,
+    role: Role,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RoleMembersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let members = ROLES
+        .prefix(role.as_str())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(RoleMembersResponse { role, members })
+}
@@ -0,0 +1,282 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        coins, from_json, to_json_binary, Addr, BankMsg, ContractResult, CosmosMsg, Decimal, Env, OwnedDeps, SystemResult,
+        Uint128, WasmMsg, WasmQuery,
+    };
+    use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+    use arbitrage_module::{
+        ArbitrageOpportunityResponse, ExecuteMsg, InstantiateMsg, ReceiveMsg, RewardPoolResponse, QueryMsg,
+    };
+    use equilibria_smart_contracts::dex::{Asset, AssetInfo, Cw20HookMsg, PairExecuteMsg};
+    use equilibria_smart_contracts::mocks::MockPair;
+    use equilibria_smart_contracts::oracle::{PoolModel, TradeDirection};
+    use equilibria_smart_contracts::ownership::OwnershipAction;
+
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+
+    const COLLATERAL: &str = "uusdc";
+
+    fn setup(eqa_price: Decimal) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        set_price(&mut deps, eqa_price);
+
+        let msg = InstantiateMsg {
+            reward_percentage: Decimal::percent(10),
+            eqa_token: Addr::unchecked("eqa_token"),
+            oracle: Addr::unchecked("oracle"),
+            eqa_denom: "ueqa".to_string(),
+            collateral_denom: COLLATERAL.to_string(),
+            max_reward_per_block: Uint128::new(3),
+            dex_pair: Addr::unchecked("pair"),
+            pool_model: PoolModel::ConstantProduct,
+            pool_fee: Decimal::zero(),
+            chain_id_mappings: None,
+        };
+        arbitrage_module::instantiate(deps.as_mut(), localnet_env(), mock_info("admin", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::FundRewardPool {};
+        arbitrage_module::execute(deps.as_mut(), localnet_env(), mock_info("treasury", &coins(100, COLLATERAL)), msg).unwrap();
+
+        deps
+    }
+
+    fn set_price(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, price: Decimal) {
+        // 900 EQA against 1100 collateral, so EQA trades above peg on the pair
+        set_market(deps, price, (900, 1100), 0);
+    }
+
+    fn set_market(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        price: Decimal,
+        (eqa_reserve, collateral_reserve): (u128, u128),
+        eqa_balance: u128,
+    ) {
+        let pair = pool((eqa_reserve, collateral_reserve));
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "oracle" => SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&serde_json::json!({ "denom": "ueqa", "price": price, "last_updated": 0 })).unwrap(),
+            )),
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "pair" => pair.query(msg),
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "eqa_token" => SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&cw20::BalanceResponse { balance: Uint128::new(eqa_balance) }).unwrap(),
+            )),
+            other => panic!("unexpected query: {:?}", other),
+        });
+    }
+
+    fn pool((eqa_reserve, collateral_reserve): (u128, u128)) -> MockPair {
+        MockPair::new(
+            Asset { info: AssetInfo::Token { contract_addr: "eqa_token".to_string() }, amount: Uint128::new(eqa_reserve) },
+            Asset { info: AssetInfo::NativeToken { denom: COLLATERAL.to_string() }, amount: Uint128::new(collateral_reserve) },
+            Decimal::zero(),
+        )
+    }
+
+    fn reward_pool(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> RewardPoolResponse {
+        from_json(arbitrage_module::query(deps.as_ref(), localnet_env(), QueryMsg::RewardPool {}).unwrap()).unwrap()
+    }
+
+    fn redeem_msg(trader: &str, amount: u128) -> ExecuteMsg {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: trader.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&ReceiveMsg::RedeemAtPeg {}).unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_mint_above_peg_with_capped_reward() {
+        let mut deps = setup(Decimal::percent(105));
+
+        // 1000 at 5% off peg with a 10% reward share earns 5, capped to 3 for the block
+        let msg = ExecuteMsg::MintAtPeg {};
+        let res = arbitrage_module::execute(deps.as_mut(), localnet_env(), mock_info("trader", &coins(1000, COLLATERAL)), msg).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => {
+                assert_eq!(contract_addr, "eqa_token");
+                let mint: Cw20ExecuteMsg = from_json(msg).unwrap();
+                assert_eq!(mint, Cw20ExecuteMsg::Mint { recipient: "trader".to_string(), amount: Uint128::new(1000) });
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "trader".to_string(), amount: coins(3, COLLATERAL) })
+        );
+
+        // The block budget is spent, so a second mint in the same block earns nothing
+        let msg = ExecuteMsg::MintAtPeg {};
+        let res = arbitrage_module::execute(deps.as_mut(), localnet_env(), mock_info("trader", &coins(1000, COLLATERAL)), msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        let pool = reward_pool(&deps);
+        assert_eq!(pool.reward_pool, Uint128::new(97));
+        assert_eq!(pool.collateral_reserve, Uint128::new(2000));
+        assert_eq!(pool.rewards_paid_this_block, Uint128::new(3));
+
+        // Redeeming is not allowed while EQA trades above peg
+        let err = arbitrage_module::execute(deps.as_mut(), localnet_env(), mock_info("eqa_token", &[]), redeem_msg("trader", 500));
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_redeem_below_peg_pays_collateral_and_reward() {
+        let mut deps = setup(Decimal::percent(105));
+
+        let msg = ExecuteMsg::MintAtPeg {};
+        arbitrage_module::execute(deps.as_mut(), localnet_env(), mock_info("minter", &coins(1000, COLLATERAL)), msg).unwrap();
+
+        // Price drops below peg in a later block
+        set_price(&mut deps, Decimal::percent(99));
+        let mut env = localnet_env();
+        env.block.height += 1;
+
+        // Minting would push the price further from peg
+        let msg = ExecuteMsg::MintAtPeg {};
+        assert!(arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("trader", &coins(1000, COLLATERAL)), msg).is_err());
+
+        // Redemptions must come from the EQA token contract
+        assert!(arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("trader", &[]), redeem_msg("trader", 500)).is_err());
+
+        // 1000 at 1% off peg with a 10% reward share earns 1 on top of the collateral
+        let res = arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("eqa_token", &[]), redeem_msg("trader", 1000)).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                let burn: Cw20ExecuteMsg = from_json(msg).unwrap();
+                assert_eq!(burn, Cw20ExecuteMsg::Burn { amount: Uint128::new(1000) });
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "trader".to_string(), amount: coins(1001, COLLATERAL) })
+        );
+
+        // Redemptions are limited to collateral minted through the module
+        assert!(arbitrage_module::execute(deps.as_mut(), env, mock_info("eqa_token", &[]), redeem_msg("trader", 1)).is_err());
+    }
+
+    #[test]
+    fn test_opportunity_sized_from_pair_reserves() {
+        let deps = setup(Decimal::percent(105));
+
+        // Selling 94 EQA moves the pair to 994/996 and returns 104 collateral
+        let query_msg = QueryMsg::ArbitrageOpportunity {};
+        let res: ArbitrageOpportunityResponse = from_json(arbitrage_module::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+        assert!(res.opportunity_exists);
+        assert_eq!(res.direction, Some(TradeDirection::Mint));
+        assert_eq!(res.optimal_trade_size, Uint128::new(94));
+        assert_eq!(res.expected_return, Uint128::new(104));
+        assert_eq!(res.expected_profit, Uint128::new(10));
+    }
+
+    #[test]
+    fn test_arbitrage_via_pair() {
+        let mut deps = setup(Decimal::one());
+
+        // Above peg on the pair: mint to the module, then sell on the pair with proceeds going to the trader
+        let msg = ExecuteMsg::ArbitrageViaPair {};
+        let res = arbitrage_module::execute(deps.as_mut(), localnet_env(), mock_info("trader", &coins(50, COLLATERAL)), msg).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        match &res.messages[1].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => {
+                assert_eq!(contract_addr, "eqa_token");
+                match from_json(msg).unwrap() {
+                    Cw20ExecuteMsg::Send { contract, amount, msg } => {
+                        assert_eq!(contract, "pair");
+                        assert_eq!(amount, Uint128::new(50));
+                        let hook: Cw20HookMsg = from_json(msg).unwrap();
+                        assert_eq!(hook, Cw20HookMsg::Swap {
+                            belief_price: Some(Decimal::one()),
+                            max_spread: Some(Decimal::zero()),
+                            to: Some("trader".to_string()),
+                        });
+                    }
+                    other => panic!("unexpected message: {:?}", other),
+                }
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        // Executed on the pair, the sale pays the trader above peg
+        let mut pair = pool((900, 1100));
+        let paid = pair.execute(&res.messages[1].msg).unwrap();
+        assert_eq!(paid, Asset { info: AssetInfo::NativeToken { denom: COLLATERAL.to_string() }, amount: Uint128::new(58) });
+
+        // Below peg on the pair: buy EQA with the collateral, then redeem whatever arrived
+        set_market(&mut deps, Decimal::one(), (1100, 900), 7);
+        let msg = ExecuteMsg::ArbitrageViaPair {};
+        let res = arbitrage_module::execute(deps.as_mut(), localnet_env(), mock_info("trader", &coins(30, COLLATERAL)), msg).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+                assert_eq!(contract_addr, "pair");
+                assert_eq!(funds, &coins(30, COLLATERAL));
+                let swap: PairExecuteMsg = from_json(msg).unwrap();
+                assert!(matches!(swap, PairExecuteMsg::Swap { to: None, .. }));
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        let complete = ExecuteMsg::CompleteRedeem { trader: "trader".to_string(), balance_before: Uint128::new(7) };
+        match &res.messages[1].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => {
+                assert_eq!(contract_addr, localnet_env().contract.address.as_str());
+                assert_eq!(from_json::<ExecuteMsg>(msg).unwrap(), complete);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        // The swap delivers 36 EQA, which only the contract itself may redeem
+        let mut pair = pool((1100, 900));
+        let bought = pair.execute(&res.messages[0].msg).unwrap();
+        assert_eq!(bought.amount, Uint128::new(36));
+        let reserves = (pair.assets[0].amount.u128(), pair.assets[1].amount.u128());
+        set_market(&mut deps, Decimal::one(), reserves, 7 + bought.amount.u128());
+        assert!(arbitrage_module::execute(deps.as_mut(), localnet_env(), mock_info("trader", &[]), complete.clone()).is_err());
+
+        let contract = localnet_env().contract.address;
+        let res = arbitrage_module::execute(deps.as_mut(), localnet_env(), mock_info(contract.as_str(), &[]), complete).unwrap();
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "trader".to_string(), amount: coins(36, COLLATERAL) })
+        );
+        assert_eq!(reward_pool(&deps).collateral_reserve, Uint128::new(14));
+    }
+
+    #[test]
+    fn test_admin_can_hand_reward_percentage_to_governance() {
+        let mut deps = setup(Decimal::one());
+        let env = localnet_env();
+
+        // Only the admin may change the reward percentage
+        let msg = ExecuteMsg::UpdateRewardPercentage { reward_percentage: Decimal::percent(20) };
+        assert!(arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).is_err());
+
+        // Transfer ownership to governance, which accepts it
+        let msg = ExecuteMsg::UpdateOwnership(OwnershipAction::ProposeNewOwner {
+            new_owner: "governance".to_string(),
+            expiry: None,
+        });
+        arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        let msg = ExecuteMsg::UpdateOwnership(OwnershipAction::AcceptOwnership {});
+        arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("governance", &[]), msg).unwrap();
+
+        // The previous admin has lost control
+        let msg = ExecuteMsg::UpdateRewardPercentage { reward_percentage: Decimal::percent(20) };
+        assert!(arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).is_err());
+
+        // Governance is still bound by the 50% cap
+        let msg = ExecuteMsg::UpdateRewardPercentage { reward_percentage: Decimal::percent(60) };
+        assert!(arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("governance", &[]), msg).is_err());
+
+        let msg = ExecuteMsg::UpdateRewardPercentage { reward_percentage: Decimal::percent(20) };
+        let res = arbitrage_module::execute(deps.as_mut(), env, mock_info("governance", &[]), msg).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "reward_percentage" && attr.value == "0.2"));
+    }
+}
@@ -14,6 +14,7 @@ mod tests {
         // Initialize registry
         let _msg = RegistryInstantiateMsg {
             admin: None, // Default to sender
+            chain_id_mappings: None,
         };
         
         // This would call the actual registry contract in integration tests
@@ -39,6 +40,7 @@ mod tests {
             admin: None, // Default to sender
             registry_address: registry_addr.clone(),
             register_cross_chain: Some(false),
+            chain_id_mappings: None,
         };
         
         // This would call the actual contract in integration tests
@@ -1,10 +1,20 @@
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_json, to_json_vec, Addr, Decimal, Env, OwnedDeps, Storage, SystemError, SystemResult, WasmQuery};
     use equilibria_smart_contracts::config::{
-        NetworkEnvironment, storage as config_storage
+        ChainIdMapping, ChainIdPattern, NetworkEnvironment, ValidateConfigResponse, storage as config_storage
     };
-    use equilibria_smart_contracts::network::NetworkManager;
+    use equilibria_smart_contracts::error::ContractError;
+    use equilibria_smart_contracts::oracle::PoolModel;
+    use equilibria_smart_contracts::network::{self, ChainIdMappingsResponse, NetworkManager, ResolvedAddress};
+    
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
     
     #[test]
     fn test_config_initialization() {
@@ -12,7 +22,8 @@ mod tests {
         
         // Initialize configuration
         config_storage::initialize_config(
-            deps.as_mut().storage,
+            &mut deps.storage,
+            &deps.api,
             "terra1registry_mainnet".to_string(),
             "terra1registry_testnet".to_string(),
             "terra1registry_localnet".to_string(),
@@ -35,7 +46,8 @@ mod tests {
         
         // Initialize configuration
         config_storage::initialize_config(
-            deps.as_mut().storage,
+            &mut deps.storage,
+            &deps.api,
             "terra1registry_mainnet".to_string(),
             "terra1registry_testnet".to_string(),
             "terra1registry_localnet".to_string(),
@@ -48,26 +60,31 @@ mod tests {
         let active_config = config.get_active_config();
         assert_eq!(active_config.environment, NetworkEnvironment::Mainnet);
         
-        // Simulate disabling fallback and changing to testnet
-        let mut updated_config = config.clone();
-        updated_config.fallback_enabled = false;
-        updated_config.active_network = NetworkEnvironment::Testnet;
-        config_storage::GLOBAL_CONFIG.save(deps.as_mut().storage, &updated_config).unwrap();
+        // Test deployments keep their own configuration even though mainnet is configured
+        for network in [NetworkEnvironment::Testnet, NetworkEnvironment::LocalNet] {
+            config_storage::set_active_network(&mut deps.storage, &deps.api, network.clone()).unwrap();
+            let config = config_storage::GLOBAL_CONFIG.load(deps.as_ref().storage).unwrap();
+            assert_eq!(config.get_active_config().environment, network);
+            assert!(!config.fallback_chain(&network).contains(&NetworkEnvironment::Mainnet));
+        }
         
-        // Now should use testnet config since fallback is disabled
-        let config = config_storage::GLOBAL_CONFIG.load(deps.as_ref().storage).unwrap();
-        let active_config = config.get_active_config();
-        assert_eq!(active_config.environment, NetworkEnvironment::Testnet);
-        
-        // Re-enable fallback
-        let mut updated_config = config.clone();
-        updated_config.fallback_enabled = true;
-        config_storage::GLOBAL_CONFIG.save(deps.as_mut().storage, &updated_config).unwrap();
+        // Fallback only fills in a missing address, in chain order
+        let mut config = config_storage::GLOBAL_CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.fallback_chain(&NetworkEnvironment::LocalNet), vec![NetworkEnvironment::LocalNet, NetworkEnvironment::Testnet]);
+        assert_eq!(
+            config.resolve_registry_address(&NetworkEnvironment::LocalNet),
+            Some(("terra1registry_localnet".to_string(), NetworkEnvironment::LocalNet))
+        );
+        config.localnet_config.registry_address = String::new();
+        assert_eq!(
+            config.resolve_registry_address(&NetworkEnvironment::LocalNet),
+            Some(("terra1registry_testnet".to_string(), NetworkEnvironment::Testnet))
+        );
         
-        // Load again and verify we're using mainnet again due to fallback
-        let config = config_storage::GLOBAL_CONFIG.load(deps.as_ref().storage).unwrap();
-        let active_config = config.get_active_config();
-        assert_eq!(active_config.environment, NetworkEnvironment::Mainnet);
+        // ...and never with fallback disabled
+        config.fallback_enabled = false;
+        assert_eq!(config.fallback_chain(&NetworkEnvironment::LocalNet), vec![NetworkEnvironment::LocalNet]);
+        assert_eq!(config.resolve_registry_address(&NetworkEnvironment::LocalNet), None);
     }
     
     #[test]
@@ -78,16 +95,212 @@ mod tests {
         
         // Detect network
         let network = NetworkManager::detect_network(&env);
-        assert_eq!(network, NetworkEnvironment::Mainnet);
+        assert_eq!(network, Some(NetworkEnvironment::Mainnet));
         
         // Test testnet detection
         env.block.chain_id = "pisco-1".to_string();
         let network = NetworkManager::detect_network(&env);
-        assert_eq!(network, NetworkEnvironment::Testnet);
+        assert_eq!(network, Some(NetworkEnvironment::Testnet));
         
         // Test localnet detection
         env.block.chain_id = "localterra-1".to_string();
         let network = NetworkManager::detect_network(&env);
-        assert_eq!(network, NetworkEnvironment::LocalNet);
+        assert_eq!(network, Some(NetworkEnvironment::LocalNet));
+        
+        // Unmapped chains are unknown rather than localnet, including the mock chain
+        env.block.chain_id = "osmosis-1".to_string();
+        assert_eq!(NetworkManager::detect_network(&env), None);
+        assert_eq!(NetworkManager::detect_network(&mock_env()), None);
+    }
+    
+    #[test]
+    fn test_contract_addresses_come_from_registry_namespace() {
+        // A registry with the oracle deployed at different addresses per network
+        let mut registry_deps = mock_dependencies();
+        let admin = mock_info("admin", &[]);
+        registry::instantiate(registry_deps.as_mut(), localnet_env(), admin.clone(), registry::InstantiateMsg { admin: None, chain_id_mappings: None }).unwrap();
+        for (network, address) in [("mainnet", "terra1oraclemain"), ("testnet", "terra1oracletest")] {
+            let msg = registry::ExecuteMsg::SetContractAddress {
+                network: Some(network.to_string()),
+                name: "oracle".to_string(),
+                address: address.to_string(),
+                code_id: None,
+                version: None,
+                description: None,
+            };
+            registry::execute(registry_deps.as_mut(), localnet_env(), admin.clone(), msg).unwrap();
+        }
+        
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(move |query| match query {
+            // Like wasmd, only pass on that the registry query failed, not why
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "terra1registry" => {
+                let msg = cosmwasm_std::from_json(msg).unwrap();
+                let res = registry::query(registry_deps.as_ref(), localnet_env(), msg);
+                SystemResult::Ok(res.map_err(|_| "query wasm contract failed".to_string()).into())
+            }
+            other => SystemResult::Err(SystemError::UnsupportedRequest { kind: format!("{:?}", other) }),
+        });
+        config_storage::initialize_config(
+            &mut deps.storage,
+            &deps.api,
+            "terra1registry".to_string(),
+            "terra1registry".to_string(),
+            "terra1registry".to_string(),
+        ).unwrap();
+        
+        assert_eq!(NetworkManager::get_contract_address(deps.as_ref(), "registry").unwrap(), "terra1registry");
+        assert_eq!(NetworkManager::get_contract_address(deps.as_ref(), "oracle").unwrap(), "terra1oraclemain");
+        
+        // Without fallback, testnet reads its own namespace
+        let mut config = config_storage::GLOBAL_CONFIG.load(deps.as_ref().storage).unwrap();
+        config.fallback_enabled = false;
+        config.active_network = NetworkEnvironment::Testnet;
+        config_storage::GLOBAL_CONFIG.save(deps.as_mut().storage, &config).unwrap();
+        assert_eq!(NetworkManager::get_contract_address(deps.as_ref(), "oracle").unwrap(), "terra1oracletest");
+        
+        // Names missing from the namespace are errors rather than placeholders
+        config.active_network = NetworkEnvironment::LocalNet;
+        config_storage::GLOBAL_CONFIG.save(deps.as_mut().storage, &config).unwrap();
+        assert!(NetworkManager::get_contract_address(deps.as_ref(), "oracle").is_err());
+        
+        // With fallback, localnet takes the missing oracle from testnet and reports where it came from
+        config.fallback_enabled = true;
+        config_storage::GLOBAL_CONFIG.save(deps.as_mut().storage, &config).unwrap();
+        let names = vec!["registry".to_string(), "oracle".to_string(), "treasury".to_string()];
+        let res = network::query_resolved_addresses(deps.as_ref(), names).unwrap();
+        assert_eq!(res.fallback_chain, vec![NetworkEnvironment::LocalNet, NetworkEnvironment::Testnet]);
+        assert_eq!(res.addresses, vec![
+            ResolvedAddress { name: "registry".to_string(), address: "terra1registry".to_string(), network: NetworkEnvironment::LocalNet },
+            ResolvedAddress { name: "oracle".to_string(), address: "terra1oracletest".to_string(), network: NetworkEnvironment::Testnet },
+        ]);
+        assert_eq!(res.missing, vec!["treasury".to_string()]);
+        
+        // An unreachable registry is an error, not a missing name to fall back past
+        config.localnet_config.registry_address = "terra1registrydown".to_string();
+        config_storage::GLOBAL_CONFIG.save(deps.as_mut().storage, &config).unwrap();
+        let err = NetworkManager::get_contract_address(deps.as_ref(), "oracle").unwrap_err();
+        assert!(err.to_string().contains("terra1registrydown"));
+        assert!(network::query_resolved_addresses(deps.as_ref(), vec!["treasury".to_string()]).is_err());
+    }
+    
+    fn query_mappings(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env: Env) -> ChainIdMappingsResponse {
+        from_json(registry::query(deps.as_ref(), env, registry::QueryMsg::GetChainIdMappings {}).unwrap()).unwrap()
+    }
+    
+    #[test]
+    fn test_chain_id_mappings_are_stored_and_enforced() {
+        // Contracts refuse to instantiate on a chain no mapping covers
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        env.block.chain_id = "osmosis-1".to_string();
+        let err = registry::instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), registry::InstantiateMsg { admin: None, chain_id_mappings: None }).unwrap_err();
+        assert!(matches!(err, ContractError::UnknownNetwork { chain_id } if chain_id == "osmosis-1"));
+        
+        // ...unless the instantiate message maps it
+        let mappings = vec![ChainIdMapping { pattern: ChainIdPattern::Prefix("osmo".to_string()), environment: NetworkEnvironment::Testnet }];
+        let msg = registry::InstantiateMsg { admin: None, chain_id_mappings: Some(mappings) };
+        registry::instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        assert_eq!(query_mappings(&deps, env.clone()).network, Some(NetworkEnvironment::Testnet));
+        let set = |pattern, environment| registry::ExecuteMsg::SetChainIdMapping { pattern, environment };
+        
+        // Only admins edit the mappings
+        let msg = set(ChainIdPattern::Exact("osmosis-1".to_string()), Some(NetworkEnvironment::Mainnet));
+        let err = registry::execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { .. }));
+        registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        
+        // Exact matches win over prefixes
+        assert_eq!(query_mappings(&deps, env.clone()).network, Some(NetworkEnvironment::Mainnet));
+        let mut test_env = mock_env();
+        test_env.block.chain_id = "osmo-test-5".to_string();
+        assert_eq!(query_mappings(&deps, test_env).network, Some(NetworkEnvironment::Testnet));
+        
+        // Removing the exact mapping falls back to the prefix
+        let msg = set(ChainIdPattern::Exact("osmosis-1".to_string()), None);
+        registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        let res = query_mappings(&deps, env.clone());
+        assert_eq!(res.network, Some(NetworkEnvironment::Testnet));
+        assert!(!res.mappings.iter().any(|mapping| mapping.pattern == ChainIdPattern::Exact("osmosis-1".to_string())));
+        
+        // An expected network must match the detected one
+        let err = NetworkManager::verify_network(deps.as_ref(), &env, Some(NetworkEnvironment::Mainnet)).unwrap_err();
+        assert!(matches!(err, ContractError::NetworkMismatch { .. }));
+        assert_eq!(NetworkManager::verify_network(deps.as_ref(), &env, Some(NetworkEnvironment::Testnet)).unwrap(), NetworkEnvironment::Testnet);
+    }
+    
+    #[test]
+    fn test_config_writes_are_validated() {
+        let mut deps = mock_dependencies();
+        
+        // Malformed registry addresses never reach storage
+        let err = config_storage::initialize_config(
+            &mut deps.storage,
+            &deps.api,
+            "terra1registry_mainnet".to_string(),
+            "Terra1Registry".to_string(),
+            "".to_string(),
+        ).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfig { field, .. } if field == "testnet_config.registry_address"));
+        assert!(config_storage::GLOBAL_CONFIG.may_load(&deps.storage).unwrap().is_none());
+        
+        // Nor does an active network left without any registry
+        config_storage::initialize_config(
+            &mut deps.storage,
+            &deps.api,
+            "terra1registry_mainnet".to_string(),
+            "".to_string(),
+            "".to_string(),
+        ).unwrap();
+        let err = config_storage::set_active_network(&mut deps.storage, &deps.api, NetworkEnvironment::Testnet).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfig { field, .. } if field == "active_network"));
+        
+        let err = config_storage::set_chain_id_mapping(&mut deps.storage, ChainIdPattern::Prefix("".to_string()), None).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfig { .. }));
+    }
+    
+    #[test]
+    fn test_validate_config_reports_every_invalid_field() {
+        let mut deps = mock_dependencies();
+        let msg = arbitrage_module::InstantiateMsg {
+            reward_percentage: Decimal::percent(10),
+            eqa_token: Addr::unchecked("eqa_token"),
+            oracle: Addr::unchecked("oracle"),
+            eqa_denom: "ueqa".to_string(),
+            collateral_denom: "uusdc".to_string(),
+            max_reward_per_block: 3u128.into(),
+            dex_pair: Addr::unchecked("pair"),
+            pool_model: PoolModel::ConstantProduct,
+            pool_fee: Decimal::zero(),
+            chain_id_mappings: None,
+        };
+        
+        // Out-of-bounds values are rejected at instantiation and on update
+        let mut bad_msg = msg.clone();
+        bad_msg.pool_fee = Decimal::one();
+        let err = arbitrage_module::instantiate(deps.as_mut(), localnet_env(), mock_info("owner", &[]), bad_msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfig { field, .. } if field == "pool_fee"));
+        
+        arbitrage_module::instantiate(deps.as_mut(), localnet_env(), mock_info("owner", &[]), msg).unwrap();
+        let update = arbitrage_module::ExecuteMsg::UpdateRewardPercentage { reward_percentage: Decimal::percent(51) };
+        let err = arbitrage_module::execute(deps.as_mut(), localnet_env(), mock_info("owner", &[]), update).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfig { field, .. } if field == "reward_percentage"));
+        
+        let query = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| -> ValidateConfigResponse {
+            let msg = arbitrage_module::QueryMsg::ValidateConfig {};
+            from_json(arbitrage_module::query(deps.as_ref(), localnet_env(), msg).unwrap()).unwrap()
+        };
+        assert!(query(&deps).valid);
+        
+        // A config written before validation existed has all of its problems reported at once
+        let mut config: serde_json::Value = from_json(deps.storage.get(b"config").unwrap()).unwrap();
+        config["eqa_denom"] = serde_json::json!("");
+        config["pool_fee"] = serde_json::json!("1.5");
+        deps.storage.set(b"config", &to_json_vec(&config).unwrap());
+        
+        let res = query(&deps);
+        assert!(!res.valid);
+        let fields = res.invalid_fields.iter().map(|invalid| invalid.field.as_str()).collect::<Vec<_>>();
+        assert_eq!(fields, vec!["eqa_denom", "pool_fee"]);
     }
 }
@@ -33,6 +33,7 @@ mod tests {
             admin: None, // Default to sender
             registry_address: "mock_registry".to_string(),
             register_cross_chain: Some(false),
+            chain_id_mappings: None,
         };
         
         // Set up initial collateral state for testing
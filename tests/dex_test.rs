@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{
+        coins, from_json, Addr, CosmosMsg, Decimal, QuerierWrapper, SystemResult, Uint128, WasmMsg, WasmQuery,
+    };
+    use cw20::Cw20ExecuteMsg;
+    use equilibria_smart_contracts::dex::{Asset, AssetInfo, Cw20HookMsg, DexPair, PairExecuteMsg};
+    use equilibria_smart_contracts::mocks::MockPair;
+
+    fn pair() -> DexPair {
+        DexPair {
+            contract_addr: Addr::unchecked("pair"),
+            eqa: AssetInfo::Token { contract_addr: "eqa_token".to_string() },
+            collateral: AssetInfo::NativeToken { denom: "uusdc".to_string() },
+        }
+    }
+
+    fn mock_pair(eqa: u128, collateral: u128) -> MockPair {
+        let pair = pair();
+        MockPair::new(
+            Asset { info: pair.eqa, amount: Uint128::new(eqa) },
+            Asset { info: pair.collateral, amount: Uint128::new(collateral) },
+            Decimal::permille(3),
+        )
+    }
+
+    #[test]
+    fn test_pair_queries() {
+        let mut deps = mock_dependencies();
+        let mock = mock_pair(1_000_000, 1_050_000);
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "pair" => mock.query(msg),
+            other => SystemResult::Err(cosmwasm_std::SystemError::UnsupportedRequest { kind: format!("{:?}", other) }),
+        });
+        let querier = QuerierWrapper::new(&deps.querier);
+        let pair = pair();
+
+        let reserves = pair.query_reserves(&querier).unwrap();
+        assert_eq!(reserves.eqa, Uint128::new(1_000_000));
+        assert_eq!(reserves.collateral, Uint128::new(1_050_000));
+        assert_eq!(pair.query_price(&querier).unwrap(), Decimal::percent(105));
+
+        // Selling 10_000 EQA: 1_050_000 - floor(1_050_000 * 1_000_000 / 1_010_000) = 10_397, less the 0.3% commission
+        let sim = pair.simulate(&querier, Asset { info: pair.eqa.clone(), amount: Uint128::new(10_000) }).unwrap();
+        assert_eq!(sim.commission_amount, Uint128::new(31));
+        assert_eq!(sim.return_amount, Uint128::new(10_366));
+
+        // Asking for that return back costs about the same offer
+        let reverse = pair
+            .reverse_simulate(&querier, Asset { info: pair.collateral.clone(), amount: sim.return_amount })
+            .unwrap();
+        assert!(reverse.offer_amount >= Uint128::new(9_999) && reverse.offer_amount <= Uint128::new(10_001));
+
+        // A pair that trades something else is rejected
+        let other = DexPair { collateral: AssetInfo::NativeToken { denom: "uluna".to_string() }, ..pair };
+        assert!(other.query_reserves(&querier).is_err());
+    }
+
+    #[test]
+    fn test_swap_messages() {
+        let pair = pair();
+
+        // Native offers are attached as funds
+        let offer = Asset { info: pair.collateral.clone(), amount: Uint128::new(500) };
+        match pair.swap_msg(offer.clone(), Some(Decimal::one()), Some(Decimal::zero()), None).unwrap() {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+                assert_eq!(contract_addr, "pair");
+                assert_eq!(funds, coins(500, "uusdc"));
+                let swap: PairExecuteMsg = from_json(msg).unwrap();
+                assert_eq!(swap, PairExecuteMsg::Swap {
+                    offer_asset: offer,
+                    belief_price: Some(Decimal::one()),
+                    max_spread: Some(Decimal::zero()),
+                    to: None,
+                });
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        // CW20 offers are sent to the pair through the token
+        let offer = Asset { info: pair.eqa.clone(), amount: Uint128::new(500) };
+        match pair.swap_msg(offer, None, None, Some("trader".to_string())).unwrap() {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+                assert_eq!(contract_addr, "eqa_token");
+                assert!(funds.is_empty());
+                match from_json(msg).unwrap() {
+                    Cw20ExecuteMsg::Send { contract, amount, msg } => {
+                        assert_eq!(contract, "pair");
+                        assert_eq!(amount, Uint128::new(500));
+                        let hook: Cw20HookMsg = from_json(msg).unwrap();
+                        assert_eq!(hook, Cw20HookMsg::Swap { belief_price: None, max_spread: None, to: Some("trader".to_string()) });
+                    }
+                    other => panic!("unexpected message: {:?}", other),
+                }
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_swaps_move_the_pool() {
+        let pair = pair();
+        let mut mock = mock_pair(1_000_000, 1_050_000);
+
+        // Selling EQA through the token pays out the simulated return and moves both reserves
+        let msg = pair.swap_msg(Asset { info: pair.eqa.clone(), amount: Uint128::new(10_000) }, None, None, None).unwrap();
+        let paid = mock.execute(&msg).unwrap();
+        assert_eq!(paid, Asset { info: pair.collateral.clone(), amount: Uint128::new(10_366) });
+        assert_eq!(mock.assets[0].amount, Uint128::new(1_010_000));
+        assert_eq!(mock.assets[1].amount, Uint128::new(1_039_634));
+
+        // Buying at the old peg now exceeds a zero spread, and leaves the pool as it was
+        let offer = Asset { info: pair.collateral.clone(), amount: Uint128::new(500) };
+        let msg = pair.swap_msg(offer.clone(), Some(Decimal::one()), Some(Decimal::zero()), None).unwrap();
+        assert!(mock.execute(&msg).is_err());
+        assert_eq!(mock.assets[1].amount, Uint128::new(1_039_634));
+
+        // Native offers must come with the funds
+        let unfunded = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pair".to_string(),
+            msg: cosmwasm_std::to_json_binary(&PairExecuteMsg::Swap { offer_asset: offer.clone(), belief_price: None, max_spread: None, to: None }).unwrap(),
+            funds: vec![],
+        });
+        assert!(mock.execute(&unfunded).is_err());
+
+        let msg = pair.swap_msg(offer, None, None, None).unwrap();
+        let paid = mock.execute(&msg).unwrap();
+        assert_eq!(paid.info, pair.eqa);
+        assert!(paid.amount < Uint128::new(500));
+        assert_eq!(mock.assets[0].amount, Uint128::new(1_010_000) - paid.amount);
+        assert_eq!(mock.assets[1].amount, Uint128::new(1_040_134));
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_json, Addr, Decimal, Env, OwnedDeps, SystemResult, Timestamp, Uint128, WasmQuery};
+    use eqa_oracle::{ExecuteMsg, InstantiateMsg, PriceResponse, QueryMsg};
+    use equilibria_smart_contracts::dex::{Asset, AssetInfo, DexPair};
+    use equilibria_smart_contracts::error::ContractError;
+    use equilibria_smart_contracts::mocks::MockPair;
+
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+
+    const START: u64 = 1_700_000_000;
+
+    fn pair() -> DexPair {
+        DexPair {
+            contract_addr: Addr::unchecked("pair"),
+            eqa: AssetInfo::Token { contract_addr: "eqa_token".to_string() },
+            collateral: AssetInfo::NativeToken { denom: "uusd".to_string() },
+        }
+    }
+
+    // An oracle pricing `ueqa` from a 1:1 pool, which the returned handle can trade against
+    fn setup() -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, Rc<RefCell<MockPair>>) {
+        let mut deps = mock_dependencies();
+        let pool = Rc::new(RefCell::new(MockPair::new(
+            Asset { info: pair().eqa, amount: Uint128::new(1_000_000) },
+            Asset { info: pair().collateral, amount: Uint128::new(1_000_000) },
+            Decimal::zero(),
+        )));
+        let querier_pool = pool.clone();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "pair" => querier_pool.borrow().query(msg),
+            other => SystemResult::Err(cosmwasm_std::SystemError::UnsupportedRequest { kind: format!("{:?}", other) }),
+        });
+
+        let msg = InstantiateMsg { admin: Addr::unchecked("admin"), price_timeout: 600, chain_id_mappings: None };
+        eqa_oracle::instantiate(deps.as_mut(), env_at(START), mock_info("admin", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::RegisterAsset { denom: "ueqa".to_string(), symbol: "EQA".to_string() };
+        eqa_oracle::execute(deps.as_mut(), env_at(START), mock_info("admin", &[]), msg).unwrap();
+        let msg = ExecuteMsg::SetPricePair { denom: "ueqa".to_string(), pair: Some(pair()) };
+        eqa_oracle::execute(deps.as_mut(), env_at(START), mock_info("admin", &[]), msg).unwrap();
+
+        (deps, pool)
+    }
+
+    fn env_at(seconds: u64) -> Env {
+        let mut env = localnet_env();
+        env.block.time = Timestamp::from_seconds(seconds);
+        env
+    }
+
+    fn record(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, seconds: u64) {
+        let msg = ExecuteMsg::RecordPrice { denom: "ueqa".to_string() };
+        eqa_oracle::execute(deps.as_mut(), env_at(seconds), mock_info("admin", &[]), msg).unwrap();
+    }
+
+    fn price(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, seconds: u64) -> Result<PriceResponse, ContractError> {
+        let msg = QueryMsg::GetPrice { denom: "ueqa".to_string() };
+        eqa_oracle::query(deps.as_ref(), env_at(seconds), msg).map(|bin| from_json(bin).unwrap())
+    }
+
+    // Sell EQA into the pool, moving its spot price to 0.25
+    fn dump_eqa(pool: &Rc<RefCell<MockPair>>) {
+        let offer = Asset { info: pair().eqa, amount: Uint128::new(1_000_000) };
+        pool.borrow_mut().swap(&offer, None, None).unwrap();
+    }
+
+    #[test]
+    fn test_set_price_pair() {
+        let (mut deps, _pool) = setup();
+        let env = env_at(START);
+
+        // Only oracle feeders may point a denom at a pair
+        let msg = ExecuteMsg::SetPricePair { denom: "ueqa".to_string(), pair: Some(pair()) };
+        let err = eqa_oracle::execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { .. }));
+
+        // Only registered assets can be priced
+        let msg = ExecuteMsg::SetPricePair { denom: "uatom".to_string(), pair: Some(pair()) };
+        assert!(eqa_oracle::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).is_err());
+
+        // Samples are only taken for denoms priced from a pair
+        let msg = ExecuteMsg::RecordPrice { denom: "uluna".to_string() };
+        let err = eqa_oracle::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::NotConfigured { .. }));
+
+        // Removing the pair goes back to the Terra oracle
+        let msg = ExecuteMsg::SetPricePair { denom: "ueqa".to_string(), pair: None };
+        eqa_oracle::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        assert_eq!(price(&deps, START).unwrap().price, Decimal::one());
+
+        // A frozen oracle serves no prices
+        eqa_oracle::execute(deps.as_mut(), env, mock_info("admin", &[]), ExecuteMsg::SetFrozen { frozen: true }).unwrap();
+        assert!(matches!(price(&deps, START).unwrap_err(), ContractError::Paused { .. }));
+    }
+
+    #[test]
+    fn test_pair_price_is_time_weighted() {
+        let (mut deps, pool) = setup();
+
+        // Nothing is served before a sample has aged
+        assert!(matches!(price(&deps, START).unwrap_err(), ContractError::NotConfigured { .. }));
+        record(&mut deps, START);
+        assert!(matches!(price(&deps, START).unwrap_err(), ContractError::InsufficientObservations { denom } if denom == "ueqa"));
+        assert_eq!(price(&deps, START + 60).unwrap(), PriceResponse {
+            denom: "ueqa".to_string(),
+            price: Decimal::one(),
+            last_updated: START,
+        });
+
+        // A pool moved within a block can't be sampled again in that block, nor move the price in it
+        record(&mut deps, START + 300);
+        dump_eqa(&pool);
+        record(&mut deps, START + 300);
+        assert_eq!(price(&deps, START + 300).unwrap().price, Decimal::one());
+
+        // The next sample sees the move, but only for the time it has held
+        record(&mut deps, START + 600);
+        assert_eq!(price(&deps, START + 600).unwrap().price, Decimal::one());
+        assert_eq!(price(&deps, START + 900).unwrap().price, Decimal::percent(75));
+
+        // Once the window has passed, only the moved price remains
+        assert!(matches!(
+            price(&deps, START + 600 + 30 * 60).unwrap_err(),
+            ContractError::StalePrice { denom, last_updated, max_age } if denom == "ueqa" && last_updated == START + 600 && max_age == 600
+        ));
+        record(&mut deps, START + 2_400);
+        assert_eq!(price(&deps, START + 2_460).unwrap().price, Decimal::percent(25));
+    }
+
+    #[test]
+    fn test_price_timeout_bounds() {
+        let (mut deps, _pool) = setup();
+        let update = |new_timeout: u64| ExecuteMsg::UpdatePriceTimeout { new_timeout };
+
+        // The oracle accepts the same bounds governance enforces
+        let err = eqa_oracle::execute(deps.as_mut(), env_at(START), mock_info("admin", &[]), update(30)).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfig { field, .. } if field == "price_timeout"));
+        eqa_oracle::execute(deps.as_mut(), env_at(START), mock_info("admin", &[]), update(60)).unwrap();
+        assert!(eqa_oracle::execute(deps.as_mut(), env_at(START), mock_info("admin", &[]), update(86_401)).is_err());
+    }
+
+    #[test]
+    fn test_new_pair_discards_samples() {
+        let (mut deps, _pool) = setup();
+        record(&mut deps, START);
+        assert!(price(&deps, START + 60).is_ok());
+
+        let msg = ExecuteMsg::SetPricePair { denom: "ueqa".to_string(), pair: Some(pair()) };
+        eqa_oracle::execute(deps.as_mut(), env_at(START + 60), mock_info("admin", &[]), msg).unwrap();
+        assert!(matches!(price(&deps, START + 120).unwrap_err(), ContractError::NotConfigured { .. }));
+    }
+}
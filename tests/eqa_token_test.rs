@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_json, Addr, Decimal, Deps, Env, Uint128};
+    use eqa_token::{BalanceResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, TokenInfoResponse};
+    use equilibria_smart_contracts::config::{ChainIdMapping, ChainIdPattern, NetworkEnvironment, ValidateConfigResponse};
+    use equilibria_smart_contracts::error::ContractError;
+    use equilibria_smart_contracts::ownership::Ownership;
+    use equilibria_smart_contracts::roles::Role;
+    use equilibria_smart_contracts::state::{TokenState, TOKEN_STATE};
+
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+
+    fn token_msg(symbol: &str) -> InstantiateMsg {
+        InstantiateMsg {
+            name: "Equilibria".to_string(),
+            symbol: symbol.to_string(),
+            decimals: 6,
+            chain_id_mappings: None,
+        }
+    }
+
+    fn balance(deps: Deps, address: &str) -> Uint128 {
+        let msg = QueryMsg::Balance { address: address.to_string() };
+        let res: BalanceResponse = from_json(eqa_token::query(deps, localnet_env(), msg).unwrap()).unwrap();
+        res.balance
+    }
+
+    #[test]
+    fn test_token_instantiation_is_validated() {
+        let mut deps = mock_dependencies();
+
+        // The mock chain is unknown until the instantiate message maps it
+        let err = eqa_token::instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), token_msg("EQA")).unwrap_err();
+        assert!(matches!(err, ContractError::UnknownNetwork { .. }));
+
+        let err = eqa_token::instantiate(deps.as_mut(), localnet_env(), mock_info("owner", &[]), token_msg("EQ4")).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfig { field, .. } if field == "symbol"));
+
+        let mut msg = token_msg("EQA");
+        msg.chain_id_mappings = Some(vec![ChainIdMapping {
+            pattern: ChainIdPattern::Exact(mock_env().block.chain_id),
+            environment: NetworkEnvironment::LocalNet,
+        }]);
+        eqa_token::instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        let res: TokenInfoResponse = from_json(eqa_token::query(deps.as_ref(), mock_env(), QueryMsg::TokenInfo {}).unwrap()).unwrap();
+        assert_eq!((res.symbol.as_str(), res.decimals, res.total_supply), ("EQA", 6, Uint128::zero()));
+        let res: ValidateConfigResponse = from_json(eqa_token::query(deps.as_ref(), mock_env(), QueryMsg::ValidateConfig {}).unwrap()).unwrap();
+        assert!(res.valid);
+    }
+
+    #[test]
+    fn test_minting_requires_the_minter_role() {
+        let mut deps = mock_dependencies();
+        let env = localnet_env();
+        eqa_token::instantiate(deps.as_mut(), env.clone(), mock_info("owner", &[]), token_msg("EQA")).unwrap();
+        let mint = |amount: u128| ExecuteMsg::Mint { amount: Uint128::new(amount), market_price: Decimal::one() };
+
+        let err = eqa_token::execute(deps.as_mut(), env.clone(), mock_info("minter", &[]), mint(1_000)).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "minter"));
+
+        let grant = ExecuteMsg::GrantRole { role: Role::Minter, address: "minter".to_string() };
+        eqa_token::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), grant).unwrap();
+
+        // At peg the fee is the 0.1% base fee
+        eqa_token::execute(deps.as_mut(), env.clone(), mock_info("minter", &[]), mint(1_000)).unwrap();
+        assert_eq!(balance(deps.as_ref(), "minter"), Uint128::new(999));
+
+        let redeem = |amount: u128| ExecuteMsg::Redeem { amount: Uint128::new(amount), market_price: Decimal::one() };
+        eqa_token::execute(deps.as_mut(), env.clone(), mock_info("minter", &[]), redeem(500)).unwrap();
+        assert_eq!(balance(deps.as_ref(), "minter"), Uint128::new(499));
+        let err = eqa_token::execute(deps.as_mut(), env.clone(), mock_info("minter", &[]), redeem(500)).unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientFunds { required, available }
+            if required == Uint128::new(500) && available == Uint128::new(499)));
+
+        let res: TokenInfoResponse = from_json(eqa_token::query(deps.as_ref(), env, QueryMsg::TokenInfo {}).unwrap()).unwrap();
+        assert_eq!(res.total_supply, Uint128::new(499));
+    }
+
+    #[test]
+    fn test_migrate_legacy_token_owner() {
+        let mut deps = mock_dependencies();
+        let env = localnet_env();
+
+        // The unversioned token kept its owner in the token state
+        TOKEN_STATE.save(&mut deps.storage, &TokenState {
+            total_supply: Uint128::new(10),
+            owner: Addr::unchecked("old_owner"),
+            name: "Equilibria".to_string(),
+            symbol: "EQA".to_string(),
+            decimals: 6,
+        }).unwrap();
+
+        eqa_token::migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        let res: Ownership = from_json(eqa_token::query(deps.as_ref(), env.clone(), QueryMsg::GetOwnership {}).unwrap()).unwrap();
+        assert_eq!(res.owner, Some(Addr::unchecked("old_owner")));
+
+        let grant = ExecuteMsg::GrantRole { role: Role::Minter, address: "minter".to_string() };
+        eqa_token::execute(deps.as_mut(), env, mock_info("old_owner", &[]), grant).unwrap();
+    }
+}
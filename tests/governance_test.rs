@@ -0,0 +1,579 @@
+#[cfg(test)]
+mod tests {
+    use arbitrage_module::{
+        ConfigResponse as ArbitrageConfigResponse, ExecuteMsg as ArbitrageExecuteMsg,
+        InstantiateMsg as ArbitrageInstantiateMsg, QueryMsg as ArbitrageQueryMsg,
+    };
+    use collateral_manager::ExecuteMsg as CollateralManagerMsg;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{coin, coins, from_json, Addr, BankMsg, CosmosMsg, Decimal, Env, OwnedDeps, Uint128, WasmMsg};
+    use equilibria_smart_contracts::error::ContractError;
+    use equilibria_smart_contracts::oracle::PoolModel;
+    use equilibria_smart_contracts::ownership::{Ownership, OwnershipAction};
+    use governance::{
+        DelegatorListResponse, ExecuteMsg, GuardianAction, GuardianActionListResponse, GuardianActionStatus,
+        GuardianCouncil, InstantiateMsg, QueryMsg, ParameterChange, ProposalResponse, ProposalStatus, VoteListResponse,
+        VoteOption, VoteResponse, VotingPowerResponse,
+    };
+
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+
+    const VOTING_PERIOD: u64 = 1000;
+    const TIMELOCK_PERIOD: u64 = 500;
+    const EXECUTION_PERIOD: u64 = 2000;
+
+    fn instantiate() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            voting_period: VOTING_PERIOD,
+            quorum_percentage: 3,
+            threshold_percentage: 50,
+            veto_threshold_percentage: 33,
+            proposal_deposit: Some(coin(1000, "uluna")),
+            timelock_period: TIMELOCK_PERIOD,
+            execution_period: EXECUTION_PERIOD,
+            guardian_council: Some(GuardianCouncil {
+                members: vec![Addr::unchecked("guardian1"), Addr::unchecked("guardian2"), Addr::unchecked("guardian3")],
+                threshold: 2,
+            }),
+            chain_id_mappings: None,
+        };
+        governance::instantiate(deps.as_mut(), localnet_env(), mock_info("admin", &[]), msg).unwrap();
+
+        deps
+    }
+
+    fn propose(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, env: Env) {
+        let msg = ExecuteMsg::ProposeUpgrade {
+            title: "Upgrade".to_string(),
+            description: "Upgrade the token contract".to_string(),
+            contract_addr: Addr::unchecked("eqa_token"),
+            new_code_id: 2,
+            migrate_msg: None,
+        };
+        governance::execute(deps.as_mut(), env, mock_info("proposer", &coins(1000, "uluna")), msg).unwrap();
+    }
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = instantiate();
+        propose(&mut deps, localnet_env());
+        deps
+    }
+
+    fn env_at(offset: u64) -> Env {
+        let mut env = localnet_env();
+        env.block.time = env.block.time.plus_seconds(offset);
+        env
+    }
+
+    fn vote(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, voter: &str, vote: VoteOption) {
+        let msg = ExecuteMsg::Vote { proposal_id: 1, vote };
+        governance::execute(deps.as_mut(), localnet_env(), mock_info(voter, &[]), msg).unwrap();
+    }
+
+    // Vote a proposal created at `start` through and execute it once its timelock has passed
+    fn pass_and_execute(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, proposal_id: u64, start: u64) -> Vec<CosmosMsg> {
+        for voter in ["voter1", "voter2", "voter3"] {
+            let msg = ExecuteMsg::Vote { proposal_id, vote: VoteOption::Yes };
+            governance::execute(deps.as_mut(), env_at(start), mock_info(voter, &[]), msg).unwrap();
+        }
+
+        let msg = ExecuteMsg::TallyProposal { proposal_id };
+        governance::execute(deps.as_mut(), env_at(start + VOTING_PERIOD + 1), mock_info("anyone", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::ExecuteProposal { proposal_id };
+        let env = env_at(start + VOTING_PERIOD + TIMELOCK_PERIOD);
+        let res = governance::execute(deps.as_mut(), env, mock_info("anyone", &[]), msg).unwrap();
+        res.messages.into_iter().map(|sub_msg| sub_msg.msg).collect()
+    }
+
+    fn proposal(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> ProposalResponse {
+        let query_msg = QueryMsg::GetProposal { id: 1 };
+        from_json(governance::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_revote_replaces_previous_vote() {
+        let mut deps = setup();
+
+        vote(&mut deps, "voter1", VoteOption::Yes);
+        vote(&mut deps, "voter1", VoteOption::Yes);
+
+        // Voting the same way twice must not inflate the tally
+        let res = proposal(&deps);
+        assert_eq!(res.yes_votes, Uint128::new(1));
+
+        // Changing the vote moves the power to the new option
+        vote(&mut deps, "voter1", VoteOption::No);
+        let res = proposal(&deps);
+        assert_eq!(res.yes_votes, Uint128::zero());
+        assert_eq!(res.no_votes, Uint128::new(1));
+        assert_eq!(res.abstain_votes, Uint128::zero());
+    }
+
+    #[test]
+    fn test_vote_queries() {
+        let mut deps = setup();
+
+        vote(&mut deps, "voter1", VoteOption::Yes);
+        vote(&mut deps, "voter2", VoteOption::Abstain);
+        vote(&mut deps, "voter3", VoteOption::No);
+
+        // Single vote lookup
+        let query_msg = QueryMsg::GetVote { proposal_id: 1, voter: "voter2".to_string() };
+        let res: VoteResponse = from_json(governance::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.voter, Addr::unchecked("voter2"));
+        assert_eq!(res.vote, VoteOption::Abstain);
+
+        // Missing vote is an error
+        let query_msg = QueryMsg::GetVote { proposal_id: 1, voter: "voter4".to_string() };
+        assert!(governance::query(deps.as_ref(), localnet_env(), query_msg).is_err());
+
+        // Paginated listing
+        let query_msg = QueryMsg::ListVotes { proposal_id: 1, start_after: None, limit: Some(2) };
+        let res: VoteListResponse = from_json(governance::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.votes.len(), 2);
+        assert_eq!(res.votes[0].voter, Addr::unchecked("voter1"));
+        assert_eq!(res.votes[1].voter, Addr::unchecked("voter2"));
+
+        let query_msg = QueryMsg::ListVotes {
+            proposal_id: 1,
+            start_after: Some("voter2".to_string()),
+            limit: Some(2),
+        };
+        let res: VoteListResponse = from_json(governance::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.votes.len(), 1);
+        assert_eq!(res.votes[0].voter, Addr::unchecked("voter3"));
+    }
+
+    #[test]
+    fn test_proposal_requires_deposit() {
+        let mut deps = setup();
+
+        let msg = ExecuteMsg::ProposeUpgrade {
+            title: "Cheap".to_string(),
+            description: "Not enough deposit".to_string(),
+            contract_addr: Addr::unchecked("eqa_token"),
+            new_code_id: 3,
+            migrate_msg: None,
+        };
+        let res = governance::execute(deps.as_mut(), localnet_env(), mock_info("proposer", &coins(999, "uluna")), msg);
+        assert!(res.is_err());
+
+        let res = proposal(&deps);
+        assert_eq!(res.deposit, coins(1000, "uluna"));
+    }
+
+    #[test]
+    fn test_passed_proposal_waits_for_timelock() {
+        let mut deps = setup();
+
+        vote(&mut deps, "voter1", VoteOption::Yes);
+        vote(&mut deps, "voter2", VoteOption::Yes);
+        vote(&mut deps, "voter3", VoteOption::No);
+
+        // Cannot tally while voting is open
+        let msg = ExecuteMsg::TallyProposal { proposal_id: 1 };
+        let err = governance::execute(deps.as_mut(), localnet_env(), mock_info("anyone", &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::VotingNotEnded { proposal_id: 1, .. }));
+
+        // Tallying refunds the deposit and starts the timelock
+        let res = governance::execute(deps.as_mut(), env_at(VOTING_PERIOD + 1), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "proposer".to_string(),
+            amount: coins(1000, "uluna"),
+        }));
+        let res = proposal(&deps);
+        assert_eq!(res.status, ProposalStatus::Passed);
+
+        // Execution is blocked until the timelock elapses, then anyone may execute
+        let msg = ExecuteMsg::ExecuteProposal { proposal_id: 1 };
+        let early = env_at(VOTING_PERIOD + TIMELOCK_PERIOD - 1);
+        let executable_at = env_at(VOTING_PERIOD + TIMELOCK_PERIOD).block.time.seconds();
+        let err = governance::execute(deps.as_mut(), early, mock_info("anyone", &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::ProposalTimelocked { proposal_id: 1, executable_at: at } if at == executable_at));
+
+        let res = governance::execute(deps.as_mut(), env_at(VOTING_PERIOD + TIMELOCK_PERIOD), mock_info("anyone", &[]), msg).unwrap();
+        assert!(matches!(
+            &res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, new_code_id: 2, .. }) if contract_addr == "eqa_token"
+        ));
+        assert_eq!(proposal(&deps).status, ProposalStatus::Executed);
+    }
+
+    #[test]
+    fn test_vetoed_proposal_burns_deposit() {
+        let mut deps = setup();
+
+        vote(&mut deps, "voter1", VoteOption::Yes);
+        vote(&mut deps, "voter2", VoteOption::Yes);
+        vote(&mut deps, "voter3", VoteOption::NoWithVeto);
+        vote(&mut deps, "voter4", VoteOption::NoWithVeto);
+
+        let msg = ExecuteMsg::TallyProposal { proposal_id: 1 };
+        let res = governance::execute(deps.as_mut(), env_at(VOTING_PERIOD + 1), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Burn { amount: coins(1000, "uluna") }));
+        assert_eq!(proposal(&deps).status, ProposalStatus::Vetoed);
+    }
+
+    #[test]
+    fn test_cannot_cancel_to_dodge_veto() {
+        let mut deps = setup();
+
+        vote(&mut deps, "voter1", VoteOption::Yes);
+        vote(&mut deps, "voter2", VoteOption::NoWithVeto);
+        vote(&mut deps, "voter3", VoteOption::NoWithVeto);
+
+        // The proposer cannot take the deposit back once votes are in
+        let msg = ExecuteMsg::CancelProposal { proposal_id: 1 };
+        let err = governance::execute(deps.as_mut(), localnet_env(), mock_info("proposer", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::VotingStarted { proposal_id: 1 }));
+        assert_eq!(proposal(&deps).status, ProposalStatus::Pending);
+
+        let msg = ExecuteMsg::TallyProposal { proposal_id: 1 };
+        let res = governance::execute(deps.as_mut(), env_at(VOTING_PERIOD + 1), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Burn { amount: coins(1000, "uluna") }));
+        assert_eq!(proposal(&deps).status, ProposalStatus::Vetoed);
+    }
+
+    #[test]
+    fn test_passed_proposal_expires() {
+        let mut deps = setup();
+
+        vote(&mut deps, "voter1", VoteOption::Yes);
+        vote(&mut deps, "voter2", VoteOption::Yes);
+        vote(&mut deps, "voter3", VoteOption::Yes);
+
+        let msg = ExecuteMsg::TallyProposal { proposal_id: 1 };
+        governance::execute(deps.as_mut(), env_at(VOTING_PERIOD + 1), mock_info("anyone", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::ExecuteProposal { proposal_id: 1 };
+        let late = env_at(VOTING_PERIOD + TIMELOCK_PERIOD + EXECUTION_PERIOD);
+        let res = governance::execute(deps.as_mut(), late, mock_info("anyone", &[]), msg).unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(proposal(&deps).status, ProposalStatus::Expired);
+    }
+
+    #[test]
+    fn test_proposer_can_cancel_before_votes() {
+        let mut deps = setup();
+
+        // Only the proposer may cancel
+        let msg = ExecuteMsg::CancelProposal { proposal_id: 1 };
+        assert!(governance::execute(deps.as_mut(), localnet_env(), mock_info("voter1", &[]), msg.clone()).is_err());
+
+        let res = governance::execute(deps.as_mut(), localnet_env(), mock_info("proposer", &[]), msg).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "proposer".to_string(),
+            amount: coins(1000, "uluna"),
+        }));
+        assert_eq!(proposal(&deps).status, ProposalStatus::Cancelled);
+
+        // Cancelled proposals no longer accept votes
+        let msg = ExecuteMsg::Vote { proposal_id: 1, vote: VoteOption::Yes };
+        let err = governance::execute(deps.as_mut(), localnet_env(), mock_info("voter1", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidProposalStatus { proposal_id: 1, status } if status == "cancelled"));
+    }
+
+    #[test]
+    fn test_parameter_change_bounds() {
+        let mut deps = setup();
+
+        // Liquidation fees above 20% are rejected at proposal time
+        let msg = ExecuteMsg::ProposeParameterChange {
+            title: "Raise fee".to_string(),
+            description: "Raise the liquidation fee".to_string(),
+            change: ParameterChange::LiquidationFee {
+                liquidation_engine: Addr::unchecked("liquidation_engine"),
+                liquidation_fee: 25,
+            },
+        };
+        let err = governance::execute(deps.as_mut(), localnet_env(), mock_info("proposer", &coins(1000, "uluna")), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfig { field, .. } if field == "liquidation_fee"));
+
+        // Oracle timeouts must stay within a minute and a day
+        let msg = ExecuteMsg::ProposeParameterChange {
+            title: "Timeout".to_string(),
+            description: "Shorten the price timeout".to_string(),
+            change: ParameterChange::OraclePriceTimeout {
+                eqa_oracle: Addr::unchecked("eqa_oracle"),
+                price_timeout: 10,
+            },
+        };
+        let res = governance::execute(deps.as_mut(), localnet_env(), mock_info("proposer", &coins(1000, "uluna")), msg);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_parameter_change_dispatches_update() {
+        let mut deps = setup();
+
+        let msg = ExecuteMsg::ProposeParameterChange {
+            title: "Raise threshold".to_string(),
+            description: "Require 150% collateralization".to_string(),
+            change: ParameterChange::LiquidationThresholdRatio {
+                liquidation_engine: Addr::unchecked("liquidation_engine"),
+                threshold_ratio: 150,
+            },
+        };
+        governance::execute(deps.as_mut(), localnet_env(), mock_info("proposer", &coins(1000, "uluna")), msg).unwrap();
+
+        for voter in ["voter1", "voter2", "voter3"] {
+            let msg = ExecuteMsg::Vote { proposal_id: 2, vote: VoteOption::Yes };
+            governance::execute(deps.as_mut(), localnet_env(), mock_info(voter, &[]), msg).unwrap();
+        }
+
+        let msg = ExecuteMsg::TallyProposal { proposal_id: 2 };
+        governance::execute(deps.as_mut(), env_at(VOTING_PERIOD + 1), mock_info("anyone", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::ExecuteProposal { proposal_id: 2 };
+        let res = governance::execute(deps.as_mut(), env_at(VOTING_PERIOD + TIMELOCK_PERIOD), mock_info("anyone", &[]), msg).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => {
+                assert_eq!(contract_addr, "liquidation_engine");
+                let update: serde_json::Value = from_json(msg).unwrap();
+                assert_eq!(update["update_config"]["threshold_ratio"], 150);
+                assert!(update["update_config"]["liquidation_fee"].is_null());
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_delegated_voting() {
+        let mut deps = instantiate();
+        let mut env = localnet_env();
+
+        for delegator in ["voter1", "voter2"] {
+            let msg = ExecuteMsg::Delegate { to: "delegate".to_string() };
+            governance::execute(deps.as_mut(), env.clone(), mock_info(delegator, &[]), msg).unwrap();
+        }
+
+        // Delegations made once the proposal has started do not count towards it
+        env.block.height += 1;
+        propose(&mut deps, env.clone());
+        let msg = ExecuteMsg::Delegate { to: "delegate".to_string() };
+        governance::execute(deps.as_mut(), env.clone(), mock_info("voter3", &[]), msg).unwrap();
+
+        // The delegate votes with its own power plus the two delegations from before the proposal
+        vote(&mut deps, "delegate", VoteOption::Yes);
+        assert_eq!(proposal(&deps).yes_votes, Uint128::new(3));
+
+        // A delegator voting directly takes its power back from the delegate, once
+        vote(&mut deps, "voter1", VoteOption::No);
+        vote(&mut deps, "voter1", VoteOption::No);
+        let res = proposal(&deps);
+        assert_eq!(res.yes_votes, Uint128::new(2));
+        assert_eq!(res.no_votes, Uint128::new(1));
+
+        let query_msg = QueryMsg::GetVote { proposal_id: 1, voter: "delegate".to_string() };
+        let res: VoteResponse = from_json(governance::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.voting_power, Uint128::new(2));
+
+        // Current power reflects every delegation, including the late one
+        let query_msg = QueryMsg::GetVotingPower { address: "delegate".to_string() };
+        let res: VotingPowerResponse = from_json(governance::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.delegated_power, Uint128::new(3));
+        assert_eq!(res.total_power, Uint128::new(4));
+
+        // Undelegating removes the delegator from the delegate's list
+        let msg = ExecuteMsg::Undelegate {};
+        governance::execute(deps.as_mut(), env, mock_info("voter2", &[]), msg).unwrap();
+
+        let query_msg = QueryMsg::ListDelegators { delegate: "delegate".to_string(), start_after: None, limit: None };
+        let res: DelegatorListResponse = from_json(governance::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+        let delegators: Vec<_> = res.delegators.iter().map(|d| d.delegator.as_str()).collect();
+        assert_eq!(delegators, vec!["voter1", "voter3"]);
+
+        // Delegating to yourself is rejected
+        let msg = ExecuteMsg::Delegate { to: "voter2".to_string() };
+        let err = governance::execute(deps.as_mut(), localnet_env(), mock_info("voter2", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::SelfDelegation {}));
+    }
+
+    #[test]
+    fn test_guardian_action_needs_threshold_and_is_reversible() {
+        let mut deps = setup();
+        let freeze = ExecuteMsg::ProposeGuardianAction {
+            action: GuardianAction::FreezeOracle { eqa_oracle: Addr::unchecked("eqa_oracle") },
+        };
+
+        // Only council members can act
+        let err = governance::execute(deps.as_mut(), localnet_env(), mock_info("voter1", &[]), freeze.clone());
+        assert!(err.is_err());
+
+        // One approval is not enough for a 2-of-3 council
+        let res = governance::execute(deps.as_mut(), localnet_env(), mock_info("guardian1", &[]), freeze).unwrap();
+        assert!(res.messages.is_empty());
+
+        let msg = ExecuteMsg::ApproveGuardianAction { action_id: 1 };
+        let err = governance::execute(deps.as_mut(), localnet_env(), mock_info("guardian1", &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyApproved { action_id: 1, .. }));
+
+        // The second approval executes the action immediately
+        let res = governance::execute(deps.as_mut(), localnet_env(), mock_info("guardian2", &[]), msg).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => {
+                assert_eq!(contract_addr, "eqa_oracle");
+                let freeze: serde_json::Value = from_json(msg).unwrap();
+                assert_eq!(freeze["set_frozen"]["frozen"], true);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        // Executed actions stay in the log and cannot be approved again
+        let query_msg = QueryMsg::ListGuardianActions { start_after: None, limit: None };
+        let res: GuardianActionListResponse = from_json(governance::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.actions.len(), 1);
+        assert_eq!(res.actions[0].status, GuardianActionStatus::Executed);
+        assert_eq!(res.actions[0].approvals, vec![Addr::unchecked("guardian1"), Addr::unchecked("guardian2")]);
+
+        let msg = ExecuteMsg::ApproveGuardianAction { action_id: 1 };
+        let err = governance::execute(deps.as_mut(), localnet_env(), mock_info("guardian3", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::GuardianActionExecuted { action_id: 1 }));
+
+        // Governance unfreezes the oracle through a normal parameter change
+        let msg = ExecuteMsg::ProposeParameterChange {
+            title: "Unfreeze oracle".to_string(),
+            description: "Resume price feeds".to_string(),
+            change: ParameterChange::OracleFrozen { eqa_oracle: Addr::unchecked("eqa_oracle"), frozen: false },
+        };
+        governance::execute(deps.as_mut(), localnet_env(), mock_info("proposer", &coins(1000, "uluna")), msg).unwrap();
+
+        for voter in ["voter1", "voter2", "voter3"] {
+            let msg = ExecuteMsg::Vote { proposal_id: 2, vote: VoteOption::Yes };
+            governance::execute(deps.as_mut(), localnet_env(), mock_info(voter, &[]), msg).unwrap();
+        }
+
+        let msg = ExecuteMsg::TallyProposal { proposal_id: 2 };
+        governance::execute(deps.as_mut(), env_at(VOTING_PERIOD + 1), mock_info("anyone", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::ExecuteProposal { proposal_id: 2 };
+        let res = governance::execute(deps.as_mut(), env_at(VOTING_PERIOD + TIMELOCK_PERIOD), mock_info("anyone", &[]), msg).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                let unfreeze: serde_json::Value = from_json(msg).unwrap();
+                assert_eq!(unfreeze["set_frozen"]["frozen"], false);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_guardian_tightens_bridge_rate_limit() {
+        let mut deps = setup();
+        let tighten = ExecuteMsg::ProposeGuardianAction {
+            action: GuardianAction::TightenBridgeRateLimit {
+                collateral_manager: Addr::unchecked("collateral_manager"),
+                asset: "noble_usdc".to_string(),
+                destination_chain: "noble-1".to_string(),
+                max_outflow: Uint128::new(1_000_000),
+            },
+        };
+        governance::execute(deps.as_mut(), localnet_env(), mock_info("guardian1", &[]), tighten).unwrap();
+
+        // The collateral manager only ever receives the tighten-only message from guardians
+        let msg = ExecuteMsg::ApproveGuardianAction { action_id: 1 };
+        let res = governance::execute(deps.as_mut(), localnet_env(), mock_info("guardian2", &[]), msg).unwrap();
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => {
+                assert_eq!(contract_addr, "collateral_manager");
+                assert_eq!(from_json::<CollateralManagerMsg>(msg).unwrap(), CollateralManagerMsg::TightenRateLimit {
+                    asset: "noble_usdc".to_string(),
+                    destination_chain: "noble-1".to_string(),
+                    max_outflow: Uint128::new(1_000_000),
+                });
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        // Loosening again goes through a proposal, which needs a valid window
+        let msg = ExecuteMsg::ProposeParameterChange {
+            title: "Restore bridge limit".to_string(),
+            description: "Reopen Noble outflows".to_string(),
+            change: ParameterChange::BridgeRateLimit {
+                collateral_manager: Addr::unchecked("collateral_manager"),
+                asset: "noble_usdc".to_string(),
+                destination_chain: "noble-1".to_string(),
+                max_outflow: Uint128::new(10_000_000),
+                window_seconds: 0,
+            },
+        };
+        assert!(governance::execute(deps.as_mut(), localnet_env(), mock_info("proposer", &coins(1000, "uluna")), msg).is_err());
+    }
+
+    #[test]
+    fn test_governance_accepts_ownership_then_changes_parameters() {
+        let mut deps = instantiate();
+        let governance_addr = localnet_env().contract.address;
+
+        let mut arbitrage = mock_dependencies();
+        let msg = ArbitrageInstantiateMsg {
+            reward_percentage: Decimal::percent(10),
+            eqa_token: Addr::unchecked("eqa_token"),
+            oracle: Addr::unchecked("oracle"),
+            eqa_denom: "ueqa".to_string(),
+            collateral_denom: "uusdc".to_string(),
+            max_reward_per_block: Uint128::new(3),
+            dex_pair: Addr::unchecked("pair"),
+            pool_model: PoolModel::ConstantProduct,
+            pool_fee: Decimal::zero(),
+            chain_id_mappings: None,
+        };
+        arbitrage_module::instantiate(arbitrage.as_mut(), localnet_env(), mock_info("admin", &[]), msg).unwrap();
+
+        // The admin proposes governance as the new owner, and a proposal accepts it
+        let msg = ArbitrageExecuteMsg::UpdateOwnership(OwnershipAction::ProposeNewOwner {
+            new_owner: governance_addr.to_string(),
+            expiry: None,
+        });
+        arbitrage_module::execute(arbitrage.as_mut(), localnet_env(), mock_info("admin", &[]), msg).unwrap();
+
+        // Governance's messages reach the module with governance as the sender
+        let mut dispatch = |msgs: Vec<CosmosMsg>| {
+            for msg in msgs {
+                match msg {
+                    CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => {
+                        assert_eq!(contract_addr, "arbitrage_module");
+                        let info = mock_info(governance_addr.as_str(), &[]);
+                        arbitrage_module::execute(arbitrage.as_mut(), localnet_env(), info, from_json(msg).unwrap()).unwrap();
+                    }
+                    other => panic!("unexpected message: {:?}", other),
+                }
+            }
+        };
+
+        let msg = ExecuteMsg::ProposeAcceptOwnership {
+            title: "Take over the arbitrage module".to_string(),
+            description: "Accept the pending ownership transfer".to_string(),
+            contract_addr: Addr::unchecked("arbitrage_module"),
+        };
+        governance::execute(deps.as_mut(), localnet_env(), mock_info("proposer", &coins(1000, "uluna")), msg).unwrap();
+        dispatch(pass_and_execute(&mut deps, 1, 0));
+
+        // Governance now owns the module and can change its parameters through proposals
+        let start = VOTING_PERIOD + TIMELOCK_PERIOD;
+        let msg = ExecuteMsg::ProposeParameterChange {
+            title: "Raise rewards".to_string(),
+            description: "Pay 20% of the arbitrage profit".to_string(),
+            change: ParameterChange::ArbitrageRewardPercentage {
+                arbitrage_module: Addr::unchecked("arbitrage_module"),
+                reward_percentage: Decimal::percent(20),
+            },
+        };
+        governance::execute(deps.as_mut(), env_at(start), mock_info("proposer", &coins(1000, "uluna")), msg).unwrap();
+        dispatch(pass_and_execute(&mut deps, 2, start));
+
+        let ownership: Ownership =
+            from_json(arbitrage_module::query(arbitrage.as_ref(), localnet_env(), ArbitrageQueryMsg::GetOwnership {}).unwrap()).unwrap();
+        assert_eq!(ownership.owner, Some(governance_addr));
+        let config: ArbitrageConfigResponse =
+            from_json(arbitrage_module::query(arbitrage.as_ref(), localnet_env(), ArbitrageQueryMsg::Config {}).unwrap()).unwrap();
+        assert_eq!(config.reward_percentage, Decimal::percent(20));
+    }
+}
@@ -1,11 +1,20 @@
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{
-        testing::mock_dependencies,
-        Uint128,
+        testing::{mock_dependencies, mock_env, mock_info},
+        to_json_binary, Addr, ContractResult, Decimal, Env, StdError, SystemResult, Uint128,
     };
+    use equilibria_smart_contracts::error::ContractError;
+    use equilibria_smart_contracts::oracle::PriceResponse;
     use equilibria_smart_contracts::state::{CollateralState, COLLATERAL};
     
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+    
     #[test]
     fn test_basic_liquidation_math() {
         // This is a basic test of liquidation math 
@@ -70,4 +79,55 @@ mod tests {
         assert_eq!(stored.usdc_noble, Uint128::new(40_000));
         assert_eq!(stored.total_locked, Uint128::new(100_000));
     }
+    
+    #[test]
+    fn test_liquidation_errors_are_typed() {
+        let mut deps = mock_dependencies();
+        let env = localnet_env();
+        let msg = liquidation_engine::InstantiateMsg {
+            threshold_ratio: 110,
+            liquidation_fee: None,
+            oracle_address: Some(Addr::unchecked("oracle")),
+            chain_id_mappings: None,
+        };
+        liquidation_engine::instantiate(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+        COLLATERAL.save(deps.as_mut().storage, &CollateralState {
+            usdc_axelar: Uint128::new(60_000),
+            usdc_noble: Uint128::new(40_000),
+            total_locked: Uint128::new(100_000),
+        }).unwrap();
+        
+        let check = |eqa_supply: u128| liquidation_engine::ExecuteMsg::CheckAndLiquidate { eqa_supply: Uint128::new(eqa_supply) };
+        let status = liquidation_engine::QueryMsg::GetLiquidationStatus { eqa_supply: Uint128::new(90_000) };
+        
+        // An unreachable oracle fails the status query rather than reporting a price of 1.0
+        let err = liquidation_engine::query(deps.as_ref(), env.clone(), status.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::GenericErr { .. })));
+        
+        let last_updated = env.block.time.seconds();
+        deps.querier.update_wasm(move |_| {
+            let price = PriceResponse { denom: "eqa".to_string(), price: Decimal::one(), last_updated };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&price).unwrap()))
+        });
+        liquidation_engine::query(deps.as_ref(), env.clone(), status).unwrap();
+        liquidation_engine::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), check(90_000)).unwrap();
+        
+        // The shortfall is reported as amounts
+        let err = liquidation_engine::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), check(95_000)).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InsufficientCollateral { required, available }
+                if required == Uint128::new(104_500) && available == Uint128::new(100_000)
+        ));
+        
+        let pause = liquidation_engine::ExecuteMsg::UpdateConfig {
+            oracle_address: None,
+            threshold_ratio: None,
+            liquidation_fee: None,
+            is_active: Some(false),
+        };
+        liquidation_engine::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), pause).unwrap();
+        let err = liquidation_engine::execute(deps.as_mut(), env, mock_info("owner", &[]), check(90_000)).unwrap_err();
+        assert!(matches!(err, ContractError::Paused { operation } if operation == "liquidation"));
+    }
 }
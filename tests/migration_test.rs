@@ -0,0 +1,268 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_json, Addr, Binary, CosmosMsg, Decimal, Env, Storage, Uint128, WasmMsg};
+    use cw_storage_plus::{Item, Map};
+    use equilibria_smart_contracts::config::ValidateConfigResponse;
+    use equilibria_smart_contracts::error::ContractError;
+    use equilibria_smart_contracts::migration::{self, ContractVersion};
+    use equilibria_smart_contracts::oracle::PoolModel;
+    use equilibria_smart_contracts::ownership::Ownership;
+    use serde::{Deserialize, Serialize};
+    use registry::{ContractHistoryResponse, ContractInfoResponse, DefaultNetworkResponse, MigrateMsg, QueryMsg};
+
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+
+    fn ownership(storage: &dyn Storage) -> Ownership {
+        from_json(storage.get(b"ownership").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_migrate_legacy_registry_layout() {
+        let mut deps = mock_dependencies();
+        let env = localnet_env();
+
+        // A registry deployed before versions, ownership and network namespaces were stored
+        Item::<Addr>::new("admin").save(&mut deps.storage, &Addr::unchecked("old_admin")).unwrap();
+        let legacy_contracts = Map::<&str, String>::new("contracts");
+        legacy_contracts.save(&mut deps.storage, "oracle", &"terra1oracle".to_string()).unwrap();
+        legacy_contracts.save(&mut deps.storage, "axelar_usdc", &"terra1usdc".to_string()).unwrap();
+        legacy_contracts.save(&mut deps.storage, "bridge", &String::new()).unwrap();
+
+        let res = registry::migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "from_version" && attr.value == "none"));
+        let version = migration::get_contract_version(&deps.storage).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "to_version" && attr.value == version.version));
+        assert_eq!(version.contract, "crates.io:registry");
+
+        // The admin became the owner and the old item is gone
+        assert_eq!(ownership(&deps.storage).owner, Some(Addr::unchecked("old_admin")));
+        assert!(deps.storage.get(b"admin").is_none());
+
+        // Entries moved into the deployment network, with the migration as their first history record
+        let res: DefaultNetworkResponse = from_json(registry::query(deps.as_ref(), env.clone(), QueryMsg::GetDefaultNetwork {}).unwrap()).unwrap();
+        let default_network = res.network;
+        let network = Some(default_network.clone());
+        let msg = QueryMsg::GetContractInfo { name: "oracle".to_string(), network: network.clone() };
+        let res: ContractInfoResponse = from_json(registry::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.entry.address, "terra1oracle");
+        assert_eq!(res.entry.deploy_height, env.block.height);
+
+        let msg = QueryMsg::GetContractHistory { name: "axelar_usdc".to_string(), network, start_after: None, limit: None };
+        let res: ContractHistoryResponse = from_json(registry::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.history.len(), 1);
+        assert_eq!(res.history[0].entry.as_ref().unwrap().address, "terra1usdc");
+
+        // Legacy entries were never validated; the config query reports them a page at a time
+        let validate = |start_after: Option<&str>, limit: u32| -> ValidateConfigResponse {
+            let msg = QueryMsg::ValidateConfig { network: None, start_after: start_after.map(str::to_string), limit: Some(limit) };
+            from_json(registry::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap()
+        };
+        assert!(validate(None, 1).valid);
+        let res = validate(Some("axelar_usdc"), 1);
+        let fields: Vec<_> = res.invalid_fields.iter().map(|invalid| invalid.field.clone()).collect();
+        assert_eq!(fields, vec![format!("contracts.{}.bridge.address", default_network)]);
+        assert!(validate(Some("bridge"), 1).valid);
+
+        // The migrated owner can manage entries
+        let msg = registry::ExecuteMsg::RemoveContractAddress { network: None, name: "oracle".to_string() };
+        registry::execute(deps.as_mut(), env.clone(), mock_info("old_admin", &[]), msg).unwrap();
+
+        // Migrating again to the same version leaves the data alone
+        let res = registry::migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "from_version" && attr.value == version.version));
+        assert_eq!(ownership(&deps.storage).owner, Some(Addr::unchecked("old_admin")));
+    }
+
+    #[test]
+    fn test_migrate_refuses_downgrades_and_other_contracts() {
+        let mut deps = mock_dependencies();
+        let env = localnet_env();
+        registry::instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), registry::InstantiateMsg { admin: None, chain_id_mappings: None }).unwrap();
+        assert_eq!(migration::get_contract_version(&deps.storage).unwrap().contract, "crates.io:registry");
+
+        let newer = ContractVersion { contract: "crates.io:registry".to_string(), version: "99.0.0".to_string() };
+        migration::CONTRACT_INFO.save(&mut deps.storage, &newer).unwrap();
+        let err = registry::migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap_err();
+        assert!(matches!(err, ContractError::CannotDowngrade { .. }));
+
+        // Pre-release suffixes compare on the release version
+        let older = ContractVersion { contract: "crates.io:registry".to_string(), version: "0.0.1-beta.1".to_string() };
+        migration::CONTRACT_INFO.save(&mut deps.storage, &older).unwrap();
+        registry::migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+        let other = ContractVersion { contract: "crates.io:governance".to_string(), version: "0.0.1".to_string() };
+        migration::CONTRACT_INFO.save(&mut deps.storage, &other).unwrap();
+        let err = registry::migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap_err();
+        assert!(matches!(err, ContractError::ContractMismatch { .. }));
+
+        let garbled = ContractVersion { contract: "crates.io:registry".to_string(), version: "v1".to_string() };
+        migration::CONTRACT_INFO.save(&mut deps.storage, &garbled).unwrap();
+        let err = registry::migrate(deps.as_mut(), env, MigrateMsg {}).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidVersion { .. }));
+    }
+
+    // Governance state as stored before versions were recorded
+    #[derive(Serialize, Deserialize)]
+    struct LegacyGovernanceConfig {
+        admin: Addr,
+        voting_period: u64,
+        quorum_percentage: u64,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum LegacyProposalStatus {
+        Pending,
+        Passed,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct LegacyProposal {
+        id: u64,
+        title: String,
+        description: String,
+        status: LegacyProposalStatus,
+        contract_addr: Addr,
+        new_code_id: u64,
+        yes_votes: Uint128,
+        no_votes: Uint128,
+        abstain_votes: Uint128,
+        end_time: u64,
+    }
+
+    fn legacy_proposal(id: u64, status: LegacyProposalStatus, end_time: u64) -> LegacyProposal {
+        LegacyProposal {
+            id,
+            title: format!("Upgrade {}", id),
+            description: "Move to the new code".to_string(),
+            status,
+            contract_addr: Addr::unchecked("collateral_manager"),
+            new_code_id: 10 + id,
+            yes_votes: Uint128::new(70),
+            no_votes: Uint128::new(10),
+            abstain_votes: Uint128::zero(),
+            end_time,
+        }
+    }
+
+    #[test]
+    fn test_migrate_legacy_governance_layout() {
+        let mut deps = mock_dependencies();
+        let env = localnet_env();
+        let now = env.block.time.seconds();
+
+        let config = LegacyGovernanceConfig { admin: Addr::unchecked("old_admin"), voting_period: 100, quorum_percentage: 30 };
+        Item::<LegacyGovernanceConfig>::new("config").save(&mut deps.storage, &config).unwrap();
+        let proposals = Map::<u64, LegacyProposal>::new("proposals");
+        proposals.save(&mut deps.storage, 1, &legacy_proposal(1, LegacyProposalStatus::Passed, now - 10)).unwrap();
+        proposals.save(&mut deps.storage, 2, &legacy_proposal(2, LegacyProposalStatus::Pending, now + 50)).unwrap();
+        Item::<u64>::new("proposal_count").save(&mut deps.storage, &2).unwrap();
+
+        governance::migrate(deps.as_mut(), env.clone(), governance::MigrateMsg {}).unwrap();
+        assert_eq!(ownership(&deps.storage).owner, Some(Addr::unchecked("old_admin")));
+
+        // The voting rules added since take their defaults
+        let msg = governance::QueryMsg::GetConfig {};
+        let res: governance::ConfigResponse = from_json(governance::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!((res.voting_period, res.quorum_percentage), (100, 30));
+        assert_eq!((res.threshold_percentage, res.veto_threshold_percentage), (50, 33));
+        assert_eq!((res.proposal_deposit, res.timelock_period), (None, 0));
+        let msg = governance::QueryMsg::ValidateConfig {};
+        let res: ValidateConfigResponse = from_json(governance::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert!(res.valid);
+
+        // Proposals became upgrade actions proposed by the old admin
+        let msg = governance::QueryMsg::GetProposal { id: 2 };
+        let res: governance::ProposalResponse = from_json(governance::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.status, governance::ProposalStatus::Pending);
+        assert_eq!(res.proposer, Addr::unchecked("old_admin"));
+        assert_eq!(res.yes_votes, Uint128::new(70));
+        assert_eq!(res.end_time, now + 50);
+        assert_eq!(res.action, governance::ProposalAction::Upgrade {
+            contract_addr: Addr::unchecked("collateral_manager"),
+            new_code_id: 12,
+            migrate_msg: Binary::from(b"{}".to_vec()),
+        });
+
+        // A passed proposal can be executed straight away
+        let msg = governance::ExecuteMsg::ExecuteProposal { proposal_id: 1 };
+        let res = governance::execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, new_code_id, .. }) => {
+                assert_eq!((contract_addr.as_str(), *new_code_id), ("collateral_manager", 11));
+            }
+            msg => panic!("unexpected message {:?}", msg),
+        }
+
+        // New proposals continue the legacy numbering
+        let msg = governance::ExecuteMsg::ProposeUpgrade {
+            title: "Upgrade 3".to_string(),
+            description: "Move to the new code".to_string(),
+            contract_addr: Addr::unchecked("collateral_manager"),
+            new_code_id: 13,
+            migrate_msg: None,
+        };
+        let res = governance::execute(deps.as_mut(), env, mock_info("old_admin", &[]), msg).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "proposal_id" && attr.value == "3"));
+    }
+
+    fn legacy_arbitrage_config() -> arbitrage_module::LegacyConfigMsg {
+        arbitrage_module::LegacyConfigMsg {
+            owner: "old_admin".to_string(),
+            eqa_token: Addr::unchecked("eqa_token"),
+            oracle: Addr::unchecked("oracle"),
+            eqa_denom: "ueqa".to_string(),
+            collateral_denom: "uusdc".to_string(),
+            max_reward_per_block: 3u128.into(),
+            dex_pair: Addr::unchecked("pair"),
+            pool_model: PoolModel::ConstantProduct,
+            pool_fee: Decimal::zero(),
+        }
+    }
+
+    #[test]
+    fn test_migrate_legacy_arbitrage_layout() {
+        let mut deps = mock_dependencies();
+        let env = localnet_env();
+
+        // The unversioned module stored nothing but its reward percentage
+        Item::<Decimal>::new("reward_percentage").save(&mut deps.storage, &Decimal::percent(10)).unwrap();
+
+        // The settings it had no storage for must come with the migration
+        let msg = arbitrage_module::MigrateMsg { legacy_config: None };
+        let err = arbitrage_module::migrate(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfig { field, .. } if field == "legacy_config"));
+        deps.storage.remove(b"contract_info"); // failed migrations are reverted on chain
+
+        let mut invalid = legacy_arbitrage_config();
+        invalid.collateral_denom = "ueqa".to_string();
+        let msg = arbitrage_module::MigrateMsg { legacy_config: Some(invalid) };
+        let err = arbitrage_module::migrate(deps.as_mut(), env.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidConfig { field, .. } if field == "collateral_denom"));
+        deps.storage.remove(b"contract_info");
+
+        let msg = arbitrage_module::MigrateMsg { legacy_config: Some(legacy_arbitrage_config()) };
+        arbitrage_module::migrate(deps.as_mut(), env.clone(), msg).unwrap();
+        assert_eq!(ownership(&deps.storage).owner, Some(Addr::unchecked("old_admin")));
+
+        let msg = arbitrage_module::QueryMsg::Config {};
+        let res: arbitrage_module::ConfigResponse = from_json(arbitrage_module::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.reward_percentage, Decimal::percent(10));
+        assert_eq!(res.dex_pair, Addr::unchecked("pair"));
+        let msg = arbitrage_module::QueryMsg::RewardPool {};
+        let res: arbitrage_module::RewardPoolResponse = from_json(arbitrage_module::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!((res.reward_pool, res.collateral_reserve), (Uint128::zero(), Uint128::zero()));
+
+        // The new owner manages the module, and later migrations need no legacy config
+        let msg = arbitrage_module::ExecuteMsg::UpdateRewardPercentage { reward_percentage: Decimal::percent(20) };
+        arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("old_admin", &[]), msg).unwrap();
+        arbitrage_module::migrate(deps.as_mut(), env, arbitrage_module::MigrateMsg { legacy_config: None }).unwrap();
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use collateral_manager::{
+        execute, instantiate, query, CollateralAsset, CollateralAssetsResponse, CollateralResponse, ExecuteMsg,
+        InstantiateMsg, QueryMsg,
+    };
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        coin, coins, from_json, to_json_binary, BankMsg, ContractResult, CosmosMsg, Env, IbcMsg, IbcTimeout, OwnedDeps,
+        SystemError, SystemResult, Uint128, WasmQuery,
+    };
+    use equilibria_smart_contracts::error::ContractError;
+    use registry::{ContractAddressResponse, QueryMsg as RegistryQueryMsg};
+
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+
+    const NOBLE_DENOM: &str = "ibc/NOBLEUSDC";
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "registry" => {
+                let RegistryQueryMsg::GetContractAddress { name, .. } = from_json(msg).unwrap() else {
+                    panic!("unexpected registry query");
+                };
+                let address = format!("{}_token", name);
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&ContractAddressResponse { name, address }).unwrap(),
+                ))
+            }
+            other => SystemResult::Err(SystemError::UnsupportedRequest { kind: format!("{:?}", other) }),
+        });
+
+        instantiate(deps.as_mut(), localnet_env(), mock_info("admin", &[]), InstantiateMsg {
+            admin: None,
+            registry_address: "registry".to_string(),
+            register_cross_chain: None,
+            chain_id_mappings: None,
+        })
+        .unwrap();
+
+        execute(deps.as_mut(), localnet_env(), mock_info("admin", &[]), ExecuteMsg::SetNativeDenom {
+            asset: "noble_usdc".to_string(),
+            denom: Some(NOBLE_DENOM.to_string()),
+        })
+        .unwrap();
+
+        deps
+    }
+
+    fn collateral(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> CollateralResponse {
+        from_json(query(deps.as_ref(), localnet_env(), QueryMsg::GetCollateralInfo {}).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_native_deposits_and_withdrawals() {
+        let mut deps = setup();
+
+        let assets: CollateralAssetsResponse =
+            from_json(query(deps.as_ref(), localnet_env(), QueryMsg::GetCollateralAssets {}).unwrap()).unwrap();
+        assert_eq!(assets.assets[0].info, CollateralAsset::Cw20 { contract_addr: "axelar_usdc_token".to_string() });
+        assert_eq!(assets.assets[1].info, CollateralAsset::Native { denom: NOBLE_DENOM.to_string() });
+
+        // Native deposits must attach exactly the amount they claim
+        let deposit = ExecuteMsg::ReceiveTokens { token_addr: NOBLE_DENOM.to_string(), amount: Uint128::new(5_000) };
+        let short = mock_info("user", &coins(4_000, NOBLE_DENOM));
+        assert!(execute(deps.as_mut(), localnet_env(), short, deposit.clone()).is_err());
+
+        let res = execute(deps.as_mut(), localnet_env(), mock_info("user", &coins(5_000, NOBLE_DENOM)), deposit).unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(collateral(&deps).usdc_noble, Uint128::new(5_000));
+
+        // Local payouts are admin-only bank sends
+        let send = ExecuteMsg::SendTokens {
+            token_addr: NOBLE_DENOM.to_string(),
+            recipient: "user".to_string(),
+            amount: Uint128::new(1_000),
+        };
+        let err = execute(deps.as_mut(), localnet_env(), mock_info("user", &[]), send.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { .. }));
+        assert_eq!(collateral(&deps).usdc_noble, Uint128::new(5_000));
+
+        let res = execute(deps.as_mut(), localnet_env(), mock_info("admin", &[]), send).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user".to_string(),
+            amount: coins(1_000, NOBLE_DENOM),
+        }));
+
+        // Remote payouts are admin-only IBC transfers with a timeout
+        let withdraw = |token: &str| ExecuteMsg::SendTokensViaIbc {
+            token_addr: token.to_string(),
+            channel_id: "channel-1".to_string(),
+            recipient: "noble1user".to_string(),
+            amount: Uint128::new(2_000),
+            timeout_seconds: Some(300),
+        };
+        assert!(execute(deps.as_mut(), localnet_env(), mock_info("user", &[]), withdraw(NOBLE_DENOM)).is_err());
+        assert!(execute(deps.as_mut(), localnet_env(), mock_info("admin", &[]), withdraw("axelar_usdc_token")).is_err());
+
+        let res = execute(deps.as_mut(), localnet_env(), mock_info("admin", &[]), withdraw(NOBLE_DENOM)).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id: "channel-1".to_string(),
+            to_address: "noble1user".to_string(),
+            amount: coin(2_000, NOBLE_DENOM),
+            timeout: IbcTimeout::with_timestamp(localnet_env().block.time.plus_seconds(300)),
+        }));
+        assert_eq!(collateral(&deps).usdc_noble, Uint128::new(2_000));
+
+        // CW20 collateral is unaffected
+        assert_eq!(collateral(&deps).usdc_axelar, Uint128::zero());
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_json, Addr, Env};
+    use equilibria_smart_contracts::error::ContractError;
+    use equilibria_smart_contracts::ownership::{Ownership, OwnershipAction};
+    use registry::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+
+    fn set_address(name: &str) -> ExecuteMsg {
+        ExecuteMsg::SetContractAddress {
+            network: None,
+            name: name.to_string(),
+            address: format!("terra1{}", name),
+            code_id: None,
+            version: None,
+            description: None,
+        }
+    }
+
+    fn propose(new_owner: &str, expiry: Option<u64>) -> ExecuteMsg {
+        ExecuteMsg::UpdateOwnership(OwnershipAction::ProposeNewOwner { new_owner: new_owner.to_string(), expiry })
+    }
+
+    #[test]
+    fn test_ownership_transfer_lifecycle() {
+        let mut deps = mock_dependencies();
+        let env = localnet_env();
+        registry::instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), InstantiateMsg { admin: None, chain_id_mappings: None }).unwrap();
+
+        // Nothing to accept or cancel yet
+        let accept = ExecuteMsg::UpdateOwnership(OwnershipAction::AcceptOwnership {});
+        let err = registry::execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), accept.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::NoPendingOwner {}));
+        let cancel = ExecuteMsg::UpdateOwnership(OwnershipAction::CancelOwnershipProposal {});
+        let err = registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), cancel.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::NoPendingOwner {}));
+
+        // Only the owner may propose, and an already-expired proposal is rejected
+        let err = registry::execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), propose("alice", None)).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let now = env.block.time.seconds();
+        let err = registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), propose("alice", Some(now))).unwrap_err();
+        assert!(matches!(err, ContractError::OwnershipProposalExpired {}));
+
+        // A pending proposal does not hand over any rights until accepted
+        registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), propose("alice", Some(now + 100))).unwrap();
+        let ownership: Ownership = from_json(registry::query(deps.as_ref(), env.clone(), QueryMsg::GetOwnership {}).unwrap()).unwrap();
+        assert_eq!(ownership, Ownership {
+            owner: Some(Addr::unchecked("admin")),
+            pending_owner: Some(Addr::unchecked("alice")),
+            pending_expiry: Some(now + 100),
+        });
+        assert!(registry::execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), set_address("a")).is_err());
+
+        // Only the proposed owner can accept
+        let err = registry::execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), accept.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        // The proposal cannot be accepted once it has expired
+        let mut late = env.clone();
+        late.block.time = late.block.time.plus_seconds(100);
+        let err = registry::execute(deps.as_mut(), late, mock_info("alice", &[]), accept.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::OwnershipProposalExpired {}));
+
+        // A cancelled proposal cannot be accepted either
+        registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), cancel).unwrap();
+        let err = registry::execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), accept.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::NoPendingOwner {}));
+
+        // Propose again and accept in time
+        registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), propose("alice", None)).unwrap();
+        registry::execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), accept).unwrap();
+        assert!(registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), set_address("a")).is_err());
+        registry::execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), set_address("a")).unwrap();
+
+        let ownership: Ownership = from_json(registry::query(deps.as_ref(), env, QueryMsg::GetOwnership {}).unwrap()).unwrap();
+        assert_eq!(ownership, Ownership { owner: Some(Addr::unchecked("alice")), pending_owner: None, pending_expiry: None });
+    }
+
+    #[test]
+    fn test_renounced_ownership_cannot_be_recovered() {
+        let mut deps = mock_dependencies();
+        let env = localnet_env();
+        registry::instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), InstantiateMsg { admin: None, chain_id_mappings: None }).unwrap();
+
+        // Renouncing also drops any pending proposal
+        registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), propose("alice", None)).unwrap();
+        let renounce = ExecuteMsg::UpdateOwnership(OwnershipAction::RenounceOwnership {});
+        let res = registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), renounce).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "owner" && attr.value == "none"));
+
+        let accept = ExecuteMsg::UpdateOwnership(OwnershipAction::AcceptOwnership {});
+        let err = registry::execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), accept).unwrap_err();
+        assert!(matches!(err, ContractError::NoPendingOwner {}));
+
+        let err = registry::execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), set_address("a")).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { .. }));
+        let err = registry::execute(deps.as_mut(), env, mock_info("admin", &[]), propose("admin", None)).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use collateral_manager::{
+        execute, instantiate, query, CollateralResponse, ExecuteMsg, InstantiateMsg, PsmInfoResponse, QueryMsg,
+        ReceiveMsg,
+    };
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        from_json, to_json_binary, ContractResult, CosmosMsg, Decimal, Env, OwnedDeps, SystemError, SystemResult,
+        Uint128, WasmMsg, WasmQuery,
+    };
+    use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+    use registry::{ContractAddressResponse, QueryMsg as RegistryQueryMsg};
+
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "registry" => {
+                let RegistryQueryMsg::GetContractAddress { name, .. } = from_json(msg).unwrap() else {
+                    panic!("unexpected registry query");
+                };
+                let address = format!("{}_token", name);
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&ContractAddressResponse { name, address }).unwrap(),
+                ))
+            }
+            other => SystemResult::Err(SystemError::UnsupportedRequest { kind: format!("{:?}", other) }),
+        });
+
+        instantiate(deps.as_mut(), localnet_env(), mock_info("admin", &[]), InstantiateMsg {
+            admin: None,
+            registry_address: "registry".to_string(),
+            register_cross_chain: None,
+            chain_id_mappings: None,
+        })
+        .unwrap();
+
+        execute(deps.as_mut(), localnet_env(), mock_info("admin", &[]), ExecuteMsg::ConfigurePsm {
+            eqa_token: "eqa_token".to_string(),
+            fee: Decimal::permille(1),
+        })
+        .unwrap();
+        execute(deps.as_mut(), localnet_env(), mock_info("admin", &[]), ExecuteMsg::SetPsmDebtCeiling {
+            asset: "noble_usdc".to_string(),
+            ceiling: Uint128::new(1_000_000),
+        })
+        .unwrap();
+
+        deps
+    }
+
+    fn swap_out(token: &str, amount: u128) -> ExecuteMsg {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "holder".to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&ReceiveMsg::PsmSwapOut { token_addr: token.to_string() }).unwrap(),
+        })
+    }
+
+    fn cw20_msgs(msgs: &[cosmwasm_std::SubMsg]) -> Vec<(String, Cw20ExecuteMsg)> {
+        msgs.iter()
+            .map(|sub| match &sub.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) => (contract_addr.clone(), from_json(msg).unwrap()),
+                other => panic!("unexpected message: {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_psm_swaps_at_par_minus_fee() {
+        let mut deps = setup();
+
+        // 100_000 Noble USDC in: 100 fee, 99_900 EQA minted
+        let res = execute(deps.as_mut(), localnet_env(), mock_info("holder", &[]), ExecuteMsg::PsmSwapIn {
+            token_addr: "noble_usdc_token".to_string(),
+            amount: Uint128::new(100_000),
+        })
+        .unwrap();
+        assert_eq!(cw20_msgs(&res.messages), vec![
+            ("noble_usdc_token".to_string(), Cw20ExecuteMsg::TransferFrom {
+                owner: "holder".to_string(),
+                recipient: localnet_env().contract.address.to_string(),
+                amount: Uint128::new(100_000),
+            }),
+            ("eqa_token".to_string(), Cw20ExecuteMsg::Mint {
+                recipient: "holder".to_string(),
+                amount: Uint128::new(99_900),
+            }),
+        ]);
+
+        // 50_000 EQA back out: burned, 49_950 USDC returned
+        let res = execute(deps.as_mut(), localnet_env(), mock_info("eqa_token", &[]), swap_out("noble_usdc_token", 50_000)).unwrap();
+        assert_eq!(cw20_msgs(&res.messages), vec![
+            ("eqa_token".to_string(), Cw20ExecuteMsg::Burn { amount: Uint128::new(50_000) }),
+            ("noble_usdc_token".to_string(), Cw20ExecuteMsg::Transfer {
+                recipient: "holder".to_string(),
+                amount: Uint128::new(49_950),
+            }),
+        ]);
+
+        let collateral: CollateralResponse =
+            from_json(query(deps.as_ref(), localnet_env(), QueryMsg::GetCollateralInfo {}).unwrap()).unwrap();
+        assert_eq!(collateral.usdc_noble, Uint128::new(50_050));
+        assert_eq!(collateral.usdc_axelar, Uint128::zero());
+
+        let info: PsmInfoResponse = from_json(query(deps.as_ref(), localnet_env(), QueryMsg::GetPsmInfo {}).unwrap()).unwrap();
+        let noble = info.assets.iter().find(|asset| asset.asset == "noble_usdc").unwrap();
+        assert_eq!(noble.debt, Uint128::new(49_900));
+    }
+
+    #[test]
+    fn test_psm_limits() {
+        let mut deps = setup();
+
+        // Axelar USDC has no ceiling yet
+        let swap_in = |token: &str, amount: u128| ExecuteMsg::PsmSwapIn {
+            token_addr: token.to_string(),
+            amount: Uint128::new(amount),
+        };
+        assert!(execute(deps.as_mut(), localnet_env(), mock_info("holder", &[]), swap_in("axelar_usdc_token", 1_000)).is_err());
+
+        // Noble USDC stops at its ceiling
+        assert!(execute(deps.as_mut(), localnet_env(), mock_info("holder", &[]), swap_in("noble_usdc_token", 1_002_000)).is_err());
+        execute(deps.as_mut(), localnet_env(), mock_info("holder", &[]), swap_in("noble_usdc_token", 1_001_001)).unwrap();
+
+        // Only the EQA token can swap out, and never more than the asset's PSM debt
+        assert!(execute(deps.as_mut(), localnet_env(), mock_info("holder", &[]), swap_out("noble_usdc_token", 1_000)).is_err());
+        assert!(execute(deps.as_mut(), localnet_env(), mock_info("eqa_token", &[]), swap_out("axelar_usdc_token", 1_000)).is_err());
+        assert!(execute(deps.as_mut(), localnet_env(), mock_info("eqa_token", &[]), swap_out("noble_usdc_token", 1_000_001)).is_err());
+
+        // Fees are capped and admin-only
+        let configure = ExecuteMsg::ConfigurePsm { eqa_token: "eqa_token".to_string(), fee: Decimal::percent(2) };
+        assert!(execute(deps.as_mut(), localnet_env(), mock_info("admin", &[]), configure).is_err());
+        let configure = ExecuteMsg::ConfigurePsm { eqa_token: "eqa_token".to_string(), fee: Decimal::zero() };
+        assert!(execute(deps.as_mut(), localnet_env(), mock_info("holder", &[]), configure).is_err());
+    }
+}
@@ -1,21 +1,31 @@
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::from_json;
+    use cosmwasm_std::{from_json, Env};
+    use equilibria_smart_contracts::ownership::OwnershipAction;
     use registry::{
         InstantiateMsg, ExecuteMsg, QueryMsg, 
-        ContractAddressResponse, AllContractsResponse
+        ContractAddressResponse, AllContractsResponse, ContractAtHeightResponse, ContractHistoryResponse,
+        ContractInfoResponse,
     };
     
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+
     #[test]
     fn test_registry_basic_functions() {
         let mut deps = mock_dependencies();
-        let env = mock_env();
+        let env = localnet_env();
         
         // Initialize registry
         let info = mock_info("admin", &[]);
         let msg = InstantiateMsg {
             admin: None, // Default to sender
+            chain_id_mappings: None,
         };
         
         let res = registry::instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
@@ -23,8 +33,12 @@ mod tests {
         
         // Set contract address
         let msg = ExecuteMsg::SetContractAddress { 
+            network: None,
             name: "test_contract".to_string(), 
-            address: "terra1test".to_string() 
+            address: "terra1test".to_string(),
+            code_id: None,
+            version: None,
+            description: None,
         };
         
         let res = registry::execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
@@ -32,22 +46,36 @@ mod tests {
         
         // Set another contract address
         let msg = ExecuteMsg::SetContractAddress { 
+            network: None,
             name: "test_contract2".to_string(), 
-            address: "terra1test2".to_string() 
+            address: "terra1test2".to_string(),
+            code_id: None,
+            version: None,
+            description: None,
         };
         
         let res = registry::execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
         assert!(res.attributes.iter().any(|attr| attr.key == "name" && attr.value == "test_contract2"));
         
         // Query contract address
-        let query_msg = QueryMsg::GetContractAddress { name: "test_contract".to_string() };
+        let query_msg = QueryMsg::GetContractAddress { name: "test_contract".to_string(), network: None };
         let res: ContractAddressResponse = from_json(registry::query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap();
         
         assert_eq!(res.name, "test_contract");
         assert_eq!(res.address, "terra1test");
         
+        // Missing names are an error, or None when asked for an optional address
+        let query_msg = QueryMsg::GetContractAddress { name: "missing".to_string(), network: None };
+        assert!(registry::query(deps.as_ref(), env.clone(), query_msg).is_err());
+        let try_get = |name: &str| -> Option<ContractAddressResponse> {
+            let query_msg = QueryMsg::TryGetContractAddress { name: name.to_string(), network: None };
+            from_json(registry::query(deps.as_ref(), env.clone(), query_msg).unwrap()).unwrap()
+        };
+        assert_eq!(try_get("missing"), None);
+        assert_eq!(try_get("test_contract").map(|res| res.address), Some("terra1test".to_string()));
+        
         // Query all contracts
-        let query_msg = QueryMsg::GetAllContracts {};
+        let query_msg = QueryMsg::GetAllContracts { network: None, start_after: None, limit: None };
         let res: AllContractsResponse = from_json(registry::query(deps.as_ref(), env, query_msg).unwrap()).unwrap();
         
         assert_eq!(res.contracts.len(), 2);
@@ -56,29 +84,38 @@ mod tests {
     #[test]
     fn test_registry_admin_functions() {
         let mut deps = mock_dependencies();
-        let env = mock_env();
+        let env = localnet_env();
         
         // Initialize registry
         let info = mock_info("admin", &[]);
         let msg = InstantiateMsg {
             admin: None, // Default to sender
+            chain_id_mappings: None,
         };
         
         let _res = registry::instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
         
-        // Update admin
-        let msg = ExecuteMsg::UpdateConfig { 
-            new_admin: Some(cosmwasm_std::Addr::unchecked("new_admin")) 
-        };
-        
+        // Hand ownership to the new admin, who has to accept it
+        let msg = ExecuteMsg::UpdateOwnership(OwnershipAction::ProposeNewOwner {
+            new_owner: "new_admin".to_string(),
+            expiry: None,
+        });
         let res = registry::execute(deps.as_mut(), env.clone(), info, msg).unwrap();
-        assert!(res.attributes.iter().any(|attr| attr.key == "action" && attr.value == "update_config"));
+        assert!(res.attributes.iter().any(|attr| attr.key == "pending_owner" && attr.value == "new_admin"));
+        
+        let msg = ExecuteMsg::UpdateOwnership(OwnershipAction::AcceptOwnership {});
+        let res = registry::execute(deps.as_mut(), env.clone(), mock_info("new_admin", &[]), msg).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "owner" && attr.value == "new_admin"));
         
         // Try to set address with new admin
         let new_admin_info = mock_info("new_admin", &[]);
         let msg = ExecuteMsg::SetContractAddress { 
+            network: None,
             name: "test_contract3".to_string(), 
-            address: "terra1test3".to_string() 
+            address: "terra1test3".to_string(),
+            code_id: None,
+            version: None,
+            description: None,
         };
         
         let res = registry::execute(deps.as_mut(), env.clone(), new_admin_info, msg).unwrap();
@@ -87,11 +124,120 @@ mod tests {
         // Try with unauthorized user
         let unauthorized_info = mock_info("unauthorized", &[]);
         let msg = ExecuteMsg::SetContractAddress { 
+            network: None,
             name: "test_contract4".to_string(), 
-            address: "terra1test4".to_string() 
+            address: "terra1test4".to_string(),
+            code_id: None,
+            version: None,
+            description: None,
         };
         
         let res = registry::execute(deps.as_mut(), env, unauthorized_info, msg);
         assert!(res.is_err()); // Should fail
     }
+
+    #[test]
+    fn test_registry_history() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("admin", &[]);
+        registry::instantiate(deps.as_mut(), localnet_env(), info.clone(), InstantiateMsg { admin: None, chain_id_mappings: None }).unwrap();
+
+        let set = |address: &str, code_id: u64, version: &str| ExecuteMsg::SetContractAddress {
+            network: None,
+            name: "oracle".to_string(),
+            address: address.to_string(),
+            code_id: Some(code_id),
+            version: Some(version.to_string()),
+            description: Some("EQA price oracle".to_string()),
+        };
+        let env_at = |height: u64| {
+            let mut env = localnet_env();
+            env.block.height = height;
+            env
+        };
+
+        // Upgrades are recorded instead of silently replacing the old entry
+        registry::execute(deps.as_mut(), env_at(100), info.clone(), set("terra1oraclev1", 7, "1.0.0")).unwrap();
+        let res = registry::execute(deps.as_mut(), env_at(200), info, set("terra1oraclev2", 9, "2.0.0")).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "previous_address" && attr.value == "terra1oraclev1"));
+
+        let query_msg = QueryMsg::GetContractInfo { name: "oracle".to_string(), network: None };
+        let res: ContractInfoResponse = from_json(registry::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.entry.address, "terra1oraclev2");
+        assert_eq!(res.entry.code_id, Some(9));
+        assert_eq!(res.entry.deploy_height, 200);
+
+        let history = |start_after: Option<u32>, limit: Option<u32>| -> Vec<String> {
+            let query_msg = QueryMsg::GetContractHistory { name: "oracle".to_string(), network: None, start_after, limit };
+            let res: ContractHistoryResponse = from_json(registry::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+            res.history.iter().map(|change| change.entry.clone().unwrap().version.unwrap()).collect()
+        };
+        assert_eq!(history(None, None), vec!["1.0.0", "2.0.0"]);
+
+        // History pages by sequence number
+        assert_eq!(history(None, Some(1)), vec!["1.0.0"]);
+        assert_eq!(history(Some(0), Some(1)), vec!["2.0.0"]);
+        assert!(history(Some(1), None).is_empty());
+
+        // Point-in-time lookups see the entry in force at each height
+        let at = |height: u64| -> Option<String> {
+            let query_msg = QueryMsg::GetContractAtHeight { name: "oracle".to_string(), network: None, height };
+            let res: ContractAtHeightResponse = from_json(registry::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+            res.entry.map(|entry| entry.address)
+        };
+        assert_eq!(at(99), None);
+        assert_eq!(at(100), Some("terra1oraclev1".to_string()));
+        assert_eq!(at(199), Some("terra1oraclev1".to_string()));
+        assert_eq!(at(500), Some("terra1oraclev2".to_string()));
+    }
+
+    #[test]
+    fn test_registry_batch_updates_and_removal() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("admin", &[]);
+        registry::instantiate(deps.as_mut(), localnet_env(), info.clone(), InstantiateMsg { admin: None, chain_id_mappings: None }).unwrap();
+
+        // The whole suite is registered in one transaction, and a bad address rejects all of it
+        let entries: Vec<(String, String)> = (0..5).map(|i| (format!("contract_{}", i), format!("terra1contract{}", i))).collect();
+        let mut bad_entries = entries.clone();
+        bad_entries.push(("bad".to_string(), "x".to_string()));
+        let msg = ExecuteMsg::SetContractAddresses { network: None, entries: bad_entries };
+        assert!(registry::execute(deps.as_mut(), localnet_env(), info.clone(), msg).is_err());
+
+        let msg = ExecuteMsg::SetContractAddresses { network: None, entries };
+        assert!(registry::execute(deps.as_mut(), localnet_env(), mock_info("anyone", &[]), msg.clone()).is_err());
+        registry::execute(deps.as_mut(), localnet_env(), info.clone(), msg).unwrap();
+
+        // Listings are paginated by name
+        let list = |deps: cosmwasm_std::Deps, start_after: Option<&str>, limit: u32| -> Vec<String> {
+            let query_msg = QueryMsg::GetAllContracts {
+                network: None,
+                start_after: start_after.map(|name| name.to_string()),
+                limit: Some(limit),
+            };
+            let res: AllContractsResponse = from_json(registry::query(deps, localnet_env(), query_msg).unwrap()).unwrap();
+            res.contracts.into_iter().map(|contract| contract.name).collect()
+        };
+        assert_eq!(list(deps.as_ref(), None, 2), vec!["contract_0", "contract_1"]);
+        assert_eq!(list(deps.as_ref(), Some("contract_1"), 10), vec!["contract_2", "contract_3", "contract_4"]);
+
+        // Stale names can be removed, but their history remains
+        let mut env = localnet_env();
+        env.block.height += 10;
+        let remove = ExecuteMsg::RemoveContractAddress { network: None, name: "contract_4".to_string() };
+        registry::execute(deps.as_mut(), env.clone(), info.clone(), remove.clone()).unwrap();
+        assert!(registry::execute(deps.as_mut(), env.clone(), info, remove).is_err());
+
+        assert_eq!(list(deps.as_ref(), Some("contract_3"), 10), Vec::<String>::new());
+        let query_msg = QueryMsg::GetContractAddress { name: "contract_4".to_string(), network: None };
+        assert!(registry::query(deps.as_ref(), localnet_env(), query_msg).is_err());
+
+        let at = |height: u64| -> Option<String> {
+            let query_msg = QueryMsg::GetContractAtHeight { name: "contract_4".to_string(), network: None, height };
+            let res: ContractAtHeightResponse = from_json(registry::query(deps.as_ref(), localnet_env(), query_msg).unwrap()).unwrap();
+            res.entry.map(|entry| entry.address)
+        };
+        assert_eq!(at(localnet_env().block.height), Some("terra1contract4".to_string()));
+        assert_eq!(at(env.block.height), None);
+    }
 }
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_json, Addr, Decimal, Env, OwnedDeps, Uint128};
+    use arbitrage_module::{ExecuteMsg, InstantiateMsg, QueryMsg};
+    use equilibria_smart_contracts::error::ContractError;
+    use equilibria_smart_contracts::oracle::PoolModel;
+    use equilibria_smart_contracts::roles::{HasRoleResponse, Role, RoleMembersResponse};
+
+    // The default chain id mappings don't cover mock_env's chain id
+    fn localnet_env() -> Env {
+        let mut env = mock_env();
+        env.block.chain_id = "localterra".to_string();
+        env
+    }
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            reward_percentage: Decimal::percent(10),
+            eqa_token: Addr::unchecked("eqa_token"),
+            oracle: Addr::unchecked("oracle"),
+            eqa_denom: "ueqa".to_string(),
+            collateral_denom: "uusdc".to_string(),
+            max_reward_per_block: Uint128::new(3),
+            dex_pair: Addr::unchecked("pair"),
+            pool_model: PoolModel::ConstantProduct,
+            pool_fee: Decimal::zero(),
+            chain_id_mappings: None,
+        };
+        arbitrage_module::instantiate(deps.as_mut(), localnet_env(), mock_info("owner", &[]), msg).unwrap();
+
+        deps
+    }
+
+    fn grant(role: Role, address: &str) -> ExecuteMsg {
+        ExecuteMsg::GrantRole { role, address: address.to_string() }
+    }
+
+    fn has_role(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, role: Role, address: &str) -> bool {
+        let msg = QueryMsg::HasRole { role, address: address.to_string() };
+        let res: HasRoleResponse = from_json(arbitrage_module::query(deps.as_ref(), localnet_env(), msg).unwrap()).unwrap();
+        res.has_role
+    }
+
+    #[test]
+    fn test_roles_gate_execute_handlers() {
+        let mut deps = setup();
+        let env = localnet_env();
+        let set_percentage = ExecuteMsg::UpdateRewardPercentage { reward_percentage: Decimal::percent(20) };
+        let set_max_reward = ExecuteMsg::UpdateMaxRewardPerBlock { max_reward_per_block: Uint128::new(5) };
+
+        // The owner holds every role without being listed
+        assert!(has_role(&deps, Role::FeeManager, "owner"));
+        let err = arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("fees", &[]), set_percentage.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "fee_manager"));
+
+        // A fee manager may change the reward percentage, but not admin settings
+        arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), grant(Role::FeeManager, "fees")).unwrap();
+        arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("fees", &[]), set_percentage.clone()).unwrap();
+        let err = arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("fees", &[]), set_max_reward.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "admin"));
+
+        // Revoking takes the permission away again
+        let revoke = ExecuteMsg::RevokeRole { role: Role::FeeManager, address: "fees".to_string() };
+        arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), revoke.clone()).unwrap();
+        assert!(!has_role(&deps, Role::FeeManager, "fees"));
+        assert!(arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("fees", &[]), set_percentage).is_err());
+
+        // Revoking a role the address does not hold is an error
+        let err = arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), revoke).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { .. }));
+
+        // Admins pass admin checks
+        arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), grant(Role::Admin, "ops")).unwrap();
+        arbitrage_module::execute(deps.as_mut(), env, mock_info("ops", &[]), set_max_reward).unwrap();
+    }
+
+    #[test]
+    fn test_only_owner_manages_admins() {
+        let mut deps = setup();
+        let env = localnet_env();
+
+        // Nobody without the admin role can grant roles
+        let err = arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("ops", &[]), grant(Role::Guardian, "ops")).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { .. }));
+
+        // An admin manages every other role
+        arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), grant(Role::Admin, "ops")).unwrap();
+        for guardian in ["guardian_b", "guardian_a", "guardian_c"] {
+            arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("ops", &[]), grant(Role::Guardian, guardian)).unwrap();
+        }
+
+        // ...but cannot create or remove other admins
+        let err = arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("ops", &[]), grant(Role::Admin, "friend")).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let revoke = ExecuteMsg::RevokeRole { role: Role::Admin, address: "ops".to_string() };
+        assert!(arbitrage_module::execute(deps.as_mut(), env.clone(), mock_info("ops", &[]), revoke).is_err());
+
+        // Members are listed in address order, a page at a time
+        let msg = QueryMsg::RoleMembers { role: Role::Guardian, start_after: None, limit: Some(2) };
+        let res: RoleMembersResponse = from_json(arbitrage_module::query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.members, vec![Addr::unchecked("guardian_a"), Addr::unchecked("guardian_b")]);
+
+        let msg = QueryMsg::RoleMembers { role: Role::Guardian, start_after: Some("guardian_b".to_string()), limit: None };
+        let res: RoleMembersResponse = from_json(arbitrage_module::query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(res.members, vec![Addr::unchecked("guardian_c")]);
+        assert!(!has_role(&deps, Role::Guardian, "ops"));
+    }
+
+    #[test]
+    fn test_guardians_pause_but_cannot_resume() {
+        let env = localnet_env();
+
+        // Guardians may disable collateral, but only the admin enables it again
+        let mut collateral = mock_dependencies();
+        let msg = collateral_manager::InstantiateMsg {
+            admin: None,
+            registry_address: "registry".to_string(),
+            register_cross_chain: Some(false),
+            chain_id_mappings: None,
+        };
+        collateral_manager::instantiate(collateral.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+        let grant = collateral_manager::ExecuteMsg::GrantRole { role: Role::Guardian, address: "guardian".to_string() };
+        collateral_manager::execute(collateral.as_mut(), env.clone(), mock_info("owner", &[]), grant).unwrap();
+
+        let set_enabled = |enabled: bool| collateral_manager::ExecuteMsg::SetCollateralEnabled {
+            asset: "noble_usdc".to_string(),
+            enabled,
+        };
+        collateral_manager::execute(collateral.as_mut(), env.clone(), mock_info("guardian", &[]), set_enabled(false)).unwrap();
+        let err = collateral_manager::execute(collateral.as_mut(), env.clone(), mock_info("guardian", &[]), set_enabled(true)).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "admin"));
+        collateral_manager::execute(collateral.as_mut(), env.clone(), mock_info("owner", &[]), set_enabled(true)).unwrap();
+
+        // Guardians may pause liquidations, but not resume them or change anything else
+        let mut liquidation = mock_dependencies();
+        let msg = liquidation_engine::InstantiateMsg { threshold_ratio: 110, liquidation_fee: None, oracle_address: None, chain_id_mappings: None };
+        liquidation_engine::instantiate(liquidation.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+        let grant = liquidation_engine::ExecuteMsg::GrantRole { role: Role::Guardian, address: "guardian".to_string() };
+        liquidation_engine::execute(liquidation.as_mut(), env.clone(), mock_info("owner", &[]), grant).unwrap();
+
+        let update = |is_active: bool, liquidation_fee: Option<u64>| liquidation_engine::ExecuteMsg::UpdateConfig {
+            oracle_address: None,
+            threshold_ratio: None,
+            liquidation_fee,
+            is_active: Some(is_active),
+        };
+        let err = liquidation_engine::execute(liquidation.as_mut(), env.clone(), mock_info("anyone", &[]), update(false, None)).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "guardian"));
+        liquidation_engine::execute(liquidation.as_mut(), env.clone(), mock_info("guardian", &[]), update(false, None)).unwrap();
+        for msg in [update(true, None), update(false, Some(0))] {
+            let err = liquidation_engine::execute(liquidation.as_mut(), env.clone(), mock_info("guardian", &[]), msg).unwrap_err();
+            assert!(matches!(err, ContractError::MissingRole { role } if role == "admin"));
+        }
+        liquidation_engine::execute(liquidation.as_mut(), env, mock_info("owner", &[]), update(true, None)).unwrap();
+    }
+}